@@ -0,0 +1,269 @@
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::ruby_modules;
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, TimeDelta, Weekday};
+use chrono_tz::Tz;
+use magnus::{Error, Symbol};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// The days that are worked: every day, other than those of the weekend and the holidays.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BusinessCalendar {
+    pub(crate) weekend: Vec<Weekday>,
+    pub(crate) holidays: BTreeSet<NaiveDate>,
+}
+
+impl Default for BusinessCalendar {
+    fn default() -> Self {
+        return BusinessCalendar {
+            weekend: vec![Weekday::Sat, Weekday::Sun],
+            holidays: BTreeSet::new(),
+        };
+    }
+}
+
+impl BusinessCalendar {
+    pub(crate) fn new(
+        weekend_symbols: Vec<Symbol>,
+        holiday_dates: Vec<String>,
+    ) -> Result<BusinessCalendar, String> {
+        let weekend = weekend_symbols
+            .iter()
+            .map(|weekday_symbol| {
+                weekday_symbol
+                    .to_string()
+                    .parse::<Weekday>()
+                    .map_err(|_| format!("Unsupported weekday {}", weekday_symbol))
+            })
+            .collect::<Result<Vec<Weekday>, String>>()?;
+        let holidays = holiday_dates
+            .iter()
+            .map(|holiday_date| {
+                NaiveDate::parse_from_str(holiday_date, "%Y-%m-%d")
+                    .map_err(|_| format!("Unsupported holiday date {}", holiday_date))
+            })
+            .collect::<Result<BTreeSet<NaiveDate>, String>>()?;
+
+        return BusinessCalendar::from_parts(weekend, holidays);
+    }
+
+    pub(crate) fn from_parts(
+        weekend: Vec<Weekday>,
+        holidays: BTreeSet<NaiveDate>,
+    ) -> Result<BusinessCalendar, String> {
+        let mut weekend = weekend;
+        weekend.sort_by_key(|weekday| weekday.num_days_from_monday());
+        weekend.dedup();
+
+        if weekend.len() >= 7 {
+            return Err(
+                "A business calendar must have at least one business day a week".to_string(),
+            );
+        }
+
+        return Ok(BusinessCalendar { weekend, holidays });
+    }
+
+    fn is_weekend(&self, date: &NaiveDate) -> bool {
+        return self.weekend.contains(&date.weekday());
+    }
+
+    pub(crate) fn is_business_day(&self, date: &NaiveDate) -> bool {
+        return !self.is_weekend(date) && !self.holidays.contains(date);
+    }
+
+    // A number of consecutive days that always includes a business day: enough whole weeks for
+    // the business days that the weekend leaves them to outnumber the holidays.
+    fn business_day_horizon_in_days(&self) -> u64 {
+        let business_days_in_week = (7 - self.weekend.len() as u64).max(1);
+
+        return 7 * (self.holidays.len() as u64 / business_days_in_week + 1);
+    }
+
+    /// The first business day after (or, optionally, on) the given date.
+    pub(crate) fn next_business_day(&self, date: &NaiveDate, inclusive: bool) -> Option<NaiveDate> {
+        let first_day_offset = if inclusive { 0 } else { 1 };
+
+        return (first_day_offset..(first_day_offset + self.business_day_horizon_in_days()))
+            .filter_map(|day_offset| date.checked_add_days(Days::new(day_offset)))
+            .find(|examined_date| self.is_business_day(examined_date));
+    }
+
+    /// The last business day before (or, optionally, on) the given date.
+    pub(crate) fn previous_business_day(
+        &self,
+        date: &NaiveDate,
+        inclusive: bool,
+    ) -> Option<NaiveDate> {
+        let first_day_offset = if inclusive { 0 } else { 1 };
+
+        return (first_day_offset..(first_day_offset + self.business_day_horizon_in_days()))
+            .filter_map(|day_offset| date.checked_sub_days(Days::new(day_offset)))
+            .find(|examined_date| self.is_business_day(examined_date));
+    }
+
+    /// The business day that the given date is moved to under the given convention; business
+    /// days are left as they are.
+    pub(crate) fn roll(&self, date: &NaiveDate, roll_convention: &RollConvention) -> NaiveDate {
+        let following = || self.next_business_day(date, true).unwrap_or(*date);
+        let preceding = || self.previous_business_day(date, true).unwrap_or(*date);
+
+        return match roll_convention {
+            RollConvention::Following => following(),
+            RollConvention::Preceding => preceding(),
+            RollConvention::ModifiedFollowing => {
+                let rolled_date = following();
+                if rolled_date.month() == date.month() {
+                    rolled_date
+                } else {
+                    preceding()
+                }
+            }
+            RollConvention::ModifiedPreceding => {
+                let rolled_date = preceding();
+                if rolled_date.month() == date.month() {
+                    rolled_date
+                } else {
+                    following()
+                }
+            }
+        };
+    }
+
+    /// The furthest that a date can be rolled, in either direction.
+    pub(crate) fn max_roll(&self) -> TimeDelta {
+        return TimeDelta::days(self.business_day_horizon_in_days() as i64);
+    }
+
+    /// The most business days that any month can have, before its holidays; a month has five
+    /// of (at most) three of its weekdays, and four of the rest.
+    pub(crate) fn max_business_days_in_month(&self) -> i32 {
+        let business_days_in_week = 7 - self.weekend.len() as i32;
+        let business_days_in_three_days = (0..7)
+            .map(|weekday_offset| {
+                (weekday_offset..weekday_offset + 3)
+                    .filter(|day_offset| {
+                        !self
+                            .weekend
+                            .iter()
+                            .any(|weekday| weekday.num_days_from_monday() == day_offset % 7)
+                    })
+                    .count() as i32
+            })
+            .max()
+            .unwrap_or(0);
+
+        return business_days_in_week * 4 + business_days_in_three_days;
+    }
+
+    /// The business days of the month that begins on the given date, in ascending order.
+    pub(crate) fn business_days_in_month(&self, first_of_month: NaiveDate) -> Vec<NaiveDate> {
+        return first_of_month
+            .iter_days()
+            .take_while(|date| date.month() == first_of_month.month())
+            .filter(|date| self.is_business_day(date))
+            .collect();
+    }
+
+    /// The date of the nth business day of the month that begins on the given date, if the month
+    /// has that many; nth business days are 0-indexed from the start of the month, or count back
+    /// from its end if negative (-1 being the last).
+    pub(crate) fn nth_business_day_in_month(
+        &self,
+        first_of_month: NaiveDate,
+        nth_business_day: i32,
+    ) -> Option<NaiveDate> {
+        let business_days = self.business_days_in_month(first_of_month);
+
+        return if nth_business_day >= 0 {
+            business_days.get(nth_business_day as usize).copied()
+        } else {
+            business_days
+                .len()
+                .checked_sub(nth_business_day.unsigned_abs() as usize)
+                .map(|index| business_days[index])
+        };
+    }
+
+    /// The number of business days before the given date, counted from the start of the
+    /// proleptic Gregorian calendar; consecutive business days have consecutive ordinals.
+    pub(crate) fn business_day_ordinal(&self, date: &NaiveDate) -> i64 {
+        // The first day of the common era was a Monday, so the days since then are counted
+        // in weeks that begin on Mondays.
+        let days_since_ce = date.num_days_from_ce() as i64 - 1;
+        let business_days_in_week = 7 - self.weekend.len() as i64;
+        let business_days_in_last_week = (0..days_since_ce.rem_euclid(7))
+            .filter(|weekday_offset| {
+                !self
+                    .weekend
+                    .iter()
+                    .any(|weekday| weekday.num_days_from_monday() as i64 == *weekday_offset)
+            })
+            .count() as i64;
+        let weekday_holidays = self
+            .holidays
+            .range(..*date)
+            .filter(|holiday| !self.is_weekend(holiday))
+            .count() as i64;
+
+        return days_since_ce.div_euclid(7) * business_days_in_week + business_days_in_last_week
+            - weekday_holidays;
+    }
+}
+
+/// How a date that is not a business day is moved onto one, as in the business day conventions
+/// of financial contracts.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RollConvention {
+    /// To the first business day after the date.
+    Following,
+    /// To the first business day after the date, unless that is in the following month, in
+    /// which case to the last business day before the date.
+    ModifiedFollowing,
+    /// To the last business day before the date.
+    Preceding,
+    /// To the last business day before the date, unless that is in the previous month, in
+    /// which case to the first business day after the date.
+    ModifiedPreceding,
+}
+
+impl RollConvention {
+    pub(crate) fn from_symbol(roll_convention_symbol: Symbol) -> Result<RollConvention, Error> {
+        return match roll_convention_symbol.to_string().as_str() {
+            "following" => Ok(RollConvention::Following),
+            "modified_following" => Ok(RollConvention::ModifiedFollowing),
+            "preceding" => Ok(RollConvention::Preceding),
+            "modified_preceding" => Ok(RollConvention::ModifiedPreceding),
+            unsupported => Err(Error::new(
+                ruby_modules::argument_error(),
+                format!("Unsupported roll :{}", unsupported),
+            )),
+        };
+    }
+}
+
+/// Moves the occurrences of a series that fall on days that are not worked onto business days,
+/// rather than dropping them (e.g. so that a pay date on the 15th of the month is brought
+/// forward to the Friday when the 15th is a Saturday).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct BusinessDayRoll {
+    pub(crate) roll_convention: RollConvention,
+    // The calendar that the schedule had when the series was added.
+    pub(crate) business_calendar: BusinessCalendar,
+}
+
+impl BusinessDayRoll {
+    /// Moves the given time onto the business day that its date rolls to, at the given local
+    /// time of day.
+    pub(crate) fn roll(&self, datetime: &DateTime<Tz>, naive_time: NaiveTime) -> DateTime<Tz> {
+        let date = datetime.date_naive();
+        let rolled_date = self.business_calendar.roll(&date, &self.roll_convention);
+
+        if rolled_date == date {
+            return *datetime;
+        }
+
+        return datetime_on_date_safely(&datetime.timezone(), rolled_date, naive_time);
+    }
+}
@@ -1,36 +1,80 @@
-use chrono::{DateTime, NaiveTime, TimeDelta};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, TimeZone};
 use chrono_tz::Tz;
 use std::ops::Add;
 
+/// Advances the cursor by an exact amount of elapsed time, however many transitions in local
+/// time it crosses; e.g. a cursor at 1:59 AM advanced by a minute on the day that clocks are
+/// turned forwards lands at 3:00 AM.
 pub(crate) fn advance_time_safely(
     datetime_cursor: &DateTime<Tz>,
     time_delta: TimeDelta,
-    naive_time: NaiveTime,
 ) -> DateTime<Tz> {
-    let new_datetime_cursor = datetime_cursor
-        .checked_add_signed(time_delta)
-        .expect("Datetime must advance")
-        .with_time(naive_time)
-        .latest();
+    // We can't operate exclusively on DateTime<Tz> values, as it will lead to
+    // invalid or ambiguous times when crossing DST / Standard Time transitions.
+    return match datetime_cursor.checked_add_signed(time_delta) {
+        None => datetime_cursor
+            .to_utc()
+            .checked_add_signed(time_delta)
+            .expect("UTC datetime should advance")
+            .with_timezone(&datetime_cursor.timezone()),
+        Some(datetime_cursor) => datetime_cursor,
+    };
+}
 
-    return match new_datetime_cursor {
+pub(crate) fn datetime_on_date_safely(
+    time_zone: &Tz,
+    naive_date: NaiveDate,
+    naive_time: NaiveTime,
+) -> DateTime<Tz> {
+    return match time_zone
+        .from_local_datetime(&naive_date.and_time(naive_time))
+        .latest()
+    {
         None => {
-            let timezone = datetime_cursor.timezone();
-            let datetime_cursor_utc = datetime_cursor
-                .with_time(naive_time)
+            // As in `set_datetime_cursor_safely`, a requested time that falls in a gap
+            // in local time is translated one hour ahead, out of the gap.
+            time_zone
+                .from_local_datetime(&naive_date.and_time(naive_time.add(TimeDelta::hours(1))))
                 .latest()
-                .expect("Datetime must advance")
-                .to_utc();
-
-            return datetime_cursor_utc
-                .checked_add_signed(time_delta)
-                .expect("UTC datetime should advance")
-                .with_timezone(&timezone);
+                .unwrap()
         }
-        Some(new_datetime_cursor) => new_datetime_cursor,
+        Some(datetime) => datetime,
     };
 }
 
+/// Returns the first instant on the given date whose local time is at or after the given time;
+/// unlike `datetime_on_date_safely`, a time that occurs twice resolves to its earlier instance,
+/// and a time that falls in a gap in local time resolves to the end of the gap.
+pub(crate) fn first_datetime_on_date_at_or_after(
+    time_zone: &Tz,
+    naive_date: NaiveDate,
+    naive_time: NaiveTime,
+) -> DateTime<Tz> {
+    let naive_datetime = naive_date.and_time(naive_time);
+
+    if let Some(datetime) = time_zone.from_local_datetime(&naive_datetime).earliest() {
+        return datetime;
+    }
+
+    // The end of the gap lies within the hour before the time that `datetime_on_date_safely`
+    // translates the requested time to; search it for the first instant past the gap.
+    let mut instant_after_gap =
+        datetime_on_date_safely(time_zone, naive_date, naive_time).timestamp();
+    let mut instant_before_gap = instant_after_gap - 3600;
+
+    while instant_after_gap - instant_before_gap > 1 {
+        let instant = instant_before_gap + (instant_after_gap - instant_before_gap) / 2;
+
+        if time_zone.timestamp_opt(instant, 0).unwrap().naive_local() >= naive_datetime {
+            instant_after_gap = instant;
+        } else {
+            instant_before_gap = instant;
+        }
+    }
+
+    return time_zone.timestamp_opt(instant_after_gap, 0).unwrap();
+}
+
 pub(crate) fn set_datetime_cursor_safely(
     datetime_cursor: DateTime<Tz>,
     naive_time: NaiveTime,
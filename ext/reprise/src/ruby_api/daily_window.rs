@@ -0,0 +1,142 @@
+use crate::ruby_api::clock::{datetime_on_date_safely, first_datetime_on_date_at_or_after};
+use crate::ruby_api::recurring_series::sub_daily::seek_by_steps;
+use crate::ruby_api::time_of_day::TimeOfDay;
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, Weekday};
+use chrono_tz::Tz;
+use magnus::Symbol;
+use serde::{Deserialize, Serialize};
+
+// A step that rarely lands within the window (e.g. one that is just over a day long) would
+// otherwise be stepped into the window indefinitely; a cursor that remains outside of it
+// after this many attempts is simply not a candidate, and is stepped forward as usual.
+const MAX_WINDOWS_SOUGHT: usize = 8;
+
+/// Restricts a sub-daily series to the part of each day between two local times of day,
+/// and optionally to certain weekdays (e.g. every 15 minutes from 9 AM to 5 PM on weekdays).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DailyWindow {
+    // The local time of day that the window opens at; midnight, if left blank.
+    pub(crate) starts_at_time_of_day: Option<TimeOfDay>,
+    // The local time of day that the window closes at, exclusively; the end of the day,
+    // if left blank.
+    pub(crate) ends_at_time_of_day: Option<TimeOfDay>,
+    // The weekdays that the window opens on; every day, if left empty.
+    pub(crate) weekdays: Vec<Weekday>,
+}
+
+impl DailyWindow {
+    pub(crate) fn new(
+        starts_at_time_of_day: Option<TimeOfDay>,
+        ends_at_time_of_day: Option<TimeOfDay>,
+        weekday_symbols: Vec<Symbol>,
+    ) -> Result<DailyWindow, String> {
+        let weekdays = weekday_symbols
+            .iter()
+            .map(|weekday_symbol| {
+                weekday_symbol
+                    .to_string()
+                    .parse::<Weekday>()
+                    .map_err(|_| format!("Unsupported weekday {}", weekday_symbol))
+            })
+            .collect::<Result<Vec<Weekday>, String>>()?;
+
+        return DailyWindow::from_weekdays(starts_at_time_of_day, ends_at_time_of_day, weekdays);
+    }
+
+    pub(crate) fn from_weekdays(
+        starts_at_time_of_day: Option<TimeOfDay>,
+        ends_at_time_of_day: Option<TimeOfDay>,
+        weekdays: Vec<Weekday>,
+    ) -> Result<DailyWindow, String> {
+        if let (Some(starts_at_time_of_day), Some(ends_at_time_of_day)) =
+            (&starts_at_time_of_day, &ends_at_time_of_day)
+        {
+            if ends_at_time_of_day <= starts_at_time_of_day {
+                return Err("The daily window must end after it starts".to_string());
+            }
+        }
+
+        let mut weekdays = weekdays;
+        weekdays.sort_by_key(|weekday| weekday.num_days_from_monday());
+        weekdays.dedup();
+
+        return Ok(DailyWindow {
+            starts_at_time_of_day,
+            ends_at_time_of_day,
+            weekdays,
+        });
+    }
+
+    fn naive_starts_at_time(&self) -> NaiveTime {
+        return self
+            .starts_at_time_of_day
+            .as_ref()
+            .map_or(NaiveTime::MIN, |time_of_day| time_of_day.to_naive_time());
+    }
+
+    fn opens_on(&self, date: &NaiveDate) -> bool {
+        return self.weekdays.is_empty() || self.weekdays.contains(&date.weekday());
+    }
+
+    /// Whether the given time falls within the window, going by its local time of day.
+    pub(crate) fn contains(&self, datetime: &DateTime<Tz>) -> bool {
+        let naive_time = datetime.time();
+
+        return self.opens_on(&datetime.date_naive())
+            && naive_time >= self.naive_starts_at_time()
+            && self
+                .ends_at_time_of_day
+                .as_ref()
+                .map_or(true, |time_of_day| naive_time < time_of_day.to_naive_time());
+    }
+
+    // The first time after the given one that the window opens at.
+    fn next_opening_after(&self, datetime: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let date = datetime.date_naive();
+        let time_zone = datetime.timezone();
+        let naive_starts_at_time = self.naive_starts_at_time();
+
+        return (0..=7)
+            .filter_map(|day_offset| date.checked_add_days(Days::new(day_offset)))
+            .filter(|examined_date| self.opens_on(examined_date))
+            .flat_map(|examined_date| {
+                // On the day that clocks are turned back, the window opens at both instances
+                // of its opening time.
+                [
+                    first_datetime_on_date_at_or_after(
+                        &time_zone,
+                        examined_date,
+                        naive_starts_at_time,
+                    ),
+                    datetime_on_date_safely(&time_zone, examined_date, naive_starts_at_time),
+                ]
+            })
+            .find(|opening| opening > datetime);
+    }
+
+    /// Moves a cursor that falls outside of the window forward to the first of its steps that
+    /// falls within the window, without visiting the steps in between; the cursor is left
+    /// on the same grid of steps, so that the series' periods are unaffected.
+    pub(crate) fn seek_into(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        step_in_seconds: i64,
+    ) -> DateTime<Tz> {
+        let mut datetime_cursor = datetime_cursor.clone();
+
+        for _ in 0..MAX_WINDOWS_SOUGHT {
+            if self.contains(&datetime_cursor) {
+                break;
+            }
+
+            match self.next_opening_after(&datetime_cursor) {
+                Some(opening) => {
+                    datetime_cursor = seek_by_steps(&datetime_cursor, &opening, step_in_seconds)
+                }
+                None => break,
+            }
+        }
+
+        return datetime_cursor;
+    }
+}
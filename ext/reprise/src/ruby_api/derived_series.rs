@@ -0,0 +1,64 @@
+use crate::ruby_api::clock::{advance_time_safely, datetime_on_date_safely};
+use chrono::{DateTime, Days, Months, NaiveDate, TimeDelta};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// A signed offset from an occurrence. Its calendar part (months, then days) moves the
+/// occurrence to another date at the same local time of day, however many transitions in
+/// local time lie in between; its absolute part (seconds) then moves it by an exact amount
+/// of elapsed time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct OccurrenceOffset {
+    pub(crate) months: i32,
+    pub(crate) days: i64,
+    pub(crate) seconds: i64,
+}
+
+impl OccurrenceOffset {
+    pub(crate) fn apply(&self, datetime: &DateTime<Tz>) -> DateTime<Tz> {
+        let datetime = if self.months == 0 && self.days == 0 {
+            *datetime
+        } else {
+            let date = offset_date(datetime.date_naive(), self.months, self.days);
+            datetime_on_date_safely(&datetime.timezone(), date, datetime.time())
+        };
+
+        return advance_time_safely(&datetime, TimeDelta::seconds(self.seconds));
+    }
+
+    /// The furthest that the offset can move an occurrence, in either direction.
+    pub(crate) fn max_shift(&self) -> TimeDelta {
+        // A month lasts at most 31 days, and a transition in local time at most a day.
+        return TimeDelta::days(31 * self.months.unsigned_abs() as i64 + self.days.abs() + 1)
+            + TimeDelta::seconds(self.seconds.abs());
+    }
+}
+
+// N.B. A month later (or earlier) than a day that the month does not have (e.g. a month
+// after January 31st) is the last day of that month.
+fn offset_date(date: NaiveDate, months: i32, days: i64) -> NaiveDate {
+    let date = if months >= 0 {
+        date.checked_add_months(Months::new(months.unsigned_abs()))
+    } else {
+        date.checked_sub_months(Months::new(months.unsigned_abs()))
+    }
+    .expect("Offset date should be in range");
+
+    return if days >= 0 {
+        date.checked_add_days(Days::new(days.unsigned_abs()))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    }
+    .expect("Offset date should be in range");
+}
+
+/// A series whose occurrences are those of another series (its base, referred to by label),
+/// each moved by an offset; e.g. a reminder two days before each occurrence of a monthly
+/// board meeting. Every series that carries the base label is a base of the derived series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DerivedSeries {
+    pub(crate) base_label: String,
+    pub(crate) offset: OccurrenceOffset,
+    pub(crate) duration_in_seconds: i64,
+    pub(crate) label: Option<String>,
+}
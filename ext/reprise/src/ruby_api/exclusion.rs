@@ -1,6 +1,7 @@
 use crate::ruby_api::traits::HasOverlapAwareness;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct Exclusion {
     pub(crate) starts_at_unix_timestamp: i64,
     pub(crate) ends_at_unix_timestamp: i64,
@@ -0,0 +1,302 @@
+use crate::ruby_api::occurrence::Occurrence;
+use crate::ruby_api::recurring_series::monthly_by_nth_weekday::{
+    nth_weekday_in_month, validate_nth_weekday,
+};
+use chrono::{DateTime, Datelike, NaiveDate, TimeDelta, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+// Holidays relative to Easter (which falls between March 22nd and April 25th) are kept within
+// a year of it.
+const MAX_EASTER_OFFSET_IN_DAYS: i64 = 300;
+
+/// The date of Easter Sunday in the given year of the Gregorian calendar, by the anonymous
+/// Gregorian computus (as published by Meeus).
+pub(crate) fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    return NaiveDate::from_ymd_opt(year, month as u32, day as u32);
+}
+
+/// How a holiday's date is placed within a year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum HolidayDate {
+    /// On the same month and day every year (e.g. December 25th).
+    Fixed { month: u32, day: u32 },
+    /// On the nth weekday of a month (e.g. the fourth Thursday of November); 0-indexed from the
+    /// start of the month, or counting back from its end if negative.
+    NthWeekday {
+        month: u32,
+        weekday: Weekday,
+        nth_weekday: i32,
+    },
+    /// A number of days before (if negative) or after Easter Sunday (e.g. -2 for Good Friday).
+    Easter { offset_in_days: i64 },
+}
+
+impl HolidayDate {
+    fn validate(&self) -> Result<(), String> {
+        return match self {
+            HolidayDate::Fixed { month, day } => {
+                validate_month(*month)?;
+
+                // Every day of the month occurs in leap years.
+                if NaiveDate::from_ymd_opt(2000, *month, *day).is_none() {
+                    return Err(format!(
+                        "The day {} is out of range for the month {}",
+                        day, month
+                    ));
+                }
+
+                Ok(())
+            }
+            HolidayDate::NthWeekday {
+                month, nth_weekday, ..
+            } => {
+                validate_month(*month)?;
+                validate_nth_weekday(*nth_weekday)
+            }
+            HolidayDate::Easter { offset_in_days } => {
+                if offset_in_days.abs() > MAX_EASTER_OFFSET_IN_DAYS {
+                    return Err(format!(
+                        "The Easter offset {} is out of range; it must be between {} and {}",
+                        offset_in_days, -MAX_EASTER_OFFSET_IN_DAYS, MAX_EASTER_OFFSET_IN_DAYS
+                    ));
+                }
+
+                Ok(())
+            }
+        };
+    }
+
+    // The date that the holiday falls on in the given year, if it falls on one.
+    fn date_in_year(&self, year: i32) -> Option<NaiveDate> {
+        return match self {
+            HolidayDate::Fixed { month, day } => NaiveDate::from_ymd_opt(year, *month, *day),
+            HolidayDate::NthWeekday {
+                month,
+                weekday,
+                nth_weekday,
+            } => nth_weekday_in_month(
+                NaiveDate::from_ymd_opt(year, *month, 1)?,
+                *weekday,
+                *nth_weekday,
+            ),
+            HolidayDate::Easter { offset_in_days } => {
+                easter_sunday(year)?.checked_add_signed(TimeDelta::days(*offset_in_days))
+            }
+        };
+    }
+}
+
+fn validate_month(month: u32) -> Result<(), String> {
+    if !(1..=12).contains(&month) {
+        return Err(format!(
+            "The month {} is out of range; it must be between 1 and 12",
+            month
+        ));
+    }
+
+    return Ok(());
+}
+
+/// Which weekday, if any, a holiday that falls on a weekend is observed on, in addition to
+/// the holiday itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ObservedShift {
+    #[default]
+    None,
+    /// On the Friday before a Saturday holiday, or on the Monday after a Sunday one.
+    NearestWeekday,
+    /// On the first weekday after the holiday that is not already a holiday (or the observed
+    /// day of one), so that consecutive weekend holidays are observed on consecutive weekdays.
+    NextWeekday,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct HolidayRule {
+    pub(crate) name: String,
+    pub(crate) date: HolidayDate,
+    #[serde(default)]
+    pub(crate) observed: ObservedShift,
+    // The years that the holiday was first and last held in, if it was not always held.
+    #[serde(default)]
+    pub(crate) first_year: Option<i32>,
+    #[serde(default)]
+    pub(crate) last_year: Option<i32>,
+}
+
+impl HolidayRule {
+    fn validate(&self) -> Result<(), String> {
+        self.date.validate()?;
+
+        if let (Some(first_year), Some(last_year)) = (self.first_year, self.last_year) {
+            if last_year < first_year {
+                return Err(format!(
+                    "The holiday {} must be last held after it is first held",
+                    self.name
+                ));
+            }
+        }
+
+        return Ok(());
+    }
+
+    fn is_held_in(&self, year: i32) -> bool {
+        return self
+            .first_year
+            .map_or(true, |first_year| year >= first_year)
+            && self.last_year.map_or(true, |last_year| year <= last_year);
+    }
+}
+
+/// A set of public holidays, each placed in every year that it is held in by a rule, rather
+/// than listed date by date; a schedule excludes the occurrences that fall on them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct HolidayCalendar {
+    pub(crate) holidays: Vec<HolidayRule>,
+}
+
+impl HolidayCalendar {
+    pub(crate) fn from_json(json: &str) -> Result<HolidayCalendar, String> {
+        let holidays: Vec<HolidayRule> = serde_json::from_str(json)
+            .map_err(|e| format!("Unsupported holiday calendar: {}", e))?;
+
+        return HolidayCalendar::new(holidays);
+    }
+
+    pub(crate) fn new(holidays: Vec<HolidayRule>) -> Result<HolidayCalendar, String> {
+        for holiday in &holidays {
+            holiday.validate()?;
+        }
+
+        return Ok(HolidayCalendar { holidays });
+    }
+
+    // The dates of the holidays held in the given year, along with the days that they are
+    // observed on; observed days can fall in the previous or following year.
+    fn holidays_held_in(&self, year: i32) -> BTreeSet<NaiveDate> {
+        let holiday_dates: Vec<(NaiveDate, ObservedShift)> = self
+            .holidays
+            .iter()
+            .filter(|holiday| holiday.is_held_in(year))
+            .filter_map(|holiday| {
+                holiday
+                    .date
+                    .date_in_year(year)
+                    .map(|date| (date, holiday.observed))
+            })
+            .collect();
+        let mut holidays: BTreeSet<NaiveDate> =
+            holiday_dates.iter().map(|(date, _)| *date).collect();
+
+        let mut weekend_holiday_dates: Vec<&(NaiveDate, ObservedShift)> = holiday_dates
+            .iter()
+            .filter(|(date, _)| is_weekend(date))
+            .collect();
+        weekend_holiday_dates.sort_by_key(|(date, _)| *date);
+
+        for (date, observed) in weekend_holiday_dates {
+            let observed_date = match observed {
+                ObservedShift::None => None,
+                ObservedShift::NearestWeekday => match date.weekday() {
+                    Weekday::Sat => date.pred_opt(),
+                    _ => date.succ_opt(),
+                },
+                ObservedShift::NextWeekday => date.iter_days().skip(1).find(|examined_date| {
+                    !is_weekend(examined_date) && !holidays.contains(examined_date)
+                }),
+            };
+
+            holidays.extend(observed_date);
+        }
+
+        return holidays;
+    }
+
+    /// The dates of the holidays, and the days that they are observed on, within the given year.
+    pub(crate) fn holidays_in(&self, year: i32) -> BTreeSet<NaiveDate> {
+        return ((year - 1)..=(year + 1))
+            .flat_map(|held_in_year| self.holidays_held_in(held_in_year))
+            .filter(|date| date.year() == year)
+            .collect();
+    }
+}
+
+fn is_weekend(date: &NaiveDate) -> bool {
+    return matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+}
+
+/// Excludes the occurrences of a schedule that overlap with any of the (local) days that are
+/// holidays according to its holiday calendars. Unlike the schedule's sorted exclusions, the
+/// holidays are only placed in the years that the excluded occurrences are examined in.
+#[derive(Debug, Clone)]
+pub(crate) struct HolidayExclusions {
+    holiday_calendars: Vec<HolidayCalendar>,
+    time_zone: Tz,
+    holidays_by_year: HashMap<i32, BTreeSet<NaiveDate>>,
+}
+
+impl HolidayExclusions {
+    pub(crate) fn new(holiday_calendars: Vec<HolidayCalendar>, time_zone: Tz) -> HolidayExclusions {
+        return HolidayExclusions {
+            holiday_calendars,
+            time_zone,
+            holidays_by_year: HashMap::new(),
+        };
+    }
+
+    fn is_holiday(&mut self, date: &NaiveDate) -> bool {
+        let holiday_calendars = &self.holiday_calendars;
+
+        return self
+            .holidays_by_year
+            .entry(date.year())
+            .or_insert_with(|| {
+                holiday_calendars
+                    .iter()
+                    .flat_map(|holiday_calendar| holiday_calendar.holidays_in(date.year()))
+                    .collect()
+            })
+            .contains(date);
+    }
+
+    pub(crate) fn is_occurrence_excluded(&mut self, occurrence: &Occurrence) -> bool {
+        if self.holiday_calendars.is_empty() {
+            return false;
+        }
+
+        let local_date_at = |unix_timestamp: i64| {
+            DateTime::from_timestamp(unix_timestamp, 0)
+                .unwrap()
+                .with_timezone(&self.time_zone)
+                .date_naive()
+        };
+        let starts_on = local_date_at(occurrence.starts_at_unix_timestamp);
+        // An occurrence that ends at midnight does not overlap with the day that follows it.
+        let ends_on = local_date_at(
+            (occurrence.ends_at_unix_timestamp - 1).max(occurrence.starts_at_unix_timestamp),
+        );
+
+        return starts_on
+            .iter_days()
+            .take_while(|date| *date <= ends_on)
+            .any(|date| self.is_holiday(&date));
+    }
+}
@@ -0,0 +1,830 @@
+use crate::ruby_api::daily_window::DailyWindow;
+use crate::ruby_api::derived_series::DerivedSeries;
+use crate::ruby_api::occurrence::Occurrence;
+use crate::ruby_api::recurring_series::monthly_by_day::MissingDayPolicy;
+use crate::ruby_api::schedule::Schedule;
+use crate::ruby_api::time_of_day::TimeOfDay;
+use crate::ruby_api::traits::{Recurrable, RecurringSeries};
+use chrono::{DateTime, Months, Offset, TimeDelta, TimeZone, Utc, Weekday};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+
+const MAXIMUM_LINE_LENGTH_IN_OCTETS: usize = 75;
+
+/// Serializes a schedule into an iCalendar (RFC 5545) VCALENDAR document, with one VEVENT
+/// per recurring (or derived) series, and one per distinct duration and label of its one-off
+/// occurrences. Occurrences removed by the schedule's exclusions (or holidays), or by the
+/// series' own, are listed as EXDATEs on the VEVENT of the series that generated them.
+pub(crate) fn to_vcalendar(schedule: &Schedule) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!(
+            "PRODID:-//Reprise//Reprise {}//EN",
+            env!("CARGO_PKG_VERSION")
+        ),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    // Series are described in the time zones that they are expanded in, which are usually
+    // (but not necessarily, e.g. for imported events) the schedule's own.
+    let mut time_zones = vec![schedule.time_zone];
+
+    for series in &schedule.recurring_series {
+        let time_zone = series.get_series_options().time_zone();
+
+        if !time_zones.contains(&time_zone) {
+            time_zones.push(time_zone);
+        }
+    }
+
+    for time_zone in &time_zones {
+        lines.extend(vtimezone_lines(
+            time_zone,
+            &schedule.local_starts_at_datetime(),
+            &schedule
+                .local_ends_at_datetime()
+                .unwrap_or_else(|| open_ended_horizon(schedule)),
+        ));
+    }
+
+    let dtstamp = format_utc_date_time(&Utc::now());
+
+    for (index, series) in schedule.recurring_series.iter().enumerate() {
+        lines.extend(vevent_lines(schedule, index, series, &dtstamp));
+    }
+
+    // Derived series have no RRULE equivalent, as they are moved from the occurrences of
+    // their base series; their occurrences are listed as RDATEs instead.
+    for (index, derived_series) in schedule.derived_series.iter().enumerate() {
+        lines.extend(derived_vevent_lines(
+            schedule,
+            schedule.recurring_series.len() + index,
+            derived_series,
+            &dtstamp,
+        ));
+    }
+
+    let one_off_occurrences_starting_index =
+        schedule.recurring_series.len() + schedule.derived_series.len();
+
+    for (index, one_off_occurrences) in grouped_one_off_occurrences(schedule).iter().enumerate() {
+        lines.extend(one_off_vevent_lines(
+            schedule,
+            one_off_occurrences_starting_index + index,
+            one_off_occurrences,
+            &dtstamp,
+        ));
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    return lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<String>>()
+        .join("");
+}
+
+fn vevent_lines(
+    schedule: &Schedule,
+    index: usize,
+    series: &RecurringSeries,
+    dtstamp: &str,
+) -> Vec<String> {
+    let series_options = series.get_series_options();
+    let series_ends_at = series_options
+        .local_ends_at_datetime()
+        .or(schedule.local_ends_at_datetime());
+    let examined_window_ends_at = match series_ends_at {
+        None if series_options.count.is_none() => Some(open_ended_horizon(schedule)),
+        _ => series_ends_at,
+    };
+
+    let mut holiday_exclusions = schedule.holiday_exclusions();
+    let (excluded_occurrences, occurrences): (Vec<Occurrence>, Vec<Occurrence>) = series
+        .generate_occurrences(schedule.local_starts_at_datetime(), examined_window_ends_at)
+        .into_iter()
+        .partition(|o| {
+            series_options.is_occurrence_excluded(o)
+                || schedule.sorted_exclusions.is_occurrence_excluded(o)
+                || holiday_exclusions.is_occurrence_excluded(o)
+        });
+
+    // An RRULE always counts its DTSTART as the first occurrence, even when it is excluded.
+    let mut all_occurrences = occurrences
+        .iter()
+        .chain(excluded_occurrences.iter())
+        .collect::<Vec<&Occurrence>>();
+    all_occurrences.sort_by_key(|o| o.starts_at_unix_timestamp);
+
+    let Some(first_occurrence) = all_occurrences.first() else {
+        return Vec::new();
+    };
+
+    let time_zone = series_options.time_zone();
+    let mut lines = vevent_header_lines(
+        schedule,
+        &time_zone,
+        index,
+        first_occurrence,
+        series_options.duration_in_seconds,
+        dtstamp,
+    );
+
+    match rrule_parts(series) {
+        Some(mut rrule_parts) => {
+            let interval =
+                (series_options.interval.max(1) as u64).saturating_mul(interval_multiplier(series));
+
+            if interval > 1 {
+                rrule_parts.push(format!("INTERVAL={}", interval));
+            }
+
+            // When the count rather than the series' end bookend is what limited the series,
+            // we can express the same limit as an RRULE count.
+            let is_limited_by_count = series_options
+                .count
+                .is_some_and(|count| all_occurrences.len() as u64 == count);
+
+            if is_limited_by_count {
+                rrule_parts.push(format!("COUNT={}", all_occurrences.len()));
+            } else if let Some(series_ends_at) = series_ends_at {
+                rrule_parts.push(format!(
+                    "UNTIL={}",
+                    format_utc_date_time(&series_ends_at.to_utc())
+                ));
+            }
+
+            lines.push(format!("RRULE:{}", rrule_parts.join(";")));
+        }
+        None => {
+            // The series cannot be described as an RRULE; list its occurrences explicitly instead.
+            lines.extend(rdate_lines(&time_zone, &all_occurrences));
+        }
+    }
+
+    lines.extend(vevent_footer_lines(
+        &time_zone,
+        &excluded_occurrences,
+        series_options.label(),
+    ));
+
+    return lines;
+}
+
+fn derived_vevent_lines(
+    schedule: &Schedule,
+    index: usize,
+    derived_series: &DerivedSeries,
+    dtstamp: &str,
+) -> Vec<String> {
+    let examined_window_ends_at = schedule
+        .local_ends_at_datetime()
+        .unwrap_or_else(|| open_ended_horizon(schedule));
+
+    let mut holiday_exclusions = schedule.holiday_exclusions();
+    let (excluded_occurrences, occurrences): (Vec<Occurrence>, Vec<Occurrence>) = schedule
+        .derived_occurrences(derived_series, examined_window_ends_at)
+        .into_iter()
+        .partition(|o| {
+            schedule.sorted_exclusions.is_occurrence_excluded(o)
+                || holiday_exclusions.is_occurrence_excluded(o)
+        });
+
+    let mut all_occurrences = occurrences
+        .iter()
+        .chain(excluded_occurrences.iter())
+        .collect::<Vec<&Occurrence>>();
+    all_occurrences.sort_by_key(|o| o.starts_at_unix_timestamp);
+
+    let Some(first_occurrence) = all_occurrences.first() else {
+        return Vec::new();
+    };
+
+    let mut lines = vevent_header_lines(
+        schedule,
+        &schedule.time_zone,
+        index,
+        first_occurrence,
+        derived_series.duration_in_seconds,
+        dtstamp,
+    );
+    lines.extend(rdate_lines(&schedule.time_zone, &all_occurrences));
+    lines.extend(vevent_footer_lines(
+        &schedule.time_zone,
+        &excluded_occurrences,
+        derived_series.label.clone(),
+    ));
+
+    return lines;
+}
+
+/// Groups the one-off occurrences within the schedule's bookends by their duration and label,
+/// which a single VEVENT shares between its DTSTART and RDATEs.
+fn grouped_one_off_occurrences(schedule: &Schedule) -> Vec<Vec<&Occurrence>> {
+    let mut grouped_occurrences: Vec<Vec<&Occurrence>> = Vec::new();
+
+    for occurrence in schedule.one_off_occurrences.iter().filter(|o| {
+        o.starts_at_unix_timestamp >= schedule.starts_at_unix_timestamp
+            && schedule
+                .ends_at_unix_timestamp
+                .map_or(true, |ends_at| o.starts_at_unix_timestamp <= ends_at)
+    }) {
+        let duration_in_seconds =
+            occurrence.ends_at_unix_timestamp - occurrence.starts_at_unix_timestamp;
+        let group = grouped_occurrences.iter_mut().find(|group| {
+            group[0].ends_at_unix_timestamp - group[0].starts_at_unix_timestamp
+                == duration_in_seconds
+                && group[0].label == occurrence.label
+        });
+
+        match group {
+            Some(group) => group.push(occurrence),
+            None => grouped_occurrences.push(vec![occurrence]),
+        }
+    }
+
+    return grouped_occurrences;
+}
+
+fn one_off_vevent_lines(
+    schedule: &Schedule,
+    index: usize,
+    all_occurrences: &[&Occurrence],
+    dtstamp: &str,
+) -> Vec<String> {
+    let first_occurrence = all_occurrences[0];
+    let mut holiday_exclusions = schedule.holiday_exclusions();
+    let excluded_occurrences: Vec<Occurrence> = all_occurrences
+        .iter()
+        .filter(|o| {
+            schedule.sorted_exclusions.is_occurrence_excluded(o)
+                || holiday_exclusions.is_occurrence_excluded(o)
+        })
+        .map(|o| (*o).clone())
+        .collect();
+
+    let mut lines = vevent_header_lines(
+        schedule,
+        &schedule.time_zone,
+        index,
+        first_occurrence,
+        first_occurrence.ends_at_unix_timestamp - first_occurrence.starts_at_unix_timestamp,
+        dtstamp,
+    );
+    lines.extend(rdate_lines(&schedule.time_zone, all_occurrences));
+    lines.extend(vevent_footer_lines(
+        &schedule.time_zone,
+        &excluded_occurrences,
+        first_occurrence.label.clone(),
+    ));
+
+    return lines;
+}
+
+fn vevent_header_lines(
+    schedule: &Schedule,
+    time_zone: &Tz,
+    index: usize,
+    first_occurrence: &Occurrence,
+    duration_in_seconds: i64,
+    dtstamp: &str,
+) -> Vec<String> {
+    return vec![
+        "BEGIN:VEVENT".to_string(),
+        format!(
+            "UID:{}-{}@reprise",
+            schedule.starts_at_unix_timestamp, index
+        ),
+        format!("DTSTAMP:{}", dtstamp),
+        format!(
+            "DTSTART;{}",
+            format_local_date_time(time_zone, first_occurrence.starts_at_unix_timestamp)
+        ),
+        format!("DURATION:{}", format_duration(duration_in_seconds)),
+    ];
+}
+
+/// Lists every occurrence after the first (which is the DTSTART) as an RDATE.
+fn rdate_lines(time_zone: &Tz, all_occurrences: &[&Occurrence]) -> Vec<String> {
+    if all_occurrences.len() <= 1 {
+        return Vec::new();
+    }
+
+    return vec![format!(
+        "RDATE;{}",
+        format_local_date_times(
+            time_zone,
+            all_occurrences
+                .iter()
+                .skip(1)
+                .map(|o| o.starts_at_unix_timestamp)
+        )
+    )];
+}
+
+fn vevent_footer_lines(
+    time_zone: &Tz,
+    excluded_occurrences: &[Occurrence],
+    label: Option<String>,
+) -> Vec<String> {
+    let mut lines = vec![];
+
+    if !excluded_occurrences.is_empty() {
+        lines.push(format!(
+            "EXDATE;{}",
+            format_local_date_times(
+                time_zone,
+                excluded_occurrences
+                    .iter()
+                    .map(|o| o.starts_at_unix_timestamp)
+            )
+        ));
+    }
+
+    if let Some(label) = label {
+        lines.push(format!("SUMMARY:{}", escape_text(&label)));
+    }
+
+    lines.push("END:VEVENT".to_string());
+
+    return lines;
+}
+
+/// Returns the FREQ and BY* parts of the RRULE that describes the series, if it can be
+/// expressed as one.
+fn rrule_parts(series: &RecurringSeries) -> Option<Vec<String>> {
+    // Occurrences that are rolled onto business days have no RRULE equivalent either.
+    if series.get_series_options().roll.is_some() {
+        return None;
+    }
+
+    let mut rrule_parts = match series {
+        RecurringSeries::Secondly(secondly) => {
+            let mut parts = vec![fixed_step_frequency_part(secondly.step_in_seconds())];
+            parts.extend(daily_window_parts(&secondly.daily_window)?);
+            Some(parts)
+        }
+        RecurringSeries::Minutely(minutely) => {
+            let mut parts = vec![fixed_step_frequency_part(minutely.step_in_seconds())];
+            parts.extend(daily_window_parts(&minutely.daily_window)?);
+            Some(parts)
+        }
+        RecurringSeries::Hourly(hourly) => {
+            let mut parts = vec![fixed_step_frequency_part(hourly.step_in_seconds())];
+            parts.extend(daily_window_parts(&hourly.daily_window)?);
+            Some(parts)
+        }
+        RecurringSeries::Daily(_) => Some(vec!["FREQ=DAILY".to_string()]),
+        // Holidays and intervals counted in business days have no RRULE equivalent, so
+        // business day series are listed as RDATEs instead.
+        RecurringSeries::BusinessDaily(_) => None,
+        RecurringSeries::Weekly(weekly) => {
+            let weekdays: Vec<&str> = weekly.weekdays.iter().map(format_weekday).collect();
+            let mut parts = vec![
+                "FREQ=WEEKLY".to_string(),
+                format!("BYDAY={}", weekdays.join(",")),
+            ];
+
+            if weekly.week_start != Weekday::Mon {
+                parts.push(format!("WKST={}", format_weekday(&weekly.week_start)));
+            }
+
+            Some(parts)
+        }
+        RecurringSeries::MonthlyByDay(monthly) => {
+            let day_numbers: Vec<String> = monthly
+                .day_numbers
+                .iter()
+                .map(|day_number| day_number.to_string())
+                .collect();
+            let mut parts = vec![
+                "FREQ=MONTHLY".to_string(),
+                format!("BYMONTHDAY={}", day_numbers.join(",")),
+            ];
+
+            parts.extend(skip_parts(&monthly.missing_day_policy));
+
+            Some(parts)
+        }
+        RecurringSeries::MonthlyByNthWeekday(monthly) => {
+            let by_day: Vec<String> = monthly
+                .nth_weekday_pairs()
+                .map(|(weekday, nth_weekday)| format_nth_weekday(&weekday, nth_weekday))
+                .collect();
+
+            Some(vec![
+                "FREQ=MONTHLY".to_string(),
+                format!("BYDAY={}", by_day.join(",")),
+            ])
+        }
+        RecurringSeries::MonthlyByWeekday(monthly) => {
+            let weekdays: Vec<&str> = monthly.weekdays.iter().map(format_weekday).collect();
+
+            Some(vec![
+                "FREQ=MONTHLY".to_string(),
+                format!("BYDAY={}", weekdays.join(",")),
+            ])
+        }
+        RecurringSeries::MonthlyByNthBusinessDay(_) => None,
+        RecurringSeries::AnnuallyByDay(annually) => Some(vec![
+            "FREQ=YEARLY".to_string(),
+            format!("BYYEARDAY={}", annually.day_number),
+        ]),
+        RecurringSeries::AnnuallyByMonthDay(annually) => {
+            let mut parts = vec![
+                "FREQ=YEARLY".to_string(),
+                format!("BYMONTH={}", annually.month),
+                format!("BYMONTHDAY={}", annually.day),
+            ];
+            parts.extend(skip_parts(&annually.missing_day_policy));
+
+            Some(parts)
+        }
+        RecurringSeries::AnnuallyByIsoWeek(annually) => {
+            let week_numbers: Vec<String> = annually
+                .week_numbers
+                .iter()
+                .map(|week_number| week_number.to_string())
+                .collect();
+            let weekdays: Vec<&str> = annually.weekdays.iter().map(format_weekday).collect();
+
+            Some(vec![
+                "FREQ=YEARLY".to_string(),
+                format!("BYWEEKNO={}", week_numbers.join(",")),
+                format!("BYDAY={}", weekdays.join(",")),
+            ])
+        }
+        RecurringSeries::AnnuallyByNthWeekday(annually) => Some(vec![
+            "FREQ=YEARLY".to_string(),
+            format!("BYMONTH={}", annually.month),
+            format!(
+                "BYDAY={}",
+                format_nth_weekday(&annually.weekday, annually.nth_weekday)
+            ),
+        ]),
+    }?;
+
+    if series.occurs_at_each_time_of_day() {
+        rrule_parts.extend(by_time_parts(series.get_series_options().times_of_day())?);
+    }
+
+    let set_positions = series.get_series_options().set_positions();
+
+    if !set_positions.is_empty() {
+        let set_positions: Vec<String> = set_positions
+            .iter()
+            .map(|set_position| set_position.to_string())
+            .collect();
+        rrule_parts.push(format!("BYSETPOS={}", set_positions.join(",")));
+    }
+
+    return Some(rrule_parts);
+}
+
+/// Returns the coarsest frequency that evenly divides the step between the occurrences of a
+/// fixed-step series, along with the number of its units that make up one step.
+fn fixed_step_frequency(step_in_seconds: i64) -> (&'static str, i64) {
+    return if step_in_seconds % 3600 == 0 {
+        ("HOURLY", step_in_seconds / 3600)
+    } else if step_in_seconds % 60 == 0 {
+        ("MINUTELY", step_in_seconds / 60)
+    } else {
+        ("SECONDLY", step_in_seconds)
+    };
+}
+
+fn fixed_step_frequency_part(step_in_seconds: i64) -> String {
+    return format!("FREQ={}", fixed_step_frequency(step_in_seconds).0);
+}
+
+/// The factor by which the series' interval is scaled in its RRULE; a fixed-step series that
+/// steps by several units of its frequency is expressed through a larger interval.
+fn interval_multiplier(series: &RecurringSeries) -> u64 {
+    let step_in_seconds = match series {
+        RecurringSeries::Secondly(secondly) => secondly.step_in_seconds(),
+        RecurringSeries::Minutely(minutely) => minutely.step_in_seconds(),
+        RecurringSeries::Hourly(hourly) => hourly.step_in_seconds(),
+        _ => return 1,
+    };
+
+    return fixed_step_frequency(step_in_seconds).1.max(1) as u64;
+}
+
+/// Returns the BYDAY and BYHOUR parts that restrict a fixed-step series to its daily window,
+/// which can only be expressed when the window opens and closes on the hour.
+fn daily_window_parts(daily_window: &Option<DailyWindow>) -> Option<Vec<String>> {
+    let daily_window = match daily_window {
+        Some(daily_window) => daily_window,
+        None => return Some(vec![]),
+    };
+    let hour_of = |time_of_day: &Option<TimeOfDay>, default: u32| match time_of_day {
+        None => Some(default),
+        Some(time_of_day) if time_of_day.minute == 0 && time_of_day.second == 0 => {
+            Some(time_of_day.hour)
+        }
+        Some(_) => None,
+    };
+    let first_hour = hour_of(&daily_window.starts_at_time_of_day, 0)?;
+    let end_hour = hour_of(&daily_window.ends_at_time_of_day, 24)?;
+    let mut parts = vec![];
+
+    if !daily_window.weekdays.is_empty() {
+        let weekdays: Vec<&str> = daily_window.weekdays.iter().map(format_weekday).collect();
+        parts.push(format!("BYDAY={}", weekdays.join(",")));
+    }
+
+    if first_hour > 0 || end_hour < 24 {
+        let hours: Vec<String> = (first_hour..end_hour)
+            .map(|hour| hour.to_string())
+            .collect();
+        parts.push(format!("BYHOUR={}", hours.join(",")));
+    }
+
+    return Some(parts);
+}
+
+/// Returns the parts that describe what becomes of the days that a month lacks; they can only be
+/// moved, rather than omitted, through RFC 7529's SKIP.
+fn skip_parts(missing_day_policy: &MissingDayPolicy) -> Vec<String> {
+    return match missing_day_policy {
+        MissingDayPolicy::Skip => vec![],
+        MissingDayPolicy::Clamp => {
+            vec!["RSCALE=GREGORIAN".to_string(), "SKIP=BACKWARD".to_string()]
+        }
+        MissingDayPolicy::Roll => vec!["RSCALE=GREGORIAN".to_string(), "SKIP=FORWARD".to_string()],
+    };
+}
+
+/// Formats an nth weekday as a `BYDAY` entry; RRULE ordinals are 1-indexed, whereas the nth
+/// weekdays of a series are 0-indexed.
+fn format_nth_weekday(weekday: &Weekday, nth_weekday: i32) -> String {
+    let ordinal = if nth_weekday >= 0 {
+        nth_weekday + 1
+    } else {
+        nth_weekday
+    };
+
+    return format!("{}{}", ordinal, format_weekday(weekday));
+}
+
+/// Returns the BYHOUR, BYMINUTE and BYSECOND parts that describe the times of day of a
+/// series, if they can be expressed as such; an RRULE occurs at every combination of the
+/// listed hours, minutes and seconds, taking any that are not listed from its DTSTART.
+fn by_time_parts(times_of_day: &[TimeOfDay]) -> Option<Vec<String>> {
+    let distinct_values = |value: fn(&TimeOfDay) -> u32| {
+        let mut values: Vec<u32> = times_of_day.iter().map(value).collect();
+        values.sort();
+        values.dedup();
+        values
+    };
+    let hours = distinct_values(|time_of_day| time_of_day.hour);
+    let minutes = distinct_values(|time_of_day| time_of_day.minute);
+    let seconds = distinct_values(|time_of_day| time_of_day.second);
+
+    // The times of day are distinct, so they cover every combination exactly when
+    // there are as many of them as there are combinations.
+    if hours.len() * minutes.len() * seconds.len() != times_of_day.len() {
+        return None;
+    }
+
+    return Some(
+        [
+            ("BYHOUR", hours),
+            ("BYMINUTE", minutes),
+            ("BYSECOND", seconds),
+        ]
+        .into_iter()
+        .filter(|(_, values)| values.len() > 1)
+        .map(|(name, values)| {
+            let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+            format!("{}={}", name, values.join(","))
+        })
+        .collect(),
+    );
+}
+
+/// Open-ended series are described by RRULEs without an end; where a finite description
+/// is unavoidable (the time zone's offset transitions, the EXDATEs, and the RDATEs of
+/// series that cannot be described by an RRULE), open-ended schedules are described up
+/// until ten years past their start, or the end of their last exclusion if later.
+fn open_ended_horizon(schedule: &Schedule) -> DateTime<Tz> {
+    let starts_at = schedule.local_starts_at_datetime();
+    // Schedules that start within ten years of the latest representable date are described up
+    // until their start instead.
+    let ten_years_after_start = starts_at
+        .checked_add_months(Months::new(120))
+        .unwrap_or(starts_at);
+    let last_exclusion_ends_at = schedule
+        .sorted_exclusions
+        .exclusions
+        .last()
+        .and_then(|e| DateTime::from_timestamp(e.ends_at_unix_timestamp, 0))
+        .map(|ends_at| ends_at.with_timezone(&schedule.time_zone));
+
+    return last_exclusion_ends_at.map_or(ten_years_after_start, |ends_at| {
+        ends_at.max(ten_years_after_start)
+    });
+}
+
+/// Describes every UTC offset transition of the time zone within the schedule's bookends,
+/// preceded by the offset in effect when the schedule starts.
+fn vtimezone_lines(
+    time_zone: &Tz,
+    starts_at: &DateTime<Tz>,
+    ends_at: &DateTime<Tz>,
+) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VTIMEZONE".to_string(),
+        format!("TZID:{}", time_zone.name()),
+    ];
+
+    lines.extend(observance_lines(time_zone, &starts_at.to_utc(), None));
+
+    // Offsets only ever change at most a few times a year; checking daily and then
+    // narrowing down on the exact second of each change is more than sufficient.
+    let mut examined_at = starts_at.to_utc();
+    let ends_at = ends_at.to_utc();
+
+    while examined_at < ends_at {
+        let next_examined_at = examined_at + TimeDelta::days(1);
+
+        if utc_offset_at(time_zone, &examined_at) != utc_offset_at(time_zone, &next_examined_at) {
+            let transitions_at = find_transition(time_zone, examined_at, next_examined_at);
+            lines.extend(observance_lines(
+                time_zone,
+                &transitions_at,
+                Some(utc_offset_at(
+                    time_zone,
+                    &(transitions_at - TimeDelta::seconds(1)),
+                )),
+            ));
+        }
+
+        examined_at = next_examined_at;
+    }
+
+    lines.push("END:VTIMEZONE".to_string());
+
+    return lines;
+}
+
+fn observance_lines(
+    time_zone: &Tz,
+    starts_at: &DateTime<Utc>,
+    previous_utc_offset: Option<i32>,
+) -> Vec<String> {
+    let offset = time_zone.offset_from_utc_datetime(&starts_at.naive_utc());
+    let utc_offset = offset.fix().local_minus_utc();
+    let observance = if offset.dst_offset().is_zero() {
+        "STANDARD"
+    } else {
+        "DAYLIGHT"
+    };
+
+    // Without a transition, the observance applies from the beginning of time.
+    let (dtstart, utc_offset_from) = match previous_utc_offset {
+        None => ("19700101T000000".to_string(), utc_offset),
+        Some(previous_utc_offset) => (
+            (starts_at.naive_utc() + TimeDelta::seconds(previous_utc_offset as i64))
+                .format("%Y%m%dT%H%M%S")
+                .to_string(),
+            previous_utc_offset,
+        ),
+    };
+
+    return vec![
+        format!("BEGIN:{}", observance),
+        format!("DTSTART:{}", dtstart),
+        format!("TZOFFSETFROM:{}", format_utc_offset(utc_offset_from)),
+        format!("TZOFFSETTO:{}", format_utc_offset(utc_offset)),
+        format!("TZNAME:{}", offset.abbreviation()),
+        format!("END:{}", observance),
+    ];
+}
+
+fn utc_offset_at(time_zone: &Tz, datetime: &DateTime<Utc>) -> i32 {
+    return time_zone
+        .offset_from_utc_datetime(&datetime.naive_utc())
+        .fix()
+        .local_minus_utc();
+}
+
+fn find_transition(
+    time_zone: &Tz,
+    mut before: DateTime<Utc>,
+    mut after: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let offset_before = utc_offset_at(time_zone, &before);
+
+    while after - before > TimeDelta::seconds(1) {
+        let midpoint = before + (after - before) / 2;
+
+        if utc_offset_at(time_zone, &midpoint) == offset_before {
+            before = midpoint;
+        } else {
+            after = midpoint;
+        }
+    }
+
+    return after;
+}
+
+fn format_weekday(weekday: &Weekday) -> &'static str {
+    return match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    };
+}
+
+fn format_utc_date_time(datetime: &DateTime<Utc>) -> String {
+    return datetime.format("%Y%m%dT%H%M%SZ").to_string();
+}
+
+fn format_local_date_time(time_zone: &Tz, unix_timestamp: i64) -> String {
+    return format_local_date_times(time_zone, std::iter::once(unix_timestamp));
+}
+
+fn format_local_date_times<I: Iterator<Item = i64>>(time_zone: &Tz, unix_timestamps: I) -> String {
+    let local_date_times = unix_timestamps
+        .map(|unix_timestamp| {
+            DateTime::from_timestamp(unix_timestamp, 0)
+                .expect("Unix timestamp must be parsed into a DateTime")
+                .with_timezone(time_zone)
+                .format("%Y%m%dT%H%M%S")
+                .to_string()
+        })
+        .collect::<Vec<String>>();
+
+    return format!("TZID={}:{}", time_zone.name(), local_date_times.join(","));
+}
+
+fn format_utc_offset(utc_offset_in_seconds: i32) -> String {
+    let sign = if utc_offset_in_seconds < 0 { '-' } else { '+' };
+    let utc_offset_in_seconds = utc_offset_in_seconds.abs();
+    let hours = utc_offset_in_seconds / 3600;
+    let minutes = (utc_offset_in_seconds % 3600) / 60;
+    let seconds = utc_offset_in_seconds % 60;
+
+    return if seconds == 0 {
+        format!("{}{:02}{:02}", sign, hours, minutes)
+    } else {
+        format!("{}{:02}{:02}{:02}", sign, hours, minutes, seconds)
+    };
+}
+
+fn format_duration(duration_in_seconds: i64) -> String {
+    let hours = duration_in_seconds / 3600;
+    let minutes = (duration_in_seconds % 3600) / 60;
+    let seconds = duration_in_seconds % 60;
+
+    let mut duration = "PT".to_string();
+
+    if hours > 0 {
+        duration.push_str(&format!("{}H", hours));
+    }
+
+    if minutes > 0 {
+        duration.push_str(&format!("{}M", minutes));
+    }
+
+    if seconds > 0 || duration_in_seconds == 0 {
+        duration.push_str(&format!("{}S", seconds));
+    }
+
+    return duration;
+}
+
+fn escape_text(text: &str) -> String {
+    return text
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n");
+}
+
+/// Content lines longer than 75 octets must be folded onto continuation lines that start
+/// with a single space, without splitting multi-byte characters.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_length = 0;
+
+    for character in line.chars() {
+        if line_length + character.len_utf8() > MAXIMUM_LINE_LENGTH_IN_OCTETS {
+            folded.push_str("\r\n ");
+            line_length = 1;
+        }
+
+        folded.push(character);
+        line_length += character.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+
+    return folded;
+}
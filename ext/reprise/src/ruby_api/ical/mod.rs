@@ -0,0 +1,48 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, TimeZone};
+use chrono_tz::Tz;
+
+pub mod export;
+pub mod reader;
+pub mod rrule;
+pub mod vtimezone;
+
+/// An iCalendar DATE (`19970714`) or DATE-TIME value, which is either given in UTC
+/// (`19970714T173000Z`) or as a local time (`19970714T173000`) whose time zone depends on
+/// the property it belongs to.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DateOrDateTime {
+    Date(NaiveDate),
+    Utc(NaiveDateTime),
+    Local(NaiveDateTime),
+}
+
+pub(crate) fn parse_date_or_date_time(value: &str) -> Option<DateOrDateTime> {
+    if let Some(utc_value) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(utc_value, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(DateOrDateTime::Utc);
+    }
+
+    if let Ok(naive_datetime) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        return Some(DateOrDateTime::Local(naive_datetime));
+    }
+
+    return NaiveDate::parse_from_str(value, "%Y%m%d")
+        .ok()
+        .map(DateOrDateTime::Date);
+}
+
+/// Interprets a local time in the given time zone. Ambiguous times resolve to their later
+/// instant and times that fall in a gap are translated one hour ahead, out of the gap, in
+/// line with how series place their occurrences around DST transitions.
+pub(crate) fn localize(naive_datetime: &NaiveDateTime, time_zone: &Tz) -> DateTime<Tz> {
+    return time_zone
+        .from_local_datetime(naive_datetime)
+        .latest()
+        .or_else(|| {
+            time_zone
+                .from_local_datetime(&(*naive_datetime + TimeDelta::hours(1)))
+                .latest()
+        })
+        .unwrap_or_else(|| time_zone.from_utc_datetime(naive_datetime));
+}
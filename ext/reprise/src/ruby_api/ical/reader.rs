@@ -0,0 +1,602 @@
+use crate::ruby_api::ical::rrule::{RRule, UnsupportedRRule};
+use crate::ruby_api::ical::vtimezone::VTimezone;
+use crate::ruby_api::ical::{localize, parse_date_or_date_time, DateOrDateTime};
+use crate::ruby_api::occurrence::Occurrence;
+use crate::ruby_api::schedule::UnixTimestamp;
+use crate::ruby_api::series_options::SeriesOptions;
+use crate::ruby_api::traits::RecurringSeries;
+use chrono::{DateTime, NaiveTime, TimeDelta, TimeZone};
+use chrono_tz::Tz;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+#[derive(Debug)]
+pub(crate) struct UnsupportedICal(String);
+
+impl UnsupportedICal {
+    pub(crate) fn new<T: Into<String>>(message: T) -> UnsupportedICal {
+        return UnsupportedICal(message.into());
+    }
+}
+
+impl fmt::Display for UnsupportedICal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.0);
+    }
+}
+
+impl From<UnsupportedRRule> for UnsupportedICal {
+    fn from(error: UnsupportedRRule) -> UnsupportedICal {
+        return UnsupportedICal(error.to_string());
+    }
+}
+
+/// A single unfolded content line, e.g. `DTSTART;TZID=America/New_York:20240701T090000`.
+#[derive(Debug)]
+pub(crate) struct ContentLine {
+    pub(crate) name: String,
+    pub(crate) parameters: Vec<(String, String)>,
+    pub(crate) value: String,
+}
+
+impl ContentLine {
+    fn parse(line: &str) -> Result<ContentLine, UnsupportedICal> {
+        let mut in_quotes = false;
+        let mut segments = Vec::new();
+        let mut segment_start = 0;
+        let mut value_start = None;
+
+        // Parameter values may be quoted, in which case they can contain ";" and ":".
+        for (index, character) in line.char_indices() {
+            match character {
+                '"' => in_quotes = !in_quotes,
+                ';' if !in_quotes => {
+                    segments.push(&line[segment_start..index]);
+                    segment_start = index + 1;
+                }
+                ':' if !in_quotes => {
+                    segments.push(&line[segment_start..index]);
+                    value_start = Some(index + 1);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let value_start = value_start.ok_or_else(|| {
+            UnsupportedICal::new(format!("\"{}\" is not a valid content line", line))
+        })?;
+
+        let name = segments[0].to_ascii_uppercase();
+        let parameters = segments[1..]
+            .iter()
+            .filter_map(|parameter| parameter.split_once('='))
+            .map(|(key, value)| {
+                (
+                    key.to_ascii_uppercase(),
+                    value.trim_matches('"').to_string(),
+                )
+            })
+            .collect();
+
+        return Ok(ContentLine {
+            name,
+            parameters,
+            value: line[value_start..].to_string(),
+        });
+    }
+
+    pub(crate) fn parameter(&self, name: &str) -> Option<&str> {
+        return self
+            .parameters
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str());
+    }
+}
+
+/// A `BEGIN:<NAME>` ... `END:<NAME>` block, with its properties and nested components.
+#[derive(Debug)]
+pub(crate) struct Component {
+    pub(crate) name: String,
+    pub(crate) properties: Vec<ContentLine>,
+    pub(crate) components: Vec<Component>,
+}
+
+impl Component {
+    fn new(name: String) -> Component {
+        return Component {
+            name,
+            properties: Vec::new(),
+            components: Vec::new(),
+        };
+    }
+
+    pub(crate) fn property(&self, name: &str) -> Option<&ContentLine> {
+        return self.properties.iter().find(|p| p.name == name);
+    }
+
+    pub(crate) fn properties_named<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a ContentLine> {
+        return self.properties.iter().filter(move |p| p.name == name);
+    }
+}
+
+/// The series and one-off occurrences that together reproduce the events of a VCALENDAR.
+#[derive(Debug, Default)]
+pub(crate) struct ImportedCalendar {
+    pub(crate) recurring_series: Vec<RecurringSeries>,
+    pub(crate) occurrences: Vec<Occurrence>,
+}
+
+/// Reads each VEVENT of an iCalendar (RFC 5545) document into the given time zone:
+/// - RRULEs are mapped through `RRule::to_recurring_series`, anchored at DTSTART and
+///   expanded in its time zone;
+/// - DTSTART (for events without an RRULE) and each RDATE become one-off occurrences;
+/// - EXDATEs, and instances moved or cancelled via RECURRENCE-ID, are removed from the
+///   occurrences of the event that they belong to (by their exact start times), and no others.
+///
+/// TZIDs are resolved as IANA time zone names where possible, falling back to the
+/// VTIMEZONE definitions embedded in the document.
+pub(crate) fn read_vcalendar(
+    ics: &str,
+    time_zone: &Tz,
+) -> Result<ImportedCalendar, UnsupportedICal> {
+    let components = parse_components(ics)?;
+    let calendar = components
+        .iter()
+        .find(|c| c.name == "VCALENDAR")
+        .ok_or_else(|| UnsupportedICal::new("No VCALENDAR component was found"))?;
+
+    let vtimezones = calendar
+        .components
+        .iter()
+        .filter(|c| c.name == "VTIMEZONE")
+        .map(VTimezone::parse)
+        .collect::<Result<Vec<VTimezone>, UnsupportedICal>>()?;
+
+    let reader = EventReader {
+        time_zone,
+        vtimezones: &vtimezones,
+    };
+    let mut imported_calendar = ImportedCalendar::default();
+    let (overriding_events, events): (Vec<&Component>, Vec<&Component>) = calendar
+        .components
+        .iter()
+        .filter(|c| c.name == "VEVENT")
+        .partition(|event| event.property("RECURRENCE-ID").is_some());
+
+    // Overrides share the UID of the event whose instances they replace, which may appear
+    // anywhere in the document.
+    let mut overridden_starts_at_by_uid: HashMap<Option<&str>, BTreeSet<UnixTimestamp>> =
+        HashMap::new();
+
+    for event in overriding_events {
+        if let Some(overridden_starts_at) =
+            reader.read_overriding_vevent(event, &mut imported_calendar)?
+        {
+            overridden_starts_at_by_uid
+                .entry(uid(event))
+                .or_default()
+                .insert(overridden_starts_at);
+        }
+    }
+
+    for event in events {
+        let overridden_starts_at = overridden_starts_at_by_uid
+            .get(&uid(event))
+            .cloned()
+            .unwrap_or_default();
+        reader.read_vevent(event, overridden_starts_at, &mut imported_calendar)?;
+    }
+
+    return Ok(imported_calendar);
+}
+
+fn uid(event: &Component) -> Option<&str> {
+    return event.property("UID").map(|p| p.value.as_str());
+}
+
+struct EventReader<'a> {
+    time_zone: &'a Tz,
+    vtimezones: &'a [VTimezone],
+}
+
+impl EventReader<'_> {
+    fn read_vevent(
+        &self,
+        event: &Component,
+        overridden_starts_at: BTreeSet<UnixTimestamp>,
+        imported_calendar: &mut ImportedCalendar,
+    ) -> Result<(), UnsupportedICal> {
+        let (dtstart, is_all_day) = self.resolve_dtstart(event)?;
+        let duration_in_seconds = self.duration_in_seconds(event, &dtstart, is_all_day)?;
+        let label = summary(event);
+
+        if is_cancelled(event) {
+            return Ok(());
+        }
+
+        // The event's EXDATEs, like the instances that its overrides move or cancel, only
+        // remove its own occurrences that start at exactly those times.
+        let mut excluded_starts_at_unix_timestamps = overridden_starts_at;
+
+        for exdate in event.properties_named("EXDATE") {
+            for value in exdate.value.split(',') {
+                let (excluded_starts_at, _) = self.resolve_date_time(exdate, value)?;
+                excluded_starts_at_unix_timestamps.insert(excluded_starts_at.timestamp());
+            }
+        }
+
+        let rrules = event
+            .properties_named("RRULE")
+            .collect::<Vec<&ContentLine>>();
+
+        // Recurrences keep the local time of day (and the dates) of DTSTART in its own
+        // time zone, which may observe different DST transitions than the schedule's.
+        let series_time_zone = if rrules.is_empty() {
+            *self.time_zone
+        } else {
+            self.resolve_dtstart_time_zone(event, &dtstart)?
+        };
+        let series_dtstart = dtstart.with_timezone(&series_time_zone);
+
+        for rrule in &rrules {
+            let mut series_options = SeriesOptions::new_starting_at(
+                series_time_zone,
+                series_dtstart,
+                duration_in_seconds,
+                label.clone(),
+            );
+            series_options.excluded_starts_at_unix_timestamps =
+                excluded_starts_at_unix_timestamps.clone();
+            let recurring_series = RRule::parse(&rrule.value, &series_time_zone)?
+                .to_recurring_series(series_options, series_dtstart)?;
+
+            imported_calendar.recurring_series.extend(recurring_series);
+        }
+
+        let mut one_off_occurrences = Vec::new();
+
+        if rrules.is_empty() {
+            one_off_occurrences.push(one_off_occurrence(
+                &dtstart,
+                duration_in_seconds,
+                label.clone(),
+            ));
+        }
+
+        for rdate in event.properties_named("RDATE") {
+            for value in rdate.value.split(',') {
+                // PERIOD values ("start/end" or "start/duration") set the duration of their
+                // own occurrence, rather than taking the event's.
+                let (starts_at_value, period_end_value) = match value.split_once('/') {
+                    Some((starts_at_value, period_end_value)) => {
+                        (starts_at_value, Some(period_end_value))
+                    }
+                    None => (value, None),
+                };
+                let (starts_at, _) = self.resolve_date_time(rdate, starts_at_value)?;
+                let occurrence_duration_in_seconds = match period_end_value {
+                    None => duration_in_seconds,
+                    Some(period_end_value) if period_end_value.starts_with(['P', '+', '-']) => {
+                        parse_duration(period_end_value)?
+                    }
+                    Some(period_end_value) => {
+                        let (ends_at, _) = self.resolve_date_time(rdate, period_end_value)?;
+                        (ends_at - starts_at).num_seconds()
+                    }
+                };
+
+                one_off_occurrences.push(one_off_occurrence(
+                    &starts_at,
+                    occurrence_duration_in_seconds,
+                    label.clone(),
+                ));
+            }
+        }
+
+        imported_calendar.occurrences.extend(
+            one_off_occurrences.into_iter().filter(|o| {
+                !excluded_starts_at_unix_timestamps.contains(&o.starts_at_unix_timestamp)
+            }),
+        );
+
+        return Ok(());
+    }
+
+    /// Reads an overridden instance of a recurring event, adding the override in its place
+    /// (unless it is a cancellation), and returns the start time of the instance that it
+    /// replaces. Overrides that don't move the instance keep the original occurrence.
+    fn read_overriding_vevent(
+        &self,
+        event: &Component,
+        imported_calendar: &mut ImportedCalendar,
+    ) -> Result<Option<UnixTimestamp>, UnsupportedICal> {
+        let recurrence_id = event
+            .property("RECURRENCE-ID")
+            .expect("Overriding VEVENT should have a RECURRENCE-ID");
+        let (original_starts_at, _) =
+            self.resolve_date_time(recurrence_id, &recurrence_id.value)?;
+        let (dtstart, is_all_day) = self.resolve_dtstart(event)?;
+        let is_cancelled = is_cancelled(event);
+
+        if !is_cancelled && original_starts_at == dtstart {
+            return Ok(None);
+        }
+
+        if !is_cancelled {
+            let duration_in_seconds = self.duration_in_seconds(event, &dtstart, is_all_day)?;
+            imported_calendar.occurrences.push(one_off_occurrence(
+                &dtstart,
+                duration_in_seconds,
+                summary(event),
+            ));
+        }
+
+        return Ok(Some(original_starts_at.timestamp()));
+    }
+
+    /// The time zone of an event's DTSTART: UTC for UTC times, the schedule's for dates and
+    /// floating times, and otherwise that of its TZID. Time zones defined by a VTIMEZONE are
+    /// replaced by an equivalent known time zone.
+    fn resolve_dtstart_time_zone(
+        &self,
+        event: &Component,
+        dtstart: &DateTime<Tz>,
+    ) -> Result<Tz, UnsupportedICal> {
+        let dtstart_property = event.property("DTSTART").unwrap();
+
+        let tzid = match parse_date_or_date_time(&dtstart_property.value) {
+            Some(DateOrDateTime::Utc(_)) => return Ok(Tz::UTC),
+            Some(DateOrDateTime::Local(_)) => dtstart_property.parameter("TZID"),
+            _ => None,
+        };
+        let Some(tzid) = tzid else {
+            return Ok(*self.time_zone);
+        };
+
+        if let Ok(dtstart_time_zone) = tzid.parse::<Tz>() {
+            return Ok(dtstart_time_zone);
+        }
+
+        return self
+            .vtimezones
+            .iter()
+            .find(|vtimezone| vtimezone.tzid == tzid)
+            .and_then(|vtimezone| vtimezone.equivalent_time_zone(self.time_zone, &dtstart.to_utc()))
+            .ok_or_else(|| {
+                UnsupportedICal::new(format!(
+                    "VTIMEZONE \"{}\" does not match any known time zone",
+                    tzid
+                ))
+            });
+    }
+
+    fn resolve_dtstart(&self, event: &Component) -> Result<(DateTime<Tz>, bool), UnsupportedICal> {
+        let dtstart_property = event
+            .property("DTSTART")
+            .ok_or_else(|| UnsupportedICal::new("VEVENT is missing a DTSTART"))?;
+
+        return self.resolve_date_time(dtstart_property, &dtstart_property.value);
+    }
+
+    fn duration_in_seconds(
+        &self,
+        event: &Component,
+        dtstart: &DateTime<Tz>,
+        is_all_day: bool,
+    ) -> Result<i64, UnsupportedICal> {
+        if let Some(dtend) = event.property("DTEND") {
+            let (ends_at, _) = self.resolve_date_time(dtend, &dtend.value)?;
+            return Ok((ends_at - dtstart).num_seconds().max(0));
+        }
+
+        if let Some(duration) = event.property("DURATION") {
+            return Ok(parse_duration(&duration.value)?.max(0));
+        }
+
+        return Ok(if is_all_day { 86_400 } else { 0 });
+    }
+
+    /// Resolves a DATE or DATE-TIME value of the given property into the schedule's time
+    /// zone, and reports whether it was a DATE (i.e. an all-day value). DATE values and
+    /// DATE-TIME values without a TZID ("floating" times) are read as schedule-local.
+    fn resolve_date_time(
+        &self,
+        property: &ContentLine,
+        value: &str,
+    ) -> Result<(DateTime<Tz>, bool), UnsupportedICal> {
+        let naive_datetime = match parse_date_or_date_time(value) {
+            None => {
+                return Err(UnsupportedICal::new(format!(
+                    "\"{}\" is not a valid {} value",
+                    value, property.name
+                )))
+            }
+            Some(DateOrDateTime::Utc(naive_datetime)) => {
+                return Ok((self.time_zone.from_utc_datetime(&naive_datetime), false));
+            }
+            Some(DateOrDateTime::Date(naive_date)) => {
+                let naive_datetime = naive_date.and_time(NaiveTime::MIN);
+                return Ok((localize(&naive_datetime, self.time_zone), true));
+            }
+            Some(DateOrDateTime::Local(naive_datetime)) => naive_datetime,
+        };
+
+        let Some(tzid) = property.parameter("TZID") else {
+            return Ok((localize(&naive_datetime, self.time_zone), false));
+        };
+
+        if let Ok(property_time_zone) = tzid.parse::<Tz>() {
+            let local_datetime = localize(&naive_datetime, &property_time_zone);
+            return Ok((local_datetime.with_timezone(self.time_zone), false));
+        }
+
+        return match self
+            .vtimezones
+            .iter()
+            .find(|vtimezone| vtimezone.tzid == tzid)
+        {
+            Some(vtimezone) => Ok((
+                vtimezone
+                    .to_utc(&naive_datetime)
+                    .with_timezone(self.time_zone),
+                false,
+            )),
+            None => Err(UnsupportedICal::new(format!(
+                "TZID \"{}\" is neither a known time zone nor defined by a VTIMEZONE",
+                tzid
+            ))),
+        };
+    }
+}
+
+fn one_off_occurrence(
+    starts_at: &DateTime<Tz>,
+    duration_in_seconds: i64,
+    label: Option<String>,
+) -> Occurrence {
+    return Occurrence::new(
+        starts_at.timestamp(),
+        starts_at.timestamp() + duration_in_seconds,
+        label,
+    );
+}
+
+fn summary(event: &Component) -> Option<String> {
+    return event.property("SUMMARY").map(|p| unescape_text(&p.value));
+}
+
+fn is_cancelled(event: &Component) -> bool {
+    return event
+        .property("STATUS")
+        .is_some_and(|p| p.value == "CANCELLED");
+}
+
+fn parse_components(ics: &str) -> Result<Vec<Component>, UnsupportedICal> {
+    // Long content lines are folded onto continuation lines that begin with whitespace.
+    let unfolded = ics
+        .replace("\r\n", "\n")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut open_components: Vec<Component> = Vec::new();
+    let mut components = Vec::new();
+
+    for line in unfolded.lines().filter(|line| !line.trim().is_empty()) {
+        let content_line = ContentLine::parse(line)?;
+
+        match content_line.name.as_str() {
+            "BEGIN" => {
+                open_components.push(Component::new(content_line.value.to_ascii_uppercase()))
+            }
+            "END" => {
+                let component = open_components
+                    .pop()
+                    .filter(|c| c.name.eq_ignore_ascii_case(&content_line.value))
+                    .ok_or_else(|| {
+                        UnsupportedICal::new(format!("Unexpected END:{}", content_line.value))
+                    })?;
+
+                match open_components.last_mut() {
+                    Some(parent) => parent.components.push(component),
+                    None => components.push(component),
+                }
+            }
+            _ => match open_components.last_mut() {
+                Some(component) => component.properties.push(content_line),
+                None => {
+                    return Err(UnsupportedICal::new(format!(
+                        "{} appears outside of a component",
+                        content_line.name
+                    )))
+                }
+            },
+        }
+    }
+
+    if let Some(component) = open_components.last() {
+        return Err(UnsupportedICal::new(format!(
+            "{} is missing its END",
+            component.name
+        )));
+    }
+
+    return Ok(components);
+}
+
+/// Parses a DURATION value, e.g. `PT1H30M`, `P1D` or `P2W`.
+fn parse_duration(value: &str) -> Result<i64, UnsupportedICal> {
+    let invalid = || UnsupportedICal::new(format!("\"{}\" is not a valid DURATION", value));
+    let out_of_range = || UnsupportedICal::new(format!("DURATION \"{}\" is out of range", value));
+
+    let (sign, unsigned_value) = match value.strip_prefix('-') {
+        Some(unsigned_value) => (-1, unsigned_value),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let designators = unsigned_value.strip_prefix('P').ok_or_else(invalid)?;
+
+    let mut duration_in_seconds: i64 = 0;
+    let mut digits = String::new();
+
+    for character in designators.chars() {
+        let unit_in_seconds = match character {
+            '0'..='9' => {
+                digits.push(character);
+                continue;
+            }
+            'T' => continue,
+            'W' => 604_800,
+            'D' => 86_400,
+            'H' => 3_600,
+            'M' => 60,
+            'S' => 1,
+            _ => return Err(invalid()),
+        };
+
+        if digits.is_empty() {
+            return Err(invalid());
+        }
+
+        let quantity = digits.parse::<i64>().map_err(|_| out_of_range())?;
+        duration_in_seconds = quantity
+            .checked_mul(unit_in_seconds)
+            .and_then(|seconds| duration_in_seconds.checked_add(seconds))
+            .ok_or_else(out_of_range)?;
+        digits.clear();
+    }
+
+    if !digits.is_empty() {
+        return Err(invalid());
+    }
+
+    // Durations are added to start times as `TimeDelta`s, which cover a narrower range.
+    if TimeDelta::try_seconds(duration_in_seconds).is_none() {
+        return Err(out_of_range());
+    }
+
+    return Ok(sign * duration_in_seconds);
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::new();
+    let mut characters = text.chars();
+
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+
+        match characters.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(escaped) => unescaped.push(escaped),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    return unescaped;
+}
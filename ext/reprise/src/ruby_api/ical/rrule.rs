@@ -0,0 +1,868 @@
+use crate::ruby_api::daily_window::DailyWindow;
+use crate::ruby_api::ical::{localize, parse_date_or_date_time, DateOrDateTime};
+use crate::ruby_api::recurring_series::annually_by_day::AnnuallyByDay;
+use crate::ruby_api::recurring_series::annually_by_iso_week::AnnuallyByIsoWeek;
+use crate::ruby_api::recurring_series::annually_by_month_day::AnnuallyByMonthDay;
+use crate::ruby_api::recurring_series::annually_by_nth_weekday::AnnuallyByNthWeekday;
+use crate::ruby_api::recurring_series::daily::Daily;
+use crate::ruby_api::recurring_series::hourly::Hourly;
+use crate::ruby_api::recurring_series::minutely::Minutely;
+use crate::ruby_api::recurring_series::monthly_by_day::{MissingDayPolicy, MonthlyByDay};
+use crate::ruby_api::recurring_series::monthly_by_nth_weekday::MonthlyByNthWeekday;
+use crate::ruby_api::recurring_series::monthly_by_weekday::MonthlyByWeekday;
+use crate::ruby_api::recurring_series::secondly::Secondly;
+use crate::ruby_api::recurring_series::weekly::Weekly;
+use crate::ruby_api::series_options::SeriesOptions;
+use crate::ruby_api::time_of_day::TimeOfDay;
+use crate::ruby_api::traits::RecurringSeries;
+use chrono::{DateTime, Datelike, NaiveTime, TimeZone, Weekday};
+use chrono_tz::Tz;
+use std::fmt;
+
+#[derive(Debug)]
+pub(crate) struct UnsupportedRRule(String);
+
+impl UnsupportedRRule {
+    fn new<T: Into<String>>(message: T) -> UnsupportedRRule {
+        return UnsupportedRRule(message.into());
+    }
+}
+
+impl fmt::Display for UnsupportedRRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.0);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Frequency {
+    Secondly,
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    pub(crate) fn name(&self) -> &'static str {
+        return match self {
+            Frequency::Secondly => "SECONDLY",
+            Frequency::Minutely => "MINUTELY",
+            Frequency::Hourly => "HOURLY",
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+    }
+
+    fn is_sub_daily(&self) -> bool {
+        return matches!(
+            self,
+            Frequency::Secondly | Frequency::Minutely | Frequency::Hourly
+        );
+    }
+}
+
+/// A parsed RFC 5545 recurrence rule, e.g. `FREQ=MONTHLY;BYDAY=-1FR;COUNT=6`.
+/// https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.10
+#[derive(Debug, Clone)]
+pub(crate) struct RRule {
+    pub(crate) frequency: Frequency,
+    pub(crate) interval: i64,
+    pub(crate) count: Option<u64>,
+    pub(crate) until: Option<DateTime<Tz>>,
+    // Each weekday may be prefixed by a signed ordinal (e.g. `2TU`, `-1FR`).
+    pub(crate) by_day: Vec<(Option<i32>, Weekday)>,
+    pub(crate) by_month_day: Vec<i32>,
+    pub(crate) by_year_day: Vec<i32>,
+    pub(crate) by_month: Vec<u32>,
+    pub(crate) by_week_no: Vec<i32>,
+    pub(crate) by_set_pos: Vec<i32>,
+    pub(crate) week_start: Weekday,
+    pub(crate) by_hour: Vec<u32>,
+    pub(crate) by_minute: Vec<u32>,
+    pub(crate) by_second: Vec<u32>,
+    // RFC 7529's SKIP, which decides what becomes of the days that a month lacks.
+    pub(crate) missing_day_policy: MissingDayPolicy,
+}
+
+impl RRule {
+    pub(crate) fn parse(rrule: &str, time_zone: &Tz) -> Result<RRule, UnsupportedRRule> {
+        let rrule = rrule.trim();
+        let rrule = rrule.strip_prefix("RRULE:").unwrap_or(rrule);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+        let mut by_month_day = Vec::new();
+        let mut by_year_day = Vec::new();
+        let mut by_month = Vec::new();
+        let mut by_week_no = Vec::new();
+        let mut by_set_pos = Vec::new();
+        let mut week_start = Weekday::Mon;
+        let mut by_hour = Vec::new();
+        let mut by_minute = Vec::new();
+        let mut by_second = Vec::new();
+        let mut rscale = None;
+        let mut skip = None;
+
+        for part in rrule.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part.split_once('=').ok_or_else(|| {
+                UnsupportedRRule::new(format!("Malformed RRULE part \"{}\"", part))
+            })?;
+
+            match name.to_ascii_uppercase().as_str() {
+                "FREQ" => frequency = Some(parse_frequency(value)?),
+                "INTERVAL" => interval = parse_number_in_range(name, value, 1, i64::MAX)?,
+                "COUNT" => count = Some(parse_number_in_range(name, value, 1, i64::MAX)? as u64),
+                "UNTIL" => until = Some(parse_until(value, time_zone)?),
+                "BYDAY" => by_day = parse_list(value, parse_by_day_entry)?,
+                "BYMONTHDAY" => {
+                    by_month_day = parse_list(value, |v| parse_signed_ordinal(name, v, 31))?
+                }
+                "BYYEARDAY" => {
+                    by_year_day = parse_list(value, |v| parse_signed_ordinal(name, v, 366))?
+                }
+                "BYMONTH" => {
+                    by_month =
+                        parse_list(value, |v| Ok(parse_number_in_range(name, v, 1, 12)? as u32))?
+                }
+                "BYWEEKNO" => {
+                    by_week_no = parse_list(value, |v| parse_signed_ordinal(name, v, 53))?
+                }
+                "BYSETPOS" => {
+                    by_set_pos = parse_list(value, |v| parse_signed_ordinal(name, v, 366))?
+                }
+                "WKST" => week_start = parse_weekday(value)?,
+                "BYHOUR" => by_hour = parse_list(value, |v| parse_time_part(name, v, 23))?,
+                "BYMINUTE" => by_minute = parse_list(value, |v| parse_time_part(name, v, 59))?,
+                "BYSECOND" => by_second = parse_list(value, |v| parse_time_part(name, v, 59))?,
+                "RSCALE" => rscale = Some(parse_rscale(value)?),
+                "SKIP" => skip = Some(parse_skip(value)?),
+                _ => {
+                    return Err(UnsupportedRRule::new(format!(
+                        "The RRULE part {} is not supported",
+                        name
+                    )))
+                }
+            }
+        }
+
+        if count.is_some() && until.is_some() {
+            return Err(UnsupportedRRule::new(
+                "COUNT and UNTIL must not occur in the same RRULE",
+            ));
+        }
+
+        if skip.is_some() && rscale.is_none() {
+            return Err(UnsupportedRRule::new(
+                "SKIP must not occur in an RRULE without RSCALE",
+            ));
+        }
+
+        return Ok(RRule {
+            frequency: frequency.ok_or_else(|| UnsupportedRRule::new("FREQ is required"))?,
+            interval,
+            count,
+            until,
+            by_day,
+            by_month_day,
+            by_year_day,
+            by_month,
+            by_week_no,
+            by_set_pos,
+            week_start,
+            by_hour,
+            by_minute,
+            by_second,
+            missing_day_policy: skip.unwrap_or_default(),
+        });
+    }
+
+    /// Translates the rule into the recurring series that generate the same occurrences,
+    /// using `dtstart` to fill in any day that the rule leaves implicit (e.g. a weekly rule
+    /// without `BYDAY` repeats on the weekday of its first occurrence).
+    pub(crate) fn to_recurring_series(
+        &self,
+        series_options: SeriesOptions,
+        dtstart: DateTime<Tz>,
+    ) -> Result<Vec<RecurringSeries>, UnsupportedRRule> {
+        let series_options = self.apply_to_series_options(series_options);
+
+        // Week numbers only select days within a year.
+        if self.frequency != Frequency::Yearly {
+            self.reject_by_rules(&["BYWEEKNO"])?;
+        }
+
+        let recurring_series = match self.frequency {
+            Frequency::Secondly | Frequency::Minutely | Frequency::Hourly => {
+                self.reject_by_rules(&[
+                    "BYMONTHDAY",
+                    "BYYEARDAY",
+                    "BYMONTH",
+                    "BYSETPOS",
+                    "BYMINUTE",
+                    "BYSECOND",
+                ])?;
+                vec![self.fixed_step_series(series_options)?]
+            }
+            Frequency::Daily => {
+                self.reject_by_rules(&["BYMONTHDAY", "BYYEARDAY", "BYMONTH", "BYSETPOS"])?;
+
+                if self.by_day.is_empty() {
+                    vec![RecurringSeries::Daily(Daily::new(series_options))]
+                } else if self.interval == 1 {
+                    // Every day, restricted to certain weekdays: the same as a weekly rule.
+                    self.weekly_series_from_by_day(&series_options)?
+                } else {
+                    return Err(UnsupportedRRule::new(
+                        "FREQ=DAILY with BYDAY is only supported when INTERVAL=1",
+                    ));
+                }
+            }
+            Frequency::Weekly => {
+                self.reject_by_rules(&["BYMONTHDAY", "BYYEARDAY", "BYMONTH", "BYSETPOS"])?;
+
+                if self.by_day.is_empty() {
+                    vec![RecurringSeries::Weekly(Weekly::from_weekdays(
+                        vec![dtstart.weekday()],
+                        self.week_start,
+                        series_options,
+                    ))]
+                } else {
+                    self.weekly_series_from_by_day(&series_options)?
+                }
+            }
+            Frequency::Monthly => {
+                self.reject_by_rules(&["BYYEARDAY", "BYMONTH"])?;
+                self.monthly_series(series_options, &dtstart)?
+            }
+            Frequency::Yearly => {
+                let recurring_series = if !self.by_week_no.is_empty() {
+                    self.reject_by_rules(&["BYMONTH", "BYMONTHDAY", "BYYEARDAY"])?;
+                    vec![self.yearly_series_by_week_no(series_options)?]
+                } else if !self.by_month.is_empty() {
+                    self.reject_by_rules(&["BYYEARDAY"])?;
+                    self.yearly_series_by_month(&series_options, &dtstart)?
+                } else if self.by_year_day.is_empty() {
+                    self.reject_by_rules(&["BYDAY", "BYMONTHDAY"])?;
+
+                    // Every year, on the month and day of the rule's first occurrence.
+                    let annual_series = AnnuallyByMonthDay::new(
+                        dtstart.month(),
+                        dtstart.day(),
+                        self.missing_day_policy,
+                        series_options,
+                    )
+                    .map_err(UnsupportedRRule::new)?;
+
+                    vec![RecurringSeries::AnnuallyByMonthDay(annual_series)]
+                } else {
+                    self.reject_by_rules(&["BYDAY", "BYMONTHDAY"])?;
+
+                    self.by_year_day
+                        .iter()
+                        .map(|day_number| {
+                            if *day_number < 0 {
+                                return Err(UnsupportedRRule::new(
+                                    "Negative BYYEARDAY values are not supported",
+                                ));
+                            }
+
+                            return AnnuallyByDay::new(*day_number as u32, series_options.clone())
+                                .map(RecurringSeries::AnnuallyByDay)
+                                .map_err(UnsupportedRRule::new);
+                        })
+                        .collect::<Result<Vec<RecurringSeries>, UnsupportedRRule>>()?
+                };
+
+                // Set positions are counted among the days of a single series within a year.
+                if recurring_series.len() > 1 && !self.by_set_pos.is_empty() {
+                    return Err(UnsupportedRRule::new(
+                        "BYSETPOS is not supported for FREQ=YEARLY rules that expand into several series",
+                    ));
+                }
+
+                recurring_series
+            }
+        };
+
+        // Each series applies its interval and count independently; once a rule fans out
+        // into several series, neither can be honoured across the rule as a whole.
+        if recurring_series.len() > 1 && (self.interval > 1 || self.count.is_some()) {
+            return Err(UnsupportedRRule::new(
+                "INTERVAL and COUNT are not supported for rules that repeat on more than one day per period",
+            ));
+        }
+
+        return Ok(recurring_series);
+    }
+
+    fn apply_to_series_options(&self, series_options: SeriesOptions) -> SeriesOptions {
+        let mut series_options = series_options;
+        series_options.interval = self.interval;
+        series_options.count = self.count;
+        series_options.set_positions = self.by_set_pos.clone();
+
+        // The BYHOUR part of a sub-daily rule restricts it to a daily window instead.
+        let has_by_time_parts =
+            !self.by_hour.is_empty() || !self.by_minute.is_empty() || !self.by_second.is_empty();
+
+        if has_by_time_parts && !self.frequency.is_sub_daily() {
+            // The rule occurs at every combination of its listed hours, minutes and seconds;
+            // any part that it does not list is taken from the series' own times of day.
+            let times_of_day = series_options
+                .times_of_day()
+                .iter()
+                .flat_map(|time_of_day| self.times_of_day_from(time_of_day))
+                .collect();
+            series_options.times_of_day = SeriesOptions::sorted_times_of_day(times_of_day);
+        }
+
+        if let Some(until) = self.until {
+            // Respect whichever of the rule's UNTIL and the series' own bookend comes first.
+            series_options.ends_at_unix_timestamp = Some(
+                series_options
+                    .ends_at_unix_timestamp
+                    .map_or(until.timestamp(), |ends_at| ends_at.min(until.timestamp())),
+            );
+        }
+
+        return series_options;
+    }
+
+    // The rule's interval is folded into the step between consecutive occurrences, so that the
+    // occurrences in between are never generated only to be skipped.
+    fn fixed_step_series(
+        &self,
+        series_options: SeriesOptions,
+    ) -> Result<RecurringSeries, UnsupportedRRule> {
+        let mut series_options = series_options;
+        series_options.interval = 1;
+
+        let unit_in_seconds: i64 = match self.frequency {
+            Frequency::Secondly => 1,
+            Frequency::Minutely => 60,
+            _ => 3600,
+        };
+        let every_in_seconds = self
+            .interval
+            .checked_mul(unit_in_seconds)
+            .ok_or_else(|| UnsupportedRRule::new("INTERVAL is out of range"))?;
+
+        let daily_window = self.daily_window()?;
+
+        let recurring_series = match self.frequency {
+            Frequency::Secondly => {
+                Secondly::new(series_options, Some(every_in_seconds), daily_window)
+                    .map(RecurringSeries::Secondly)
+            }
+            Frequency::Minutely => {
+                Minutely::new(series_options, Some(every_in_seconds), daily_window)
+                    .map(RecurringSeries::Minutely)
+            }
+            _ => Hourly::new(series_options, Some(every_in_seconds), daily_window)
+                .map(RecurringSeries::Hourly),
+        };
+
+        return recurring_series.map_err(UnsupportedRRule::new);
+    }
+
+    // The BYDAY and BYHOUR parts of a sub-daily rule only keep the occurrences on certain
+    // weekdays and in certain hours; those hours must form a single window within each day.
+    fn daily_window(&self) -> Result<Option<DailyWindow>, UnsupportedRRule> {
+        if self.by_day.is_empty() && self.by_hour.is_empty() {
+            return Ok(None);
+        }
+
+        let weekdays = self
+            .by_day
+            .iter()
+            .map(|(ordinal, weekday)| match ordinal {
+                Some(_) => Err(UnsupportedRRule::new(
+                    "BYDAY ordinals are only supported for FREQ=MONTHLY",
+                )),
+                None => Ok(*weekday),
+            })
+            .collect::<Result<Vec<Weekday>, UnsupportedRRule>>()?;
+
+        let mut hours = self.by_hour.clone();
+        hours.sort();
+        hours.dedup();
+
+        let (starts_at_time_of_day, ends_at_time_of_day) = match (hours.first(), hours.last()) {
+            (Some(first_hour), Some(last_hour)) => {
+                if (last_hour - first_hour + 1) as usize != hours.len() {
+                    return Err(UnsupportedRRule::new(format!(
+                        "BYHOUR must list consecutive hours for FREQ={}",
+                        self.frequency.name()
+                    )));
+                }
+
+                let time_of_day_at = |hour: u32| TimeOfDay {
+                    hour,
+                    minute: 0,
+                    second: 0,
+                };
+
+                (
+                    Some(time_of_day_at(*first_hour)),
+                    // A window that runs through the last hour of the day closes at midnight.
+                    Some(last_hour + 1)
+                        .filter(|hour| *hour < 24)
+                        .map(time_of_day_at),
+                )
+            }
+            _ => (None, None),
+        };
+
+        return DailyWindow::from_weekdays(starts_at_time_of_day, ends_at_time_of_day, weekdays)
+            .map(Some)
+            .map_err(UnsupportedRRule::new);
+    }
+
+    fn monthly_series(
+        &self,
+        series_options: SeriesOptions,
+        dtstart: &DateTime<Tz>,
+    ) -> Result<Vec<RecurringSeries>, UnsupportedRRule> {
+        if !self.by_month_day.is_empty() {
+            if !self.by_day.is_empty() {
+                return Err(UnsupportedRRule::new(
+                    "BYMONTHDAY cannot be combined with BYDAY",
+                ));
+            }
+
+            return MonthlyByDay::new(
+                self.by_month_day.clone(),
+                self.missing_day_policy,
+                series_options,
+            )
+            .map(|series| vec![RecurringSeries::MonthlyByDay(series)])
+            .map_err(UnsupportedRRule::new);
+        }
+
+        if self.by_day.is_empty() {
+            self.reject_by_rules(&["BYSETPOS"])?;
+
+            return MonthlyByDay::new(
+                vec![dtstart.day() as i32],
+                self.missing_day_policy,
+                series_options,
+            )
+            .map(|series| vec![RecurringSeries::MonthlyByDay(series)])
+            .map_err(UnsupportedRRule::new);
+        }
+
+        if let ([(None, weekday)], [set_position]) =
+            (self.by_day.as_slice(), self.by_set_pos.as_slice())
+        {
+            // A single position among the occurrences of a single weekday is its nth weekday;
+            // e.g. `BYDAY=TU;BYSETPOS=2` is the same as `BYDAY=2TU`.
+            let mut series_options = series_options;
+            series_options.set_positions = Vec::new();
+
+            return Ok(vec![self.monthly_by_nth_weekday(
+                vec![(*weekday, *set_position)],
+                series_options,
+            )?]);
+        }
+
+        if self.by_day.iter().all(|(ordinal, _)| ordinal.is_none()) {
+            // Every occurrence of a weekday within the month is a weekly series, unless months
+            // are skipped, or positions are counted among the occurrences within each month.
+            if self.interval == 1 && self.by_set_pos.is_empty() {
+                return self.weekly_series_from_by_day(&series_options);
+            }
+
+            let weekdays = self.by_day.iter().map(|(_, weekday)| *weekday).collect();
+
+            return MonthlyByWeekday::from_weekdays(weekdays, series_options)
+                .map(|monthly_series| vec![RecurringSeries::MonthlyByWeekday(monthly_series)])
+                .map_err(UnsupportedRRule::new);
+        }
+
+        let nth_weekdays = self
+            .by_day
+            .iter()
+            .map(|(ordinal, weekday)| match ordinal {
+                None => Err(UnsupportedRRule::new(
+                    "BYDAY cannot mix weekdays with and without ordinals",
+                )),
+                Some(ordinal) => Ok((*weekday, *ordinal)),
+            })
+            .collect::<Result<Vec<(Weekday, i32)>, UnsupportedRRule>>()?;
+
+        return Ok(vec![
+            self.monthly_by_nth_weekday(nth_weekdays, series_options)?
+        ]);
+    }
+
+    fn monthly_by_nth_weekday(
+        &self,
+        ordinals: Vec<(Weekday, i32)>,
+        series_options: SeriesOptions,
+    ) -> Result<RecurringSeries, UnsupportedRRule> {
+        if let Some((_, ordinal)) = ordinals.iter().find(|(_, ordinal)| ordinal.abs() > 5) {
+            return Err(UnsupportedRRule::new(format!(
+                "The weekday ordinal {} is out of range for a month",
+                ordinal
+            )));
+        }
+
+        // RRULE ordinals are 1-indexed, whereas the nth weekday of a monthly series is 0-indexed;
+        // negative ordinals count back from the last weekday of the month in both.
+        let nth_weekdays = ordinals
+            .into_iter()
+            .map(|(weekday, ordinal)| (weekday, if ordinal > 0 { ordinal - 1 } else { ordinal }))
+            .collect();
+
+        return MonthlyByNthWeekday::from_nth_weekdays(nth_weekdays, series_options)
+            .map(RecurringSeries::MonthlyByNthWeekday)
+            .map_err(UnsupportedRRule::new);
+    }
+
+    // Each month of the rule is a separate annual series, on either its days (`BYMONTHDAY`) or
+    // its nth weekdays (`BYDAY` with ordinals).
+    fn yearly_series_by_month(
+        &self,
+        series_options: &SeriesOptions,
+        dtstart: &DateTime<Tz>,
+    ) -> Result<Vec<RecurringSeries>, UnsupportedRRule> {
+        if !self.by_month_day.is_empty() && !self.by_day.is_empty() {
+            return Err(UnsupportedRRule::new(
+                "BYMONTHDAY cannot be combined with BYDAY for FREQ=YEARLY",
+            ));
+        }
+
+        // Without BYMONTHDAY or BYDAY, the rule repeats on the day of the month of its first
+        // occurrence; unless the rule moves missing days, months that never have that day
+        // (e.g. the 31st of April) are ignored, as the rule's invalid dates are.
+        let has_implicit_month_day = self.by_month_day.is_empty() && self.by_day.is_empty();
+        let month_days = if has_implicit_month_day {
+            vec![dtstart.day() as i32]
+        } else {
+            self.by_month_day.clone()
+        };
+
+        let mut recurring_series = Vec::new();
+
+        for month in &self.by_month {
+            for day in &month_days {
+                if *day < 0 {
+                    return Err(UnsupportedRRule::new(
+                        "Negative BYMONTHDAY values are not supported for FREQ=YEARLY",
+                    ));
+                }
+
+                let annual_series = match AnnuallyByMonthDay::new(
+                    *month,
+                    *day as u32,
+                    self.missing_day_policy,
+                    series_options.clone(),
+                ) {
+                    Err(_)
+                        if has_implicit_month_day
+                            && self.missing_day_policy == MissingDayPolicy::Skip =>
+                    {
+                        continue;
+                    }
+                    annual_series => annual_series.map_err(UnsupportedRRule::new)?,
+                };
+                recurring_series.push(RecurringSeries::AnnuallyByMonthDay(annual_series));
+            }
+
+            for (ordinal, weekday) in &self.by_day {
+                let ordinal = ordinal.ok_or_else(|| {
+                    UnsupportedRRule::new(
+                        "BYDAY weekdays without ordinals are not supported for FREQ=YEARLY",
+                    )
+                })?;
+
+                if ordinal.abs() > 5 {
+                    return Err(UnsupportedRRule::new(format!(
+                        "The weekday ordinal {} is out of range for a month",
+                        ordinal
+                    )));
+                }
+
+                // As for monthly rules, RRULE ordinals are 1-indexed whereas nth weekdays are not.
+                let nth_weekday = if ordinal > 0 { ordinal - 1 } else { ordinal };
+                let annual_series = AnnuallyByNthWeekday::from_nth_weekday(
+                    *month,
+                    *weekday,
+                    nth_weekday,
+                    series_options.clone(),
+                )
+                .map_err(UnsupportedRRule::new)?;
+                recurring_series.push(RecurringSeries::AnnuallyByNthWeekday(annual_series));
+            }
+        }
+
+        return Ok(recurring_series);
+    }
+
+    fn yearly_series_by_week_no(
+        &self,
+        series_options: SeriesOptions,
+    ) -> Result<RecurringSeries, UnsupportedRRule> {
+        // Week numbers follow ISO 8601 only when weeks start on Monday.
+        if self.week_start != Weekday::Mon {
+            return Err(UnsupportedRRule::new(
+                "BYWEEKNO is only supported when WKST=MO",
+            ));
+        }
+
+        // Without BYDAY, the rule occurs on every day of the selected weeks.
+        let weekdays = if self.by_day.is_empty() {
+            vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Sat,
+                Weekday::Sun,
+            ]
+        } else {
+            self.by_day
+                .iter()
+                .map(|(ordinal, weekday)| match ordinal {
+                    Some(_) => Err(UnsupportedRRule::new(
+                        "BYDAY ordinals cannot be combined with BYWEEKNO",
+                    )),
+                    None => Ok(*weekday),
+                })
+                .collect::<Result<Vec<Weekday>, UnsupportedRRule>>()?
+        };
+
+        return AnnuallyByIsoWeek::from_week_numbers(
+            self.by_week_no.clone(),
+            weekdays,
+            series_options,
+        )
+        .map(RecurringSeries::AnnuallyByIsoWeek)
+        .map_err(UnsupportedRRule::new);
+    }
+
+    fn weekly_series_from_by_day(
+        &self,
+        series_options: &SeriesOptions,
+    ) -> Result<Vec<RecurringSeries>, UnsupportedRRule> {
+        let weekdays = self
+            .by_day
+            .iter()
+            .map(|(ordinal, weekday)| match ordinal {
+                Some(_) => Err(UnsupportedRRule::new(
+                    "BYDAY ordinals are only supported for FREQ=MONTHLY",
+                )),
+                None => Ok(*weekday),
+            })
+            .collect::<Result<Vec<Weekday>, UnsupportedRRule>>()?;
+
+        return Ok(vec![RecurringSeries::Weekly(Weekly::from_weekdays(
+            weekdays,
+            self.week_start,
+            series_options.clone(),
+        ))]);
+    }
+
+    fn times_of_day_from(&self, time_of_day: &TimeOfDay) -> Vec<TimeOfDay> {
+        let values_or = |values: &Vec<u32>, default: u32| {
+            if values.is_empty() {
+                vec![default]
+            } else {
+                values.clone()
+            }
+        };
+        let mut times_of_day = Vec::new();
+
+        for hour in values_or(&self.by_hour, time_of_day.hour) {
+            for minute in values_or(&self.by_minute, time_of_day.minute) {
+                for second in values_or(&self.by_second, time_of_day.second) {
+                    times_of_day.push(TimeOfDay {
+                        hour,
+                        minute,
+                        second,
+                    });
+                }
+            }
+        }
+
+        return times_of_day;
+    }
+
+    fn reject_by_rules(&self, names: &[&str]) -> Result<(), UnsupportedRRule> {
+        for name in names {
+            let is_present = match *name {
+                "BYDAY" => !self.by_day.is_empty(),
+                "BYMONTHDAY" => !self.by_month_day.is_empty(),
+                "BYYEARDAY" => !self.by_year_day.is_empty(),
+                "BYMONTH" => !self.by_month.is_empty(),
+                "BYWEEKNO" => !self.by_week_no.is_empty(),
+                "BYSETPOS" => !self.by_set_pos.is_empty(),
+                "BYHOUR" => !self.by_hour.is_empty(),
+                "BYMINUTE" => !self.by_minute.is_empty(),
+                "BYSECOND" => !self.by_second.is_empty(),
+                _ => false,
+            };
+
+            if is_present {
+                return Err(UnsupportedRRule::new(format!(
+                    "{} is not supported for FREQ={}",
+                    name,
+                    self.frequency.name()
+                )));
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+fn parse_frequency(value: &str) -> Result<Frequency, UnsupportedRRule> {
+    return match value.to_ascii_uppercase().as_str() {
+        "SECONDLY" => Ok(Frequency::Secondly),
+        "MINUTELY" => Ok(Frequency::Minutely),
+        "HOURLY" => Ok(Frequency::Hourly),
+        "DAILY" => Ok(Frequency::Daily),
+        "WEEKLY" => Ok(Frequency::Weekly),
+        "MONTHLY" => Ok(Frequency::Monthly),
+        "YEARLY" => Ok(Frequency::Yearly),
+        _ => Err(UnsupportedRRule::new(format!(
+            "\"{}\" is not a valid FREQ",
+            value
+        ))),
+    };
+}
+
+pub(crate) fn parse_weekday(value: &str) -> Result<Weekday, UnsupportedRRule> {
+    return match value.to_ascii_uppercase().as_str() {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        _ => Err(UnsupportedRRule::new(format!(
+            "\"{}\" is not a valid weekday",
+            value
+        ))),
+    };
+}
+
+fn parse_by_day_entry(value: &str) -> Result<(Option<i32>, Weekday), UnsupportedRRule> {
+    if value.len() < 2 || !value.is_char_boundary(value.len() - 2) {
+        return Err(UnsupportedRRule::new(format!(
+            "\"{}\" is not a valid BYDAY value",
+            value
+        )));
+    }
+
+    let (ordinal, weekday) = value.split_at(value.len() - 2);
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        Some(parse_signed_ordinal("BYDAY", ordinal, 53)?)
+    };
+
+    return Ok((ordinal, parse_weekday(weekday)?));
+}
+
+fn parse_list<T, F>(value: &str, parse_entry: F) -> Result<Vec<T>, UnsupportedRRule>
+where
+    F: Fn(&str) -> Result<T, UnsupportedRRule>,
+{
+    return value
+        .split(',')
+        .map(|entry| parse_entry(entry.trim()))
+        .collect();
+}
+
+fn parse_number_in_range(
+    name: &str,
+    value: &str,
+    min: i64,
+    max: i64,
+) -> Result<i64, UnsupportedRRule> {
+    return value
+        .parse::<i64>()
+        .ok()
+        .filter(|number| *number >= min && *number <= max)
+        .ok_or_else(|| {
+            UnsupportedRRule::new(format!("\"{}\" is not a valid {} value", value, name))
+        });
+}
+
+/// Parses an RFC 7529 RSCALE; only the Gregorian calendar is supported.
+fn parse_rscale(value: &str) -> Result<(), UnsupportedRRule> {
+    return match value.to_ascii_uppercase().as_str() {
+        "GREGORIAN" => Ok(()),
+        _ => Err(UnsupportedRRule::new(format!(
+            "RSCALE={} is not supported",
+            value
+        ))),
+    };
+}
+
+/// Parses an RFC 7529 SKIP into the equivalent policy for the days that a month lacks.
+fn parse_skip(value: &str) -> Result<MissingDayPolicy, UnsupportedRRule> {
+    return match value.to_ascii_uppercase().as_str() {
+        "OMIT" => Ok(MissingDayPolicy::Skip),
+        "BACKWARD" => Ok(MissingDayPolicy::Clamp),
+        "FORWARD" => Ok(MissingDayPolicy::Roll),
+        _ => Err(UnsupportedRRule::new(format!(
+            "\"{}\" is not a valid SKIP value",
+            value
+        ))),
+    };
+}
+
+/// Parses an hour, minute or second within `0..=max`; leap seconds are not supported.
+fn parse_time_part(name: &str, value: &str, max: u32) -> Result<u32, UnsupportedRRule> {
+    return Ok(parse_number_in_range(name, value, 0, max as i64)? as u32);
+}
+
+/// Parses a non-zero ordinal within `-max..=max`, as used by `BYMONTHDAY`, `BYYEARDAY`, etc.
+fn parse_signed_ordinal(name: &str, value: &str, max: i32) -> Result<i32, UnsupportedRRule> {
+    let ordinal = parse_number_in_range(
+        name,
+        value.trim_start_matches('+'),
+        -(max as i64),
+        max as i64,
+    )?;
+
+    if ordinal == 0 {
+        return Err(UnsupportedRRule::new(format!(
+            "\"{}\" is not a valid {} value",
+            value, name
+        )));
+    }
+
+    return Ok(ordinal as i32);
+}
+
+/// Parses an UNTIL value into the given time zone; as UNTIL is inclusive, DATE values are
+/// read as the last second of that local day.
+fn parse_until(value: &str, time_zone: &Tz) -> Result<DateTime<Tz>, UnsupportedRRule> {
+    return match parse_date_or_date_time(value) {
+        Some(DateOrDateTime::Utc(naive_datetime)) => {
+            Ok(time_zone.from_utc_datetime(&naive_datetime))
+        }
+        Some(DateOrDateTime::Local(naive_datetime)) => Ok(localize(&naive_datetime, time_zone)),
+        Some(DateOrDateTime::Date(naive_date)) => Ok(localize(
+            &naive_date.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap()),
+            time_zone,
+        )),
+        None => Err(UnsupportedRRule::new(format!(
+            "\"{}\" is not a valid UNTIL value",
+            value
+        ))),
+    };
+}
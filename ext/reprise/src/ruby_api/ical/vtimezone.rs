@@ -0,0 +1,301 @@
+use crate::ruby_api::ical::reader::{Component, UnsupportedICal};
+use crate::ruby_api::ical::rrule::parse_weekday;
+use crate::ruby_api::ical::{parse_date_or_date_time, DateOrDateTime};
+use chrono::{
+    DateTime, Datelike, NaiveDate, NaiveDateTime, Offset, TimeDelta, TimeZone, Utc, Weekday,
+};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+// Every observance rule recurs yearly, so a time zone that agrees with a VTIMEZONE over two
+// years (checked hourly) agrees with it for as long as both keep their current rules.
+const EQUIVALENCE_CHECKED_HOURS: i64 = 2 * 366 * 24;
+
+/// A time zone defined inline by a VTIMEZONE component, for TZIDs that are not IANA
+/// names (e.g. "Eastern Standard Time" in calendars exported by Outlook).
+#[derive(Debug)]
+pub(crate) struct VTimezone {
+    pub(crate) tzid: String,
+    observances: Vec<Observance>,
+}
+
+/// A STANDARD or DAYLIGHT sub-component: the UTC offset it switches to, and the local
+/// times (expressed in the offset in effect beforehand) at which it takes effect.
+#[derive(Debug)]
+struct Observance {
+    offset_from_in_seconds: i64,
+    offset_to_in_seconds: i64,
+    dtstart: NaiveDateTime,
+    rdates: Vec<NaiveDateTime>,
+    rule: Option<ObservanceRule>,
+}
+
+/// The subset of RRULE used by VTIMEZONE observances in practice: a yearly onset on a
+/// given month, anchored to either the nth weekday or a day of that month.
+#[derive(Debug)]
+struct ObservanceRule {
+    month: u32,
+    onset_day: OnsetDay,
+    until: Option<NaiveDateTime>,
+}
+
+#[derive(Debug)]
+enum OnsetDay {
+    NthWeekday(i32, Weekday),
+    MonthDay(u32),
+}
+
+impl VTimezone {
+    pub(crate) fn parse(component: &Component) -> Result<VTimezone, UnsupportedICal> {
+        let tzid = component
+            .property("TZID")
+            .map(|property| property.value.clone())
+            .ok_or_else(|| UnsupportedICal::new("VTIMEZONE is missing a TZID"))?;
+
+        let observances = component
+            .components
+            .iter()
+            .filter(|c| c.name == "STANDARD" || c.name == "DAYLIGHT")
+            .map(Observance::parse)
+            .collect::<Result<Vec<Observance>, UnsupportedICal>>()?;
+
+        if observances.is_empty() {
+            return Err(UnsupportedICal::new(format!(
+                "VTIMEZONE {} has no STANDARD or DAYLIGHT observances",
+                tzid
+            )));
+        }
+
+        return Ok(VTimezone { tzid, observances });
+    }
+
+    /// Resolves a local time in this time zone to UTC. As elsewhere in Reprise, ambiguous
+    /// local times resolve to their later instant, and local times that fall in a gap
+    /// are shifted forward by the size of the gap.
+    pub(crate) fn to_utc(&self, local: &NaiveDateTime) -> DateTime<Utc> {
+        let candidates = self
+            .observances
+            .iter()
+            .flat_map(|o| [o.offset_from_in_seconds, o.offset_to_in_seconds])
+            .map(|offset| (offset, *local - TimeDelta::seconds(offset)))
+            .collect::<Vec<(i64, NaiveDateTime)>>();
+
+        let valid_candidate = candidates
+            .iter()
+            .filter(|(offset, utc)| self.offset_at(utc) == *offset)
+            .map(|(_, utc)| *utc)
+            .max();
+        let utc = valid_candidate
+            .or_else(|| candidates.iter().map(|(_, utc)| *utc).max())
+            .unwrap();
+
+        return utc.and_utc();
+    }
+
+    /// Finds a time zone whose UTC offsets agree with this one's from the given instant onwards,
+    /// preferring the given time zone over the others, so that the events defined in this one
+    /// can be expanded in it.
+    pub(crate) fn equivalent_time_zone(
+        &self,
+        preferred_time_zone: &Tz,
+        starts_at: &DateTime<Utc>,
+    ) -> Option<Tz> {
+        let offsets_in_seconds = (0..EQUIVALENCE_CHECKED_HOURS)
+            .map(|hours| {
+                let utc = starts_at.naive_utc() + TimeDelta::hours(hours);
+                return (utc, self.offset_at(&utc));
+            })
+            .collect::<Vec<(NaiveDateTime, i64)>>();
+
+        return std::iter::once(*preferred_time_zone)
+            .chain(TZ_VARIANTS)
+            .find(|time_zone| {
+                return offsets_in_seconds.iter().all(|(utc, offset_in_seconds)| {
+                    let offset = time_zone.offset_from_utc_datetime(utc).fix();
+                    return offset.local_minus_utc() as i64 == *offset_in_seconds;
+                });
+            });
+    }
+
+    fn offset_at(&self, utc: &NaiveDateTime) -> i64 {
+        let latest_onset = self
+            .observances
+            .iter()
+            .filter_map(|o| o.latest_onset_before(utc).map(|onset| (onset, o)))
+            .max_by_key(|(onset, _)| *onset);
+
+        return match latest_onset {
+            Some((_, observance)) => observance.offset_to_in_seconds,
+            None => {
+                self.observances
+                    .iter()
+                    .min_by_key(|o| o.dtstart)
+                    .unwrap()
+                    .offset_from_in_seconds
+            }
+        };
+    }
+}
+
+impl Observance {
+    fn parse(component: &Component) -> Result<Observance, UnsupportedICal> {
+        let required = |name: &str| {
+            return component
+                .property(name)
+                .map(|p| p.value.as_str())
+                .ok_or_else(|| {
+                    UnsupportedICal::new(format!(
+                        "{} observance is missing {}",
+                        component.name, name
+                    ))
+                });
+        };
+
+        let rdates = component
+            .properties_named("RDATE")
+            .flat_map(|property| property.value.split(','))
+            .map(parse_local_date_time)
+            .collect::<Result<Vec<NaiveDateTime>, UnsupportedICal>>()?;
+
+        let rule = match component.property("RRULE") {
+            None => None,
+            Some(property) => Some(ObservanceRule::parse(&property.value)?),
+        };
+
+        return Ok(Observance {
+            offset_from_in_seconds: parse_utc_offset(required("TZOFFSETFROM")?)?,
+            offset_to_in_seconds: parse_utc_offset(required("TZOFFSETTO")?)?,
+            dtstart: parse_local_date_time(required("DTSTART")?)?,
+            rdates,
+            rule,
+        });
+    }
+
+    /// The most recent instant, at or before the given UTC time, at which this
+    /// observance came into effect.
+    fn latest_onset_before(&self, utc: &NaiveDateTime) -> Option<NaiveDateTime> {
+        let to_utc = |local: NaiveDateTime| local - TimeDelta::seconds(self.offset_from_in_seconds);
+
+        let mut onsets = vec![to_utc(self.dtstart)];
+        onsets.extend(self.rdates.iter().map(|rdate| to_utc(*rdate)));
+
+        if let Some(rule) = &self.rule {
+            // An onset late in the previous year may still be the latest one.
+            for year in (self.dtstart.year()..=utc.year()).rev().take(2) {
+                if let Some(onset) = rule.onset_in_year(year, &self.dtstart) {
+                    if onset >= self.dtstart && rule.until.map_or(true, |until| onset <= until) {
+                        onsets.push(to_utc(onset));
+                    }
+                }
+            }
+        }
+
+        return onsets.into_iter().filter(|onset| onset <= utc).max();
+    }
+}
+
+impl ObservanceRule {
+    fn parse(rrule: &str) -> Result<ObservanceRule, UnsupportedICal> {
+        let unsupported = || {
+            return UnsupportedICal::new(format!(
+                "VTIMEZONE observance rule \"{}\" is not supported",
+                rrule
+            ));
+        };
+
+        let mut month = None;
+        let mut onset_day = None;
+        let mut until = None;
+
+        for part in rrule.split(';') {
+            let (name, value) = part.split_once('=').ok_or_else(unsupported)?;
+
+            match name {
+                "FREQ" if value == "YEARLY" => {}
+                "INTERVAL" if value == "1" => {}
+                "BYMONTH" => month = value.parse::<u32>().ok(),
+                "BYMONTHDAY" => onset_day = value.parse::<u32>().ok().map(OnsetDay::MonthDay),
+                "BYDAY" => {
+                    let (nth, weekday) = value.split_at(value.len().saturating_sub(2));
+                    let weekday = parse_weekday(weekday).map_err(|_| unsupported())?;
+                    onset_day = nth
+                        .parse::<i32>()
+                        .ok()
+                        .map(|nth| OnsetDay::NthWeekday(nth, weekday));
+                }
+                "UNTIL" => {
+                    until = match parse_date_or_date_time(value) {
+                        Some(DateOrDateTime::Utc(until) | DateOrDateTime::Local(until)) => {
+                            Some(until)
+                        }
+                        Some(DateOrDateTime::Date(until)) => until.and_hms_opt(23, 59, 59),
+                        None => return Err(unsupported()),
+                    }
+                }
+                _ => return Err(unsupported()),
+            }
+        }
+
+        return match (month, onset_day) {
+            (Some(month), Some(onset_day)) => Ok(ObservanceRule {
+                month,
+                onset_day,
+                until,
+            }),
+            _ => Err(unsupported()),
+        };
+    }
+
+    fn onset_in_year(&self, year: i32, dtstart: &NaiveDateTime) -> Option<NaiveDateTime> {
+        let date = match self.onset_day {
+            OnsetDay::MonthDay(day) => NaiveDate::from_ymd_opt(year, self.month, day),
+            OnsetDay::NthWeekday(nth, weekday) if nth > 0 => {
+                NaiveDate::from_weekday_of_month_opt(year, self.month, weekday, nth as u8)
+            }
+            OnsetDay::NthWeekday(nth, weekday) => {
+                let first_of_next_month = match self.month {
+                    12 => NaiveDate::from_ymd_opt(year + 1, 1, 1),
+                    month => NaiveDate::from_ymd_opt(year, month + 1, 1),
+                }?;
+                let last_of_month = first_of_next_month.pred_opt()?;
+                let days_back = (last_of_month.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                last_of_month
+                    .checked_sub_signed(TimeDelta::days(days_back as i64 + 7 * (-nth as i64 - 1)))
+                    .filter(|date| date.month() == self.month)
+            }
+        };
+
+        return date.map(|date| date.and_time(dtstart.time()));
+    }
+}
+
+fn parse_local_date_time(value: &str) -> Result<NaiveDateTime, UnsupportedICal> {
+    return match parse_date_or_date_time(value) {
+        Some(DateOrDateTime::Local(naive_datetime)) => Ok(naive_datetime),
+        _ => Err(UnsupportedICal::new(format!(
+            "\"{}\" is not a valid VTIMEZONE local time",
+            value
+        ))),
+    };
+}
+
+/// Parses a UTC-OFFSET value (e.g. "-0500" or "+053000") into seconds.
+fn parse_utc_offset(value: &str) -> Result<i64, UnsupportedICal> {
+    let invalid = || UnsupportedICal::new(format!("\"{}\" is not a valid UTC offset", value));
+
+    let (sign, digits) = match (value.strip_prefix('+'), value.strip_prefix('-')) {
+        (Some(digits), _) => (1, digits),
+        (_, Some(digits)) => (-1, digits),
+        _ => return Err(invalid()),
+    };
+
+    if !(digits.len() == 4 || digits.len() == 6) || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let field =
+        |range: std::ops::Range<usize>| digits.get(range).map_or(0, |d| d.parse::<i64>().unwrap());
+
+    return Ok(sign * (field(0..2) * 3600 + field(2..4) * 60 + field(4..6)));
+}
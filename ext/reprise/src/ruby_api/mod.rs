@@ -1,19 +1,27 @@
 use magnus::prelude::*;
 use magnus::{Error};
 
+mod business_calendar;
 pub mod clock;
+mod daily_window;
+mod derived_series;
 mod exclusion;
+mod holiday_calendar;
+mod ical;
 pub mod interval;
 mod occurrence;
+mod occurrence_iterator;
 mod recurring_series;
 mod ruby_modules;
 mod schedule;
+mod serialization;
 mod series_options;
 mod sorted_exclusions;
 mod time_of_day;
 mod traits;
 
 pub fn init() -> Result<(), Error> {
+    ruby_modules::init()?;
     schedule::init()?;
     occurrence::init()?;
     Ok(())
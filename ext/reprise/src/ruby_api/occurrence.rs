@@ -2,8 +2,9 @@ use crate::ruby_api::ruby_modules;
 use crate::ruby_api::traits::HasOverlapAwareness;
 use magnus::{class, method, Error, Module, Ruby, Time};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[magnus::wrap(class = "Reprise::Core::Occurrence")]
 pub(crate) struct Occurrence {
     pub(crate) starts_at_unix_timestamp: i64,
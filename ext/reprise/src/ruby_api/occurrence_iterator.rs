@@ -0,0 +1,633 @@
+use crate::ruby_api::clock::{datetime_on_date_safely, set_datetime_cursor_safely};
+use crate::ruby_api::derived_series::DerivedSeries;
+use crate::ruby_api::holiday_calendar::HolidayExclusions;
+use crate::ruby_api::occurrence::Occurrence;
+use crate::ruby_api::sorted_exclusions::SortedExclusions;
+use crate::ruby_api::traits::{Recurrable, RecurringSeries};
+use chrono::{DateTime, Duration, NaiveTime};
+use chrono_tz::Tz;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+// The Gregorian calendar repeats itself every 400 years (146,097 days), and a series' candidates
+// are drawn from the calendar alone; a series that yields no candidate for that long never will.
+const MAX_DAYS_WITHOUT_CANDIDATE: i64 = 146_097;
+
+/// The position of a single series' expansion, from which its occurrences can be drawn one
+/// at a time. A cursor always begins at the start of its series, so that the series' count
+/// and interval apply identically however far it is sought (e.g. to the start of a queried
+/// window) before drawing occurrences. The cursor does not own its series, so that
+/// it can be driven both by `Recurrable::generate_occurrences` and by a
+/// `ScheduleOccurrences` iterator that owns the series it merges.
+#[derive(Debug, Clone)]
+pub(crate) struct SeriesCursor {
+    starts_at: DateTime<Tz>,
+    // A cursor without an end only stops once its series' own count or bookend is reached.
+    ends_at: Option<DateTime<Tz>>,
+    datetime_cursor: DateTime<Tz>,
+    occurrence_count: u64,
+    first_period_index: i64,
+    // The candidates at the series' remaining times of day, on the day of the last
+    // candidate drawn from the series.
+    pending_candidates: VecDeque<DateTime<Tz>>,
+    // The period whose candidates at the series' set positions were last drawn; the rest of
+    // that period's candidates have been accounted for.
+    set_positions_period_index: Option<i64>,
+    // The last occurrence drawn from a series whose candidates are rolled onto business days;
+    // candidates that roll onto (or before) it are merged into it.
+    last_rolled_occurrence_datetime: Option<DateTime<Tz>>,
+    // Where the cursor was when the last candidate was drawn from the series (or when it was
+    // last sought); an open-ended cursor gives up on a series that has none left.
+    last_candidate_datetime_cursor: DateTime<Tz>,
+}
+
+impl SeriesCursor {
+    pub(crate) fn new<S: Recurrable + ?Sized>(
+        series: &S,
+        starts_at: DateTime<Tz>,
+        ends_at: Option<DateTime<Tz>>,
+    ) -> SeriesCursor {
+        // If the series itself has its own defined bookends, respect those; otherwise, fall back to the
+        // bookends passed by the parent schedule. Either way, the series is expanded in its own time
+        // zone, which may differ from the schedule's (e.g. that of an imported event's DTSTART).
+        let time_zone = series.get_series_options().time_zone();
+        let starts_at = series
+            .get_series_options()
+            .local_starts_at_datetime()
+            .unwrap_or(starts_at.with_timezone(&time_zone));
+        let ends_at = series
+            .get_series_options()
+            .local_ends_at_datetime()
+            .or(ends_at.map(|ends_at| ends_at.with_timezone(&time_zone)));
+
+        // The candidates of a rolled series are drawn from as far before its start as they
+        // can be rolled, since they may be rolled forward into it.
+        let max_roll = series.get_series_options().max_roll();
+
+        let datetime_cursor =
+            set_datetime_cursor_safely(starts_at - max_roll, series.naive_starts_at_time());
+
+        let mut series_cursor = SeriesCursor {
+            starts_at,
+            ends_at,
+            datetime_cursor,
+            occurrence_count: 0,
+            first_period_index: 0,
+            pending_candidates: VecDeque::new(),
+            set_positions_period_index: None,
+            last_rolled_occurrence_datetime: None,
+            last_candidate_datetime_cursor: datetime_cursor,
+        };
+
+        // The series' interval is counted from the period of its first candidate.
+        if let Some((first_candidate, _)) = series_cursor.clone().next_rolled_candidate(series) {
+            series_cursor.first_period_index = series.period_index(&first_candidate);
+        }
+
+        return series_cursor;
+    }
+
+    /// Moves the cursor forward so that the next occurrence it yields is the first one that
+    /// starts at or after the given instant. The occurrences before the instant are still
+    /// accounted for, so that a series' count and interval remain anchored to its start.
+    pub(crate) fn seek<S: Recurrable + ?Sized>(&mut self, series: &S, instant: &DateTime<Tz>) {
+        let instant = &instant.with_timezone(&self.datetime_cursor.timezone());
+
+        // Without a count, the position of a candidate within the series is irrelevant, so
+        // the cursor can jump straight to the instant; otherwise, every candidate before the
+        // instant has to be visited.
+        if series.get_series_options().count.is_none() {
+            // A rolled series' candidates may be rolled forward past the instant.
+            let sought_datetime_cursor = series.seek_datetime_cursor(
+                &self.datetime_cursor,
+                &(*instant - series.get_series_options().max_roll()),
+            );
+
+            if sought_datetime_cursor > self.datetime_cursor {
+                self.datetime_cursor = sought_datetime_cursor;
+                self.pending_candidates.clear();
+                // The sought period's candidates are drawn afresh; those before the instant
+                // are stepped over below.
+                self.set_positions_period_index = None;
+                self.last_candidate_datetime_cursor = sought_datetime_cursor;
+            }
+        }
+
+        // Step over the remaining occurrences that precede the instant, which still count
+        // towards the series' count.
+        loop {
+            let checkpoint = self.clone();
+
+            match self.next_occurrence_datetime(series) {
+                Some(occurrence_datetime) if occurrence_datetime < *instant => {
+                    continue;
+                }
+                _ => {
+                    *self = checkpoint;
+                    return;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn next_occurrence<S: Recurrable + ?Sized>(
+        &mut self,
+        series: &S,
+    ) -> Option<Occurrence> {
+        let occurrence_datetime = self.next_occurrence_datetime(series)?;
+
+        return Some(Occurrence {
+            starts_at_unix_timestamp: occurrence_datetime.timestamp(),
+            ends_at_unix_timestamp: (occurrence_datetime
+                + Duration::seconds(series.get_occurrence_duration_in_seconds()))
+            .timestamp(),
+            label: series.get_series_options().label(),
+        });
+    }
+
+    fn next_occurrence_datetime<S: Recurrable + ?Sized>(
+        &mut self,
+        series: &S,
+    ) -> Option<DateTime<Tz>> {
+        // Only yield the candidates in every Nth period if an interval has been requested.
+        let interval = series.get_series_options().interval.max(1);
+
+        while !series.is_occurrence_count_reached(self.occurrence_count) {
+            let (occurrence_candidate, occurrence_datetime) = self.next_rolled_candidate(series)?;
+            let elapsed_periods =
+                series.period_index(&occurrence_candidate) - self.first_period_index;
+
+            if elapsed_periods.rem_euclid(interval) == 0 {
+                self.occurrence_count += 1;
+                if series.get_series_options().roll.is_some() {
+                    self.last_rolled_occurrence_datetime = Some(occurrence_datetime);
+                }
+
+                return Some(occurrence_datetime);
+            }
+        }
+
+        return None;
+    }
+
+    // The next candidate, along with the time that it is rolled onto; without a roll, both are
+    // the same. Rolled candidates that fall outside of the bookends of the series, or onto an
+    // occurrence that has already been drawn, are skipped.
+    fn next_rolled_candidate<S: Recurrable + ?Sized>(
+        &mut self,
+        series: &S,
+    ) -> Option<(DateTime<Tz>, DateTime<Tz>)> {
+        let series_options = series.get_series_options();
+
+        loop {
+            let occurrence_candidate = self.next_candidate(series)?;
+
+            if series_options.roll.is_none() {
+                return Some((occurrence_candidate, occurrence_candidate));
+            }
+
+            let occurrence_datetime = series_options.rolled_datetime(&occurrence_candidate);
+
+            if occurrence_datetime >= self.starts_at
+                && self
+                    .ends_at
+                    .map_or(true, |ends_at| occurrence_datetime <= ends_at)
+                && self
+                    .last_rolled_occurrence_datetime
+                    .map_or(true, |last_datetime| occurrence_datetime > last_datetime)
+            {
+                return Some((occurrence_candidate, occurrence_datetime));
+            }
+        }
+    }
+
+    fn next_candidate<S: Recurrable + ?Sized>(&mut self, series: &S) -> Option<DateTime<Tz>> {
+        // The candidates of a rolled series are drawn from as far beyond its bookends as they
+        // can be rolled; the rolled candidates are held to the bookends themselves.
+        let max_roll = series.get_series_options().max_roll();
+        let starts_at = self.starts_at - max_roll;
+        let ends_at = self.ends_at.map(|ends_at| ends_at + max_roll);
+
+        loop {
+            let Some(occurrence_candidate_datetime) = self.pending_candidates.pop_front() else {
+                if ends_at.is_some_and(|ends_at| self.datetime_cursor >= ends_at) {
+                    return None;
+                }
+
+                // A series whose parameters no date can satisfy (e.g. a set position beyond the
+                // candidates in any of its periods) would otherwise be expanded until the end of
+                // the cursor, or forever without one.
+                if self.datetime_cursor - self.last_candidate_datetime_cursor
+                    > Duration::days(MAX_DAYS_WITHOUT_CANDIDATE)
+                {
+                    return None;
+                }
+
+                if let Some(occurrence_candidate_datetime) =
+                    series.next_occurrence_candidate(&self.datetime_cursor)
+                {
+                    self.pending_candidates =
+                        self.candidates_on_day_of(series, occurrence_candidate_datetime);
+                }
+                self.advance_datetime_cursor(series);
+
+                continue;
+            };
+
+            self.last_candidate_datetime_cursor = self.datetime_cursor;
+
+            if occurrence_candidate_datetime >= starts_at
+                && ends_at.map_or(true, |ends_at| occurrence_candidate_datetime <= ends_at)
+            {
+                return Some(occurrence_candidate_datetime);
+            }
+        }
+    }
+
+    // The candidates to draw from the series on the day of the given candidate. With set
+    // positions, all of the candidates that they select within a period are drawn on the
+    // first of its days that the cursor visits, and none on the days that follow.
+    fn candidates_on_day_of<S: Recurrable + ?Sized>(
+        &mut self,
+        series: &S,
+        occurrence_candidate: DateTime<Tz>,
+    ) -> VecDeque<DateTime<Tz>> {
+        if series.get_series_options().set_positions().is_empty() {
+            return candidates_at_each_time_of_day(series, occurrence_candidate);
+        }
+
+        let period_index = series.period_index(&occurrence_candidate);
+        if self.set_positions_period_index == Some(period_index) {
+            return VecDeque::new();
+        }
+        self.set_positions_period_index = Some(period_index);
+
+        return candidates_at_set_positions(series, occurrence_candidate);
+    }
+
+    fn advance_datetime_cursor<S: Recurrable + ?Sized>(&mut self, series: &S) {
+        let considered_datetime_cursor = self.datetime_cursor;
+        self.datetime_cursor = series.advance_datetime_cursor(&self.datetime_cursor);
+
+        if self.datetime_cursor == considered_datetime_cursor {
+            // If we fail to advance, and the next cursor is identical to the
+            // last, there is an edge case in a series' advance cursor
+            // logic that would, if left to its own devices, result in an
+            // infinite loop that would never terminate. We explicitly panic
+            // here as there is no other way to recover, callers need to be
+            // informed via an exception.
+            panic!(
+                "Infinite loop prevented during schedule expansion. \
+                This is a bug in Reprise, please share with the maintainers. \
+                Affected series: {:?}",
+                series
+            );
+        }
+    }
+}
+
+/// Expands a series' candidate, which falls at its earliest time of day, into the
+/// candidates at each of the series' times of day on the same day, in ascending order.
+fn candidates_at_each_time_of_day<S: Recurrable + ?Sized>(
+    series: &S,
+    occurrence_candidate: DateTime<Tz>,
+) -> VecDeque<DateTime<Tz>> {
+    let times_of_day = series.get_series_options().times_of_day();
+
+    if !series.occurs_at_each_time_of_day() || times_of_day.len() == 1 {
+        return VecDeque::from([occurrence_candidate]);
+    }
+
+    // N.B. A time of day that falls in a gap in local time is moved out of the gap, possibly
+    // past (or onto) one of its successors, so the candidates are ordered once placed.
+    let mut occurrence_candidates = vec![occurrence_candidate];
+    occurrence_candidates.extend(times_of_day[1..].iter().map(|time_of_day| {
+        datetime_on_date_safely(
+            &occurrence_candidate.timezone(),
+            occurrence_candidate.date_naive(),
+            time_of_day.to_naive_time(),
+        )
+    }));
+    occurrence_candidates.sort();
+    occurrence_candidates.dedup();
+
+    return VecDeque::from(occurrence_candidates);
+}
+
+/// Selects the candidates at the series' set positions among all of its candidates (at each of
+/// its times of day) within the period that contains the given candidate, in ascending order.
+/// The whole period is expanded, regardless of the bookends of the series, so that positions
+/// are counted identically however the series is queried.
+fn candidates_at_set_positions<S: Recurrable + ?Sized>(
+    series: &S,
+    occurrence_candidate: DateTime<Tz>,
+) -> VecDeque<DateTime<Tz>> {
+    let period_index = series.period_index(&occurrence_candidate);
+    let period_start_date = series
+        .period_start_date(&occurrence_candidate)
+        .unwrap_or(occurrence_candidate.date_naive());
+
+    let mut datetime_cursor = series.seek_datetime_cursor(
+        &occurrence_candidate,
+        &datetime_on_date_safely(
+            &occurrence_candidate.timezone(),
+            period_start_date,
+            NaiveTime::MIN,
+        ),
+    );
+    let mut period_candidates = Vec::new();
+
+    while series.period_index(&datetime_cursor) <= period_index {
+        if let Some(period_candidate) = series.next_occurrence_candidate(&datetime_cursor) {
+            if series.period_index(&period_candidate) == period_index {
+                period_candidates.extend(candidates_at_each_time_of_day(series, period_candidate));
+            }
+        }
+
+        let considered_datetime_cursor = datetime_cursor;
+        datetime_cursor = series.advance_datetime_cursor(&datetime_cursor);
+        if datetime_cursor <= considered_datetime_cursor {
+            break;
+        }
+    }
+
+    let candidate_count = period_candidates.len() as i64;
+    let mut selected_candidates: Vec<DateTime<Tz>> = series
+        .get_series_options()
+        .set_positions()
+        .iter()
+        .filter_map(|set_position| {
+            let candidate_index = if *set_position > 0 {
+                *set_position as i64 - 1
+            } else {
+                candidate_count + *set_position as i64
+            };
+
+            return if (0..candidate_count).contains(&candidate_index) {
+                Some(period_candidates[candidate_index as usize])
+            } else {
+                None
+            };
+        })
+        .collect();
+    selected_candidates.sort();
+    selected_candidates.dedup();
+
+    return VecDeque::from(selected_candidates);
+}
+
+/// The position of a derived series' expansion from one of its base series. Each occurrence of
+/// the base series that is not excluded is moved by the derived series' offset; the moved
+/// occurrences are held to the bookends of the schedule.
+#[derive(Debug, Clone)]
+pub(crate) struct DerivedSeriesCursor {
+    derived_series: DerivedSeries,
+    // The index of the base series among the schedule's recurring series.
+    base_series_index: usize,
+    base_series_cursor: SeriesCursor,
+    starts_at: DateTime<Tz>,
+    ends_at: Option<DateTime<Tz>>,
+}
+
+impl DerivedSeriesCursor {
+    /// Cursors for each of the derived series' base series among the given recurring series,
+    /// which are held to the given schedule bookends; the derived occurrences are held to the
+    /// (possibly narrower) given end.
+    pub(crate) fn for_base_series(
+        derived_series: &DerivedSeries,
+        recurring_series: &[RecurringSeries],
+        starts_at: DateTime<Tz>,
+        schedule_ends_at: Option<DateTime<Tz>>,
+        ends_at: Option<DateTime<Tz>>,
+    ) -> Vec<DerivedSeriesCursor> {
+        // The base occurrences that are moved onto the end of the derived series can lie
+        // beyond it, by as much as the offset moves them.
+        let max_shift = derived_series.offset.max_shift();
+        let base_series_ends_at = match (schedule_ends_at, ends_at) {
+            (Some(schedule_ends_at), Some(ends_at)) => {
+                Some(schedule_ends_at.min(ends_at + max_shift))
+            }
+            (schedule_ends_at, ends_at) => {
+                schedule_ends_at.or(ends_at.map(|ends_at| ends_at + max_shift))
+            }
+        };
+
+        return recurring_series
+            .iter()
+            .enumerate()
+            .filter(|(_, series)| {
+                series.get_series_options().label.as_ref() == Some(&derived_series.base_label)
+            })
+            .map(|(base_series_index, base_series)| DerivedSeriesCursor {
+                derived_series: derived_series.clone(),
+                base_series_index,
+                base_series_cursor: SeriesCursor::new(base_series, starts_at, base_series_ends_at),
+                starts_at,
+                ends_at,
+            })
+            .collect();
+    }
+
+    /// Moves the cursor forward so that the next occurrence it yields is the first one that
+    /// starts at or after the given instant.
+    pub(crate) fn seek(&mut self, recurring_series: &[RecurringSeries], instant: &DateTime<Tz>) {
+        let base_series = &recurring_series[self.base_series_index];
+        self.base_series_cursor.seek(
+            base_series,
+            &(*instant - self.derived_series.offset.max_shift()),
+        );
+        self.starts_at = self.starts_at.max(*instant);
+    }
+
+    pub(crate) fn next_occurrence(
+        &mut self,
+        recurring_series: &[RecurringSeries],
+        sorted_exclusions: &SortedExclusions,
+        holiday_exclusions: &mut HolidayExclusions,
+    ) -> Option<Occurrence> {
+        let base_series = &recurring_series[self.base_series_index];
+
+        loop {
+            let base_occurrence = self.base_series_cursor.next_occurrence(base_series)?;
+
+            // An occurrence derived from an excluded occurrence (e.g. a reminder of a cancelled
+            // meeting) is excluded along with it.
+            if base_series
+                .get_series_options()
+                .is_occurrence_excluded(&base_occurrence)
+                || sorted_exclusions.is_occurrence_excluded(&base_occurrence)
+                || holiday_exclusions.is_occurrence_excluded(&base_occurrence)
+            {
+                continue;
+            }
+
+            let base_occurrence_datetime =
+                DateTime::from_timestamp(base_occurrence.starts_at_unix_timestamp, 0)
+                    .unwrap()
+                    .with_timezone(&self.starts_at.timezone());
+            let occurrence_datetime = self.derived_series.offset.apply(&base_occurrence_datetime);
+
+            // The base series' occurrences are moved in order, so the derived occurrences
+            // remain in ascending order.
+            if self
+                .ends_at
+                .is_some_and(|ends_at| occurrence_datetime > ends_at)
+            {
+                return None;
+            }
+
+            if occurrence_datetime < self.starts_at {
+                continue;
+            }
+
+            return Some(Occurrence {
+                starts_at_unix_timestamp: occurrence_datetime.timestamp(),
+                ends_at_unix_timestamp: (occurrence_datetime
+                    + Duration::seconds(self.derived_series.duration_in_seconds))
+                .timestamp(),
+                label: self.derived_series.label.clone(),
+            });
+        }
+    }
+}
+
+/// Lazily yields the occurrences of a set of series (and of any series derived from them) in
+/// ascending order of their start times (optionally, from a sought instant onwards), omitting
+/// any that overlap with an exclusion or fall on a holiday. Each series is only expanded as
+/// far as the occurrences that have been consumed: the iterator holds at most one
+/// pending occurrence per series, and merges them through a min-heap.
+#[derive(Debug)]
+pub(crate) struct ScheduleOccurrences {
+    recurring_series: Vec<RecurringSeries>,
+    series_cursors: Vec<SeriesCursor>,
+    derived_series_cursors: Vec<DerivedSeriesCursor>,
+    pending_occurrences: Vec<Option<Occurrence>>,
+    // Occurrences that were added to the schedule one at a time, in ascending order of their
+    // start times; they are merged as though they were the occurrences of a single series.
+    one_off_occurrences: VecDeque<Occurrence>,
+    // Ordered by start time, then by the index of the series in `recurring_series`; the
+    // derived series' cursors are indexed after the recurring series, and the one-off
+    // occurrences after those.
+    queue: BinaryHeap<Reverse<(i64, usize)>>,
+    sorted_exclusions: SortedExclusions,
+    holiday_exclusions: HolidayExclusions,
+}
+
+impl ScheduleOccurrences {
+    pub(crate) fn new(
+        recurring_series: Vec<RecurringSeries>,
+        derived_series_cursors: Vec<DerivedSeriesCursor>,
+        one_off_occurrences: Vec<Occurrence>,
+        sorted_exclusions: SortedExclusions,
+        holiday_exclusions: HolidayExclusions,
+        starts_at: DateTime<Tz>,
+        ends_at: Option<DateTime<Tz>>,
+        sought_instant: Option<DateTime<Tz>>,
+    ) -> ScheduleOccurrences {
+        let series_cursors = recurring_series
+            .iter()
+            .map(|series| {
+                let mut series_cursor = SeriesCursor::new(series, starts_at, ends_at);
+                if let Some(sought_instant) = &sought_instant {
+                    series_cursor.seek(series, sought_instant);
+                }
+                series_cursor
+            })
+            .collect();
+        let derived_series_cursors: Vec<DerivedSeriesCursor> = derived_series_cursors
+            .into_iter()
+            .map(|mut derived_series_cursor| {
+                if let Some(sought_instant) = &sought_instant {
+                    derived_series_cursor.seek(&recurring_series, sought_instant);
+                }
+                derived_series_cursor
+            })
+            .collect();
+        // One-off occurrences are held to the same bookends as the occurrences of a series.
+        let one_off_occurrences_start_at =
+            sought_instant.map_or(starts_at, |sought_instant| sought_instant.max(starts_at));
+        let one_off_occurrences: VecDeque<Occurrence> = one_off_occurrences
+            .into_iter()
+            .filter(|o| {
+                o.starts_at_unix_timestamp >= one_off_occurrences_start_at.timestamp()
+                    && ends_at.map_or(true, |ends_at| {
+                        o.starts_at_unix_timestamp <= ends_at.timestamp()
+                    })
+            })
+            .collect();
+
+        let mut schedule_occurrences = ScheduleOccurrences {
+            pending_occurrences: (0..=(recurring_series.len() + derived_series_cursors.len()))
+                .map(|_| None)
+                .collect(),
+            recurring_series,
+            series_cursors,
+            derived_series_cursors,
+            one_off_occurrences,
+            queue: BinaryHeap::new(),
+            sorted_exclusions,
+            holiday_exclusions,
+        };
+
+        for series_index in 0..schedule_occurrences.pending_occurrences.len() {
+            schedule_occurrences.enqueue_next_occurrence(series_index);
+        }
+
+        return schedule_occurrences;
+    }
+
+    fn enqueue_next_occurrence(&mut self, series_index: usize) {
+        let next_occurrence = match self.series_cursors.get_mut(series_index) {
+            Some(series_cursor) => {
+                series_cursor.next_occurrence(&self.recurring_series[series_index])
+            }
+            None => match self
+                .derived_series_cursors
+                .get_mut(series_index - self.recurring_series.len())
+            {
+                Some(derived_series_cursor) => derived_series_cursor.next_occurrence(
+                    &self.recurring_series,
+                    &self.sorted_exclusions,
+                    &mut self.holiday_exclusions,
+                ),
+                None => self.one_off_occurrences.pop_front(),
+            },
+        };
+
+        if let Some(occurrence) = next_occurrence {
+            self.queue
+                .push(Reverse((occurrence.starts_at_unix_timestamp, series_index)));
+            self.pending_occurrences[series_index] = Some(occurrence);
+        }
+    }
+}
+
+impl Iterator for ScheduleOccurrences {
+    type Item = Occurrence;
+
+    fn next(&mut self) -> Option<Occurrence> {
+        loop {
+            let Reverse((_, series_index)) = self.queue.pop()?;
+            let occurrence = self.pending_occurrences[series_index]
+                .take()
+                .expect("Queued series should have a pending occurrence");
+            self.enqueue_next_occurrence(series_index);
+
+            let is_excluded_from_series =
+                self.recurring_series
+                    .get(series_index)
+                    .is_some_and(|series| {
+                        series
+                            .get_series_options()
+                            .is_occurrence_excluded(&occurrence)
+                    });
+
+            if !is_excluded_from_series
+                && !self.sorted_exclusions.is_occurrence_excluded(&occurrence)
+                && !self.holiday_exclusions.is_occurrence_excluded(&occurrence)
+            {
+                return Some(occurrence);
+            }
+        }
+    }
+}
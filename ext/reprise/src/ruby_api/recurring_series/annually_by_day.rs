@@ -1,21 +1,32 @@
-use crate::ruby_api::clock::set_datetime_cursor_safely;
+use crate::ruby_api::clock::{datetime_on_date_safely, set_datetime_cursor_safely};
 use crate::ruby_api::series_options::SeriesOptions;
 use crate::ruby_api::traits::Recurrable;
-use chrono::{DateTime, Datelike, TimeDelta};
+use chrono::{DateTime, Datelike, NaiveDate, TimeDelta};
 use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct AnnuallyByDay {
     pub(crate) day_number: u32,
     pub(crate) series_options: SeriesOptions,
 }
 
 impl AnnuallyByDay {
-    pub(crate) fn new(day_number: u32, series_options: SeriesOptions) -> AnnuallyByDay {
-        return AnnuallyByDay {
+    pub(crate) fn new(
+        day_number: u32,
+        series_options: SeriesOptions,
+    ) -> Result<AnnuallyByDay, String> {
+        if !(1..=366).contains(&day_number) {
+            return Err(format!(
+                "The day {} is out of range; it must be between 1 and 366",
+                day_number
+            ));
+        }
+
+        return Ok(AnnuallyByDay {
             day_number,
             series_options,
-        };
+        });
     }
 
     fn safely_advance_one_year(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
@@ -78,4 +89,35 @@ impl Recurrable for AnnuallyByDay {
             return set_datetime_cursor_safely(next_day, self.naive_starts_at_time());
         };
     }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        return occurrence_candidate.year() as i64;
+    }
+
+    fn period_start_date(&self, occurrence_candidate: &DateTime<Tz>) -> Option<NaiveDate> {
+        return NaiveDate::from_ymd_opt(occurrence_candidate.year(), 1, 1);
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        let instant_date = instant.date_naive();
+
+        // The 366th day only appears in leap years, which can be as many as eight years apart.
+        for year in instant_date.year()..=(instant_date.year() + 8) {
+            if let Some(date) = NaiveDate::from_yo_opt(year, self.day_number) {
+                if date >= instant_date {
+                    return datetime_on_date_safely(
+                        &datetime_cursor.timezone(),
+                        date,
+                        self.naive_starts_at_time(),
+                    );
+                }
+            }
+        }
+
+        return datetime_cursor.clone();
+    }
 }
@@ -0,0 +1,186 @@
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::series_options::SeriesOptions;
+use crate::ruby_api::traits::Recurrable;
+use chrono::{DateTime, Datelike, NaiveDate, Weekday};
+use chrono_tz::Tz;
+use magnus::Symbol;
+use serde::{Deserialize, Serialize};
+
+// ISO years have either 52 or 53 weeks.
+const MAX_WEEKS_IN_ISO_YEAR: i32 = 53;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct AnnuallyByIsoWeek {
+    // ISO 8601 week numbers, counted from the start of the ISO year, or from its end if
+    // negative (-1 being the last week).
+    pub(crate) week_numbers: Vec<i32>,
+    pub(crate) weekdays: Vec<Weekday>,
+    pub(crate) series_options: SeriesOptions,
+}
+
+impl AnnuallyByIsoWeek {
+    pub(crate) fn new(
+        week_numbers: Vec<i32>,
+        weekday_symbols: Vec<Symbol>,
+        series_options: SeriesOptions,
+    ) -> Result<AnnuallyByIsoWeek, String> {
+        let weekdays = weekday_symbols
+            .iter()
+            .map(|weekday_symbol| {
+                weekday_symbol
+                    .to_string()
+                    .parse::<Weekday>()
+                    .map_err(|_| format!("Unsupported weekday {}", weekday_symbol))
+            })
+            .collect::<Result<Vec<Weekday>, String>>()?;
+
+        return AnnuallyByIsoWeek::from_week_numbers(week_numbers, weekdays, series_options);
+    }
+
+    pub(crate) fn from_week_numbers(
+        week_numbers: Vec<i32>,
+        weekdays: Vec<Weekday>,
+        series_options: SeriesOptions,
+    ) -> Result<AnnuallyByIsoWeek, String> {
+        if week_numbers.is_empty() {
+            return Err("At least one week number is required".to_string());
+        }
+
+        if weekdays.is_empty() {
+            return Err("At least one weekday is required".to_string());
+        }
+
+        if let Some(week_number) = week_numbers
+            .iter()
+            .find(|week_number| **week_number == 0 || week_number.abs() > MAX_WEEKS_IN_ISO_YEAR)
+        {
+            return Err(format!(
+                "The week number {} is out of range; it must be between 1 and {} or -{} and -1",
+                week_number, MAX_WEEKS_IN_ISO_YEAR, MAX_WEEKS_IN_ISO_YEAR
+            ));
+        }
+
+        let mut week_numbers = week_numbers;
+        week_numbers.sort();
+        week_numbers.dedup();
+
+        let mut weekdays = weekdays;
+        weekdays.sort_by_key(|weekday| weekday.num_days_from_monday());
+        weekdays.dedup();
+
+        series_options.validate_set_positions(week_numbers.len() * weekdays.len(), "year")?;
+
+        return Ok(AnnuallyByIsoWeek {
+            week_numbers,
+            weekdays,
+            series_options,
+        });
+    }
+
+    fn weeks_in_iso_year(iso_year: i32) -> u32 {
+        return if NaiveDate::from_isoywd_opt(iso_year, 53, Weekday::Mon).is_some() {
+            53
+        } else {
+            52
+        };
+    }
+
+    // The dates that the series occurs on in the given ISO year, in ascending order.
+    fn dates_for_iso_year(&self, iso_year: i32) -> Vec<NaiveDate> {
+        let weeks_in_iso_year = AnnuallyByIsoWeek::weeks_in_iso_year(iso_year) as i32;
+
+        let mut dates: Vec<NaiveDate> = self
+            .week_numbers
+            .iter()
+            .map(|week_number| {
+                if *week_number > 0 {
+                    *week_number
+                } else {
+                    weeks_in_iso_year + 1 + *week_number
+                }
+            })
+            .filter(|week| *week >= 1 && *week <= weeks_in_iso_year)
+            .flat_map(|week| {
+                self.weekdays.iter().filter_map(move |weekday| {
+                    NaiveDate::from_isoywd_opt(iso_year, week as u32, *weekday)
+                })
+            })
+            .collect();
+        dates.sort();
+        dates.dedup();
+
+        return dates;
+    }
+
+    fn occurs_on(&self, date: &NaiveDate) -> bool {
+        return self
+            .dates_for_iso_year(date.iso_week().year())
+            .contains(date);
+    }
+
+    // The first date after (or, optionally, on) the given date that the series occurs on.
+    fn next_date(&self, date: &NaiveDate, inclusive: bool) -> Option<NaiveDate> {
+        let iso_year = date.iso_week().year();
+
+        // ISO years with 53 weeks can be as many as seven years apart.
+        return (iso_year..=(iso_year + 7))
+            .flat_map(|examined_iso_year| self.dates_for_iso_year(examined_iso_year))
+            .find(|examined_date| examined_date > date || (inclusive && examined_date == date));
+    }
+
+    fn datetime_on_next_date(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        date: &NaiveDate,
+        inclusive: bool,
+    ) -> DateTime<Tz> {
+        // Every week number that passes validation appears within the years examined; a series
+        // restored from an invalid payload only ever moves forward, several years at a time.
+        let next_date = self.next_date(date, inclusive).unwrap_or_else(|| {
+            NaiveDate::from_ymd_opt(date.year() + 8, 1, 1).expect("Year should advance")
+        });
+
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            next_date,
+            self.naive_starts_at_time(),
+        );
+    }
+}
+
+impl Recurrable for AnnuallyByIsoWeek {
+    fn get_series_options(&self) -> &SeriesOptions {
+        return &self.series_options;
+    }
+
+    fn next_occurrence_candidate(&self, datetime_cursor: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        return if self.occurs_on(&datetime_cursor.date_naive()) {
+            Some(datetime_cursor).cloned()
+        } else {
+            None
+        };
+    }
+
+    fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
+        // Move directly to the next day that the series occurs on.
+        return self.datetime_on_next_date(datetime_cursor, &datetime_cursor.date_naive(), false);
+    }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        // An interval skips ISO years, which may begin in the last days of the calendar year
+        // before, or end in the first days of the one after.
+        return occurrence_candidate.iso_week().year() as i64;
+    }
+
+    fn period_start_date(&self, occurrence_candidate: &DateTime<Tz>) -> Option<NaiveDate> {
+        return NaiveDate::from_isoywd_opt(occurrence_candidate.iso_week().year(), 1, Weekday::Mon);
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        return self.datetime_on_next_date(datetime_cursor, &instant.date_naive(), true);
+    }
+}
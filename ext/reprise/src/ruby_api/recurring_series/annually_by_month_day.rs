@@ -0,0 +1,122 @@
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::recurring_series::monthly_by_day::MissingDayPolicy;
+use crate::ruby_api::series_options::SeriesOptions;
+use crate::ruby_api::traits::Recurrable;
+use chrono::{DateTime, Datelike, NaiveDate};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+// A leap year, in which every day of every month exists.
+const LEAP_YEAR: i32 = 2000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct AnnuallyByMonthDay {
+    pub(crate) month: u32,
+    pub(crate) day: u32,
+    pub(crate) series_options: SeriesOptions,
+    // Only the 29th of February is ever missing; this decides what becomes of it in common years.
+    #[serde(default)]
+    pub(crate) missing_day_policy: MissingDayPolicy,
+}
+
+impl AnnuallyByMonthDay {
+    pub(crate) fn new(
+        month: u32,
+        day: u32,
+        missing_day_policy: MissingDayPolicy,
+        series_options: SeriesOptions,
+    ) -> Result<AnnuallyByMonthDay, String> {
+        if !(1..=12).contains(&month) {
+            return Err(format!(
+                "The month {} is out of range; it must be between 1 and 12",
+                month
+            ));
+        }
+
+        if NaiveDate::from_ymd_opt(LEAP_YEAR, month, day).is_none() {
+            return Err(format!("The day {} does not exist in month {}", day, month));
+        }
+
+        return Ok(AnnuallyByMonthDay {
+            month,
+            day,
+            series_options,
+            missing_day_policy,
+        });
+    }
+
+    // The date that the series occurs on in the given year, if any.
+    fn date_for_year(&self, year: i32) -> Option<NaiveDate> {
+        return NaiveDate::from_ymd_opt(year, self.month, self.day).or_else(|| {
+            match self.missing_day_policy {
+                MissingDayPolicy::Skip => None,
+                MissingDayPolicy::Clamp => NaiveDate::from_ymd_opt(year, self.month, self.day - 1),
+                MissingDayPolicy::Roll => NaiveDate::from_ymd_opt(year, self.month + 1, 1),
+            }
+        });
+    }
+
+    // The first date after (or, optionally, on) the given date that the series occurs on.
+    fn next_date(&self, date: &NaiveDate, inclusive: bool) -> Option<NaiveDate> {
+        // Leap years can be as many as eight years apart.
+        return (date.year()..=(date.year() + 8))
+            .filter_map(|year| self.date_for_year(year))
+            .find(|examined_date| examined_date > date || (inclusive && examined_date == date));
+    }
+
+    fn datetime_on_next_date(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        date: &NaiveDate,
+        inclusive: bool,
+    ) -> DateTime<Tz> {
+        // A series without any day that exists has no occurrences; it only ever moves
+        // forward, several years at a time.
+        let next_date = self.next_date(date, inclusive).unwrap_or_else(|| {
+            NaiveDate::from_ymd_opt(date.year() + 9, 1, 1).expect("Year should advance")
+        });
+
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            next_date,
+            self.naive_starts_at_time(),
+        );
+    }
+}
+
+impl Recurrable for AnnuallyByMonthDay {
+    fn get_series_options(&self) -> &SeriesOptions {
+        return &self.series_options;
+    }
+
+    fn next_occurrence_candidate(&self, datetime_cursor: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let cursor_date = datetime_cursor.date_naive();
+
+        return if self.date_for_year(cursor_date.year()) == Some(cursor_date) {
+            Some(datetime_cursor).cloned()
+        } else {
+            None
+        };
+    }
+
+    fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
+        // Move directly to the next day that the series occurs on.
+        return self.datetime_on_next_date(datetime_cursor, &datetime_cursor.date_naive(), false);
+    }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        return occurrence_candidate.year() as i64;
+    }
+
+    fn period_start_date(&self, occurrence_candidate: &DateTime<Tz>) -> Option<NaiveDate> {
+        return NaiveDate::from_ymd_opt(occurrence_candidate.year(), 1, 1);
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        return self.datetime_on_next_date(datetime_cursor, &instant.date_naive(), true);
+    }
+}
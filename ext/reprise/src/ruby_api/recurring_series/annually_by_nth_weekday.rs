@@ -0,0 +1,130 @@
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::recurring_series::monthly_by_nth_weekday::{
+    nth_weekday_in_month, validate_nth_weekday,
+};
+use crate::ruby_api::series_options::SeriesOptions;
+use crate::ruby_api::traits::Recurrable;
+use chrono::{DateTime, Datelike, NaiveDate, Weekday};
+use chrono_tz::Tz;
+use magnus::Symbol;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct AnnuallyByNthWeekday {
+    pub(crate) month: u32,
+    pub(crate) weekday: Weekday,
+    // 0-indexed from the start of the month, or counting back from its end if negative.
+    pub(crate) nth_weekday: i32,
+    pub(crate) series_options: SeriesOptions,
+}
+
+impl AnnuallyByNthWeekday {
+    pub(crate) fn new(
+        month: u32,
+        weekday_symbol: Symbol,
+        nth_weekday: i32,
+        series_options: SeriesOptions,
+    ) -> Result<AnnuallyByNthWeekday, String> {
+        let weekday = weekday_symbol
+            .to_string()
+            .parse::<Weekday>()
+            .map_err(|_| format!("Unsupported weekday {}", weekday_symbol))?;
+
+        return AnnuallyByNthWeekday::from_nth_weekday(month, weekday, nth_weekday, series_options);
+    }
+
+    pub(crate) fn from_nth_weekday(
+        month: u32,
+        weekday: Weekday,
+        nth_weekday: i32,
+        series_options: SeriesOptions,
+    ) -> Result<AnnuallyByNthWeekday, String> {
+        if !(1..=12).contains(&month) {
+            return Err(format!(
+                "The month {} is out of range; it must be between 1 and 12",
+                month
+            ));
+        }
+
+        validate_nth_weekday(nth_weekday)?;
+
+        return Ok(AnnuallyByNthWeekday {
+            month,
+            weekday,
+            nth_weekday,
+            series_options,
+        });
+    }
+
+    // The date that the series occurs on in the given year, if the month has that many weekdays.
+    fn date_for_year(&self, year: i32) -> Option<NaiveDate> {
+        return nth_weekday_in_month(
+            NaiveDate::from_ymd_opt(year, self.month, 1)?,
+            self.weekday,
+            self.nth_weekday,
+        );
+    }
+
+    // The first date after (or, optionally, on) the given date that the series occurs on.
+    fn next_date(&self, date: &NaiveDate, inclusive: bool) -> Option<NaiveDate> {
+        // The fifth occurrence of a weekday in February only exists in some leap years, which
+        // can be decades apart; the calendar repeats itself every 400 years.
+        return (date.year()..=(date.year() + 400))
+            .filter_map(|year| self.date_for_year(year))
+            .find(|examined_date| examined_date > date || (inclusive && examined_date == date));
+    }
+
+    fn datetime_on_next_date(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        date: &NaiveDate,
+        inclusive: bool,
+    ) -> DateTime<Tz> {
+        let next_date = self.next_date(date, inclusive).unwrap_or_else(|| {
+            NaiveDate::from_ymd_opt(date.year() + 401, 1, 1).expect("Year should advance")
+        });
+
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            next_date,
+            self.naive_starts_at_time(),
+        );
+    }
+}
+
+impl Recurrable for AnnuallyByNthWeekday {
+    fn get_series_options(&self) -> &SeriesOptions {
+        return &self.series_options;
+    }
+
+    fn next_occurrence_candidate(&self, datetime_cursor: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let cursor_date = datetime_cursor.date_naive();
+
+        return if self.date_for_year(cursor_date.year()) == Some(cursor_date) {
+            Some(datetime_cursor).cloned()
+        } else {
+            None
+        };
+    }
+
+    fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
+        // Move directly to the next day that the series occurs on.
+        return self.datetime_on_next_date(datetime_cursor, &datetime_cursor.date_naive(), false);
+    }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        return occurrence_candidate.year() as i64;
+    }
+
+    fn period_start_date(&self, occurrence_candidate: &DateTime<Tz>) -> Option<NaiveDate> {
+        return NaiveDate::from_ymd_opt(occurrence_candidate.year(), 1, 1);
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        return self.datetime_on_next_date(datetime_cursor, &instant.date_naive(), true);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::ruby_api::business_calendar::BusinessCalendar;
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::series_options::SeriesOptions;
+use crate::ruby_api::traits::Recurrable;
+use chrono::{DateTime, NaiveDate};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct BusinessDaily {
+    // The series occurs on every business day of the calendar that the schedule had when the
+    // series was added; its periods (and so its interval) are business days.
+    pub(crate) business_calendar: BusinessCalendar,
+    pub(crate) series_options: SeriesOptions,
+}
+
+impl BusinessDaily {
+    pub(crate) fn new(
+        business_calendar: BusinessCalendar,
+        series_options: SeriesOptions,
+    ) -> BusinessDaily {
+        return BusinessDaily {
+            business_calendar,
+            series_options,
+        };
+    }
+
+    fn datetime_on_next_date(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        date: &NaiveDate,
+        inclusive: bool,
+    ) -> DateTime<Tz> {
+        // Every week has a business day, and the calendar has finitely many holidays.
+        let next_date = self
+            .business_calendar
+            .next_business_day(date, inclusive)
+            .expect("Business calendar should have a next business day");
+
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            next_date,
+            self.naive_starts_at_time(),
+        );
+    }
+}
+
+impl Recurrable for BusinessDaily {
+    fn get_series_options(&self) -> &SeriesOptions {
+        return &self.series_options;
+    }
+
+    fn next_occurrence_candidate(&self, datetime_cursor: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        return if self
+            .business_calendar
+            .is_business_day(&datetime_cursor.date_naive())
+        {
+            Some(datetime_cursor).cloned()
+        } else {
+            None
+        };
+    }
+
+    fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
+        // Move directly to the next business day.
+        return self.datetime_on_next_date(datetime_cursor, &datetime_cursor.date_naive(), false);
+    }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        return self
+            .business_calendar
+            .business_day_ordinal(&occurrence_candidate.date_naive());
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        return self.datetime_on_next_date(datetime_cursor, &instant.date_naive(), true);
+    }
+}
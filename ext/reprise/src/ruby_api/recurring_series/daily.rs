@@ -1,10 +1,11 @@
+use crate::ruby_api::clock::datetime_on_date_safely;
 use crate::ruby_api::series_options::SeriesOptions;
-use crate::ruby_api::time_of_day::TimeOfDay;
 use crate::ruby_api::traits::Recurrable;
-use chrono::{DateTime, Days, TimeDelta};
+use chrono::{DateTime, Datelike, Days, TimeDelta};
 use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct Daily {
     pub(crate) series_options: SeriesOptions,
 }
@@ -20,10 +21,6 @@ impl Recurrable for Daily {
         return &self.series_options;
     }
 
-    fn get_time_of_day(&self) -> &TimeOfDay {
-        return &self.series_options.time_of_day;
-    }
-
     fn get_occurrence_duration_in_seconds(&self) -> i64 {
         return self.series_options.duration_in_seconds;
     }
@@ -63,4 +60,20 @@ impl Recurrable for Daily {
                     .with_timezone(&datetime_cursor.timezone())
             });
     }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        return occurrence_candidate.date_naive().num_days_from_ce() as i64;
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            instant.date_naive(),
+            self.naive_starts_at_time(),
+        );
+    }
 }
@@ -1,7 +1,15 @@
 pub mod annually_by_day;
+pub mod annually_by_iso_week;
+pub mod annually_by_month_day;
+pub mod annually_by_nth_weekday;
+pub mod business_daily;
 pub mod daily;
 pub mod hourly;
 pub mod minutely;
 pub mod monthly_by_day;
+pub mod monthly_by_nth_business_day;
 pub mod monthly_by_nth_weekday;
+pub mod monthly_by_weekday;
+pub mod secondly;
+pub mod sub_daily;
 pub mod weekly;
@@ -1,22 +1,172 @@
-use crate::ruby_api::clock::advance_time_safely;
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::ruby_modules;
+use crate::ruby_api::serialization::one_or_many;
 use crate::ruby_api::series_options::SeriesOptions;
 use crate::ruby_api::traits::Recurrable;
-use chrono::{DateTime, Datelike, Months, TimeDelta};
+use chrono::{DateTime, Datelike, Months, NaiveDate};
 use chrono_tz::Tz;
+use magnus::{Error, Symbol};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+/// What a monthly series does in a month that lacks one of its days (e.g. the 31st of April).
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MissingDayPolicy {
+    /// The series does not occur on that day in that month.
+    #[default]
+    Skip,
+    /// The series occurs on the closest day of the month instead; the last day for days
+    /// counted from the start of the month, or the first for days counted from its end.
+    Clamp,
+    /// The series occurs on the first day of the following month instead.
+    Roll,
+}
+
+impl MissingDayPolicy {
+    pub(crate) fn from_symbol(missing_day_symbol: Symbol) -> Result<MissingDayPolicy, Error> {
+        return match missing_day_symbol.to_string().as_str() {
+            "skip" => Ok(MissingDayPolicy::Skip),
+            "clamp" => Ok(MissingDayPolicy::Clamp),
+            "roll" => Ok(MissingDayPolicy::Roll),
+            unsupported => Err(Error::new(
+                ruby_modules::argument_error(),
+                format!("Unsupported missing_day :{}", unsupported),
+            )),
+        };
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct MonthlyByDay {
-    pub(crate) day_number: u32,
+    // Days are counted from the start of the month, or from its end if negative (-1 being
+    // the last day). Schedules serialized before a monthly series could repeat on several
+    // days recorded a single `day_number`.
+    #[serde(alias = "day_number", deserialize_with = "one_or_many")]
+    pub(crate) day_numbers: Vec<i32>,
     pub(crate) series_options: SeriesOptions,
+    #[serde(default)]
+    pub(crate) missing_day_policy: MissingDayPolicy,
 }
 
 impl MonthlyByDay {
-    pub(crate) fn new(day_number: u32, series_options: SeriesOptions) -> MonthlyByDay {
-        return MonthlyByDay {
-            day_number,
+    pub(crate) fn new(
+        day_numbers: Vec<i32>,
+        missing_day_policy: MissingDayPolicy,
+        series_options: SeriesOptions,
+    ) -> Result<MonthlyByDay, String> {
+        if let Some(day_number) = day_numbers
+            .iter()
+            .find(|day_number| **day_number == 0 || day_number.abs() > 31)
+        {
+            return Err(format!(
+                "The day {} is out of range; it must be between 1 and 31, or -31 and -1",
+                day_number
+            ));
+        }
+
+        series_options.validate_set_positions(day_numbers.len(), "month")?;
+
+        return Ok(MonthlyByDay {
+            day_numbers,
             series_options,
+            missing_day_policy,
+        });
+    }
+
+    // The dates that the series occurs on for the given month, in ascending order; when
+    // missing days are rolled over, this may include the first day of the following month.
+    fn dates_for_month(&self, first_of_month: NaiveDate) -> Vec<NaiveDate> {
+        let first_of_next_month = first_of_month + Months::new(1);
+        let days_in_month = (first_of_next_month - first_of_month).num_days() as i32;
+
+        let mut dates: Vec<NaiveDate> = self
+            .day_numbers
+            .iter()
+            .filter(|day_number| **day_number != 0)
+            .filter_map(|day_number| {
+                let day = if *day_number > 0 {
+                    *day_number
+                } else {
+                    days_in_month + 1 + *day_number
+                };
+
+                if day >= 1 && day <= days_in_month {
+                    return first_of_month.with_day(day as u32);
+                }
+
+                return match self.missing_day_policy {
+                    MissingDayPolicy::Skip => None,
+                    MissingDayPolicy::Clamp => {
+                        first_of_month.with_day(day.clamp(1, days_in_month) as u32)
+                    }
+                    MissingDayPolicy::Roll => Some(first_of_next_month),
+                };
+            })
+            .collect();
+        dates.sort();
+        dates.dedup();
+
+        return dates;
+    }
+
+    fn is_rolled_over_into(&self, date: &NaiveDate) -> bool {
+        let first_of_previous_month = date.with_day(1).unwrap() - Months::new(1);
+
+        return self.missing_day_policy == MissingDayPolicy::Roll
+            && self.dates_for_month(first_of_previous_month).contains(date);
+    }
+
+    // The first day of the month whose days the given date was drawn from; a day rolled
+    // over from the previous month belongs to that month, even if the series also occurs
+    // on that day in its own month.
+    fn scheduled_month_of(&self, date: &NaiveDate) -> NaiveDate {
+        let first_of_month = date.with_day(1).unwrap();
+
+        return if self.is_rolled_over_into(date) {
+            first_of_month - Months::new(1)
+        } else {
+            first_of_month
         };
     }
+
+    fn occurs_on(&self, date: &NaiveDate) -> bool {
+        return self.is_rolled_over_into(date)
+            || self
+                .dates_for_month(date.with_day(1).unwrap())
+                .contains(date);
+    }
+
+    // The first date after (or, optionally, on) the given date that the series occurs on.
+    fn next_date(&self, date: &NaiveDate, inclusive: bool) -> Option<NaiveDate> {
+        let first_of_month = date.with_day(1).unwrap();
+
+        // Any day that exists at all appears at least once within two consecutive months;
+        // the month before is examined for days rolled over into the given month.
+        return (0..4)
+            .flat_map(|month_offset| {
+                self.dates_for_month(first_of_month - Months::new(1) + Months::new(month_offset))
+            })
+            .find(|examined_date| examined_date > date || (inclusive && examined_date == date));
+    }
+
+    fn datetime_on_next_date(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        date: &NaiveDate,
+        inclusive: bool,
+    ) -> DateTime<Tz> {
+        // A series without any day that exists has no occurrences; it only ever moves
+        // forward, a few months at a time.
+        let next_date = self
+            .next_date(date, inclusive)
+            .unwrap_or_else(|| date.with_day(1).unwrap() + Months::new(3));
+
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            next_date,
+            self.naive_starts_at_time(),
+        );
+    }
 }
 
 impl Recurrable for MonthlyByDay {
@@ -25,7 +175,7 @@ impl Recurrable for MonthlyByDay {
     }
 
     fn next_occurrence_candidate(&self, datetime_cursor: &DateTime<Tz>) -> Option<DateTime<Tz>> {
-        return if datetime_cursor.day() == self.day_number {
+        return if self.occurs_on(&datetime_cursor.date_naive()) {
             Some(datetime_cursor).cloned()
         } else {
             None
@@ -33,26 +183,25 @@ impl Recurrable for MonthlyByDay {
     }
 
     fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
-        return if datetime_cursor.day() == self.day_number {
-            // If the current value already falls on the right day, moving forward
-            // we only need to increment by month.
-            match datetime_cursor.checked_add_months(Months::new(1)) {
-                None => datetime_cursor
-                    .to_utc()
-                    .checked_add_months(Months::new(1))
-                    .expect("Datetime must advance")
-                    .with_timezone(&datetime_cursor.timezone()),
-                Some(new_datetime_cursor) => new_datetime_cursor
-                    .with_time(self.naive_starts_at_time())
-                    .latest()
-                    .unwrap(),
-            }
-        } else {
-            advance_time_safely(
-                datetime_cursor,
-                TimeDelta::days(1),
-                self.naive_starts_at_time(),
-            )
-        };
+        // Move directly to the next day that the series occurs on.
+        return self.datetime_on_next_date(datetime_cursor, &datetime_cursor.date_naive(), false);
+    }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        let scheduled_month = self.scheduled_month_of(&occurrence_candidate.date_naive());
+
+        return scheduled_month.year() as i64 * 12 + scheduled_month.month0() as i64;
+    }
+
+    fn period_start_date(&self, occurrence_candidate: &DateTime<Tz>) -> Option<NaiveDate> {
+        return Some(self.scheduled_month_of(&occurrence_candidate.date_naive()));
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        return self.datetime_on_next_date(datetime_cursor, &instant.date_naive(), true);
     }
 }
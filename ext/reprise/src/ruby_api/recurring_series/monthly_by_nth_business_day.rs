@@ -0,0 +1,112 @@
+use crate::ruby_api::business_calendar::BusinessCalendar;
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::series_options::SeriesOptions;
+use crate::ruby_api::traits::Recurrable;
+use chrono::{DateTime, Datelike, Months, NaiveDate};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MonthlyByNthBusinessDay {
+    // 0-indexed from the start of the month, or counting back from its end if negative (-1
+    // being the last); months without that many business days are skipped.
+    pub(crate) nth_business_day: i32,
+    pub(crate) business_calendar: BusinessCalendar,
+    pub(crate) series_options: SeriesOptions,
+}
+
+impl MonthlyByNthBusinessDay {
+    pub(crate) fn new(
+        nth_business_day: i32,
+        business_calendar: BusinessCalendar,
+        series_options: SeriesOptions,
+    ) -> Result<MonthlyByNthBusinessDay, String> {
+        // No month has more business days than its weekend leaves it.
+        let max_business_days_in_month = business_calendar.max_business_days_in_month();
+        if !(-max_business_days_in_month..max_business_days_in_month).contains(&nth_business_day) {
+            return Err(format!(
+                "The nth business day {} is out of range; it must be between {} and {}",
+                nth_business_day,
+                -max_business_days_in_month,
+                max_business_days_in_month - 1
+            ));
+        }
+
+        return Ok(MonthlyByNthBusinessDay {
+            nth_business_day,
+            business_calendar,
+            series_options,
+        });
+    }
+
+    fn date_for_month(&self, first_of_month: NaiveDate) -> Option<NaiveDate> {
+        return self
+            .business_calendar
+            .nth_business_day_in_month(first_of_month, self.nth_business_day);
+    }
+
+    fn occurs_on(&self, date: &NaiveDate) -> bool {
+        return self.date_for_month(date.with_day(1).unwrap()) == Some(*date);
+    }
+
+    // The first date after (or, optionally, on) the given date that the series occurs on.
+    fn next_date(&self, date: &NaiveDate, inclusive: bool) -> Option<NaiveDate> {
+        let first_of_month = date.with_day(1).unwrap();
+
+        return (0..13)
+            .filter_map(|month_offset| {
+                self.date_for_month(first_of_month + Months::new(month_offset))
+            })
+            .find(|examined_date| examined_date > date || (inclusive && examined_date == date));
+    }
+
+    fn datetime_on_next_date(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        date: &NaiveDate,
+        inclusive: bool,
+    ) -> DateTime<Tz> {
+        // A calendar whose holidays leave no month of the coming year with enough business
+        // days only ever moves forward, a year at a time.
+        let next_date = self
+            .next_date(date, inclusive)
+            .unwrap_or_else(|| date.with_day(1).unwrap() + Months::new(12));
+
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            next_date,
+            self.naive_starts_at_time(),
+        );
+    }
+}
+
+impl Recurrable for MonthlyByNthBusinessDay {
+    fn get_series_options(&self) -> &SeriesOptions {
+        return &self.series_options;
+    }
+
+    fn next_occurrence_candidate(&self, datetime_cursor: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        return if self.occurs_on(&datetime_cursor.date_naive()) {
+            Some(datetime_cursor).cloned()
+        } else {
+            None
+        };
+    }
+
+    fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
+        // Move directly to the next day that the series occurs on.
+        return self.datetime_on_next_date(datetime_cursor, &datetime_cursor.date_naive(), false);
+    }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        return occurrence_candidate.year() as i64 * 12 + occurrence_candidate.month0() as i64;
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        return self.datetime_on_next_date(datetime_cursor, &instant.date_naive(), true);
+    }
+}
@@ -1,113 +1,174 @@
-use crate::ruby_api::clock::advance_time_safely;
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::serialization::one_or_many;
 use crate::ruby_api::series_options::SeriesOptions;
 use crate::ruby_api::traits::Recurrable;
-use chrono::{DateTime, Datelike, Months, TimeDelta, Weekday};
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Weekday};
 use chrono_tz::Tz;
 use magnus::Symbol;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+// No weekday occurs more than five times in a month.
+const MAX_WEEKDAYS_IN_MONTH: i32 = 5;
+
+pub(crate) fn validate_nth_weekday(nth_weekday: i32) -> Result<(), String> {
+    if !(-MAX_WEEKDAYS_IN_MONTH..MAX_WEEKDAYS_IN_MONTH).contains(&nth_weekday) {
+        return Err(format!(
+            "The nth weekday {} is out of range; it must be between {} and {}",
+            nth_weekday,
+            -MAX_WEEKDAYS_IN_MONTH,
+            MAX_WEEKDAYS_IN_MONTH - 1
+        ));
+    }
+
+    return Ok(());
+}
+
+// The date of the nth weekday of the given month, if the month has that many.
+pub(crate) fn nth_weekday_in_month(
+    first_of_month: NaiveDate,
+    weekday: Weekday,
+    nth_weekday: i32,
+) -> Option<NaiveDate> {
+    let nth_weekday_date = if nth_weekday >= 0 {
+        let days_until_weekday = (weekday.num_days_from_monday() + 7
+            - first_of_month.weekday().num_days_from_monday())
+            % 7;
+
+        first_of_month.checked_add_days(Days::new(
+            days_until_weekday as u64 + 7 * nth_weekday as u64,
+        ))?
+    } else {
+        let last_of_month = first_of_month + Months::new(1) - Days::new(1);
+        let days_since_weekday = (last_of_month.weekday().num_days_from_monday() + 7
+            - weekday.num_days_from_monday())
+            % 7;
+
+        last_of_month.checked_sub_days(Days::new(
+            days_since_weekday as u64 + 7 * (nth_weekday.unsigned_abs() as u64 - 1),
+        ))?
+    };
+
+    return if nth_weekday_date.month() == first_of_month.month() {
+        Some(nth_weekday_date)
+    } else {
+        None
+    };
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct MonthlyByNthWeekday {
-    pub(crate) weekday: Weekday,
-    pub(crate) nth_weekday: i32,
+    // Each weekday is paired with the nth weekday at the same position; nth weekdays are
+    // 0-indexed from the start of the month, or count back from its end if negative (-1 being
+    // the last). Schedules serialized before a monthly series could repeat on several weekdays
+    // recorded a single `weekday` and `nth_weekday`.
+    #[serde(alias = "weekday", deserialize_with = "one_or_many")]
+    pub(crate) weekdays: Vec<Weekday>,
+    #[serde(alias = "nth_weekday", deserialize_with = "one_or_many")]
+    pub(crate) nth_weekdays: Vec<i32>,
     pub(crate) series_options: SeriesOptions,
 }
 
 impl MonthlyByNthWeekday {
     pub(crate) fn new(
-        weekday_symbol: Symbol,
-        nth_weekday: i32,
+        nth_weekday_symbols: Vec<(Symbol, i32)>,
         series_options: SeriesOptions,
-    ) -> MonthlyByNthWeekday {
-        let weekday = weekday_symbol
-            .to_string()
-            .parse::<Weekday>()
-            .expect("Weekday should parse successfully");
-
-        return MonthlyByNthWeekday {
-            weekday,
-            nth_weekday,
-            series_options,
-        };
+    ) -> Result<MonthlyByNthWeekday, String> {
+        let nth_weekdays = nth_weekday_symbols
+            .into_iter()
+            .map(|(weekday_symbol, nth_weekday)| {
+                let weekday = weekday_symbol
+                    .to_string()
+                    .parse::<Weekday>()
+                    .map_err(|_| format!("Unsupported weekday {}", weekday_symbol))?;
+
+                return Ok((weekday, nth_weekday));
+            })
+            .collect::<Result<Vec<(Weekday, i32)>, String>>()?;
+
+        return MonthlyByNthWeekday::from_nth_weekdays(nth_weekdays, series_options);
     }
 
-    fn identify_all_weekdays_in_month_of(&self, datetime: &DateTime<Tz>) -> Vec<DateTime<Tz>> {
-        let mut weekdays_in_month: Vec<DateTime<Tz>> = Vec::new();
-        let start_of_month = self.start_of_month_from(datetime);
-        let end_of_month: DateTime<Tz> = self.beginning_of_next_month_from(&start_of_month);
+    pub(crate) fn from_nth_weekdays(
+        nth_weekdays: Vec<(Weekday, i32)>,
+        series_options: SeriesOptions,
+    ) -> Result<MonthlyByNthWeekday, String> {
+        if nth_weekdays.is_empty() {
+            return Err("At least one nth weekday is required".to_string());
+        }
 
-        let mut examined_datetime = start_of_month;
+        for (_, nth_weekday) in &nth_weekdays {
+            validate_nth_weekday(*nth_weekday)?;
+        }
 
-        // Set the cursor to the first day in the month with the requested weekday.
-        while examined_datetime < end_of_month {
-            if examined_datetime.weekday() == self.weekday {
-                break;
-            }
+        series_options.validate_set_positions(nth_weekdays.len(), "month")?;
 
-            examined_datetime = advance_time_safely(
-                &examined_datetime,
-                TimeDelta::days(1),
-                self.naive_starts_at_time(),
-            );
-        }
+        let (weekdays, nth_weekdays) = nth_weekdays.into_iter().unzip();
 
-        // Push the day and all remaining days with the same weekday into the collection.
-        while examined_datetime < end_of_month {
-            weekdays_in_month.push(examined_datetime);
+        return Ok(MonthlyByNthWeekday {
+            weekdays,
+            nth_weekdays,
+            series_options,
+        });
+    }
 
-            examined_datetime = advance_time_safely(
-                &examined_datetime,
-                TimeDelta::days(7),
-                self.naive_starts_at_time(),
-            );
-        }
+    pub(crate) fn nth_weekday_pairs(&self) -> impl Iterator<Item = (Weekday, i32)> + '_ {
+        return self
+            .weekdays
+            .iter()
+            .copied()
+            .zip(self.nth_weekdays.iter().copied());
+    }
 
-        return weekdays_in_month;
+    // The dates that the series occurs on for the given month, in ascending order.
+    fn dates_for_month(&self, first_of_month: NaiveDate) -> Vec<NaiveDate> {
+        let mut dates: Vec<NaiveDate> = self
+            .nth_weekday_pairs()
+            .filter_map(|(weekday, nth_weekday)| {
+                nth_weekday_in_month(first_of_month, weekday, nth_weekday)
+            })
+            .collect();
+        dates.sort();
+        dates.dedup();
+
+        return dates;
     }
 
-    fn get_unsigned_nth_weekday_index_from_signed(&self, collection_size: i32) -> usize {
-        return if self.nth_weekday >= 0 {
-            // Return the request nth weekday index if positive.
-            self.nth_weekday as usize
-        } else {
-            // TODO: if the index is STILL negative (imagine indexing -7 into a collection of four
-            // elements) then we should panic. A slice access of -4, for example, makes very little
-            // sense relative to something like 1, or 2.
-            (collection_size - self.nth_weekday.abs()) as usize
-        };
+    fn occurs_on(&self, date: &NaiveDate) -> bool {
+        return self
+            .dates_for_month(date.with_day(1).unwrap())
+            .contains(date);
     }
 
-    fn beginning_of_next_month_from(&self, datetime: &DateTime<Tz>) -> DateTime<Tz> {
-        return if datetime.month() == 12 {
-            datetime
-                .with_year(datetime.year() + 1)
-                .expect("Datetime should advance to the next year")
-                .with_month(1)
-                .expect("Datetime should be set to January")
-                .with_day(1)
-                .expect("Datetime should be set to the first day of the month")
-        } else {
-            match datetime.checked_add_months(Months::new(1)) {
-                None => datetime
-                    .to_utc()
-                    .checked_add_months(Months::new(1))
-                    .expect("Datetime should advance by one month")
-                    .with_timezone(&datetime.timezone())
-                    .with_day(1)
-                    .expect("Datetime should be set to the first day of the month"),
-                Some(datetime) => datetime
-                    .with_day(1)
-                    .expect("Datetime should be set to the first of the month"),
-            }
-        };
+    // The first date after (or, optionally, on) the given date that the series occurs on.
+    fn next_date(&self, date: &NaiveDate, inclusive: bool) -> Option<NaiveDate> {
+        let first_of_month = date.with_day(1).unwrap();
+
+        // Every weekday occurs at least four times in every month, and a fifth time in at
+        // least one month of any year.
+        return (0..13)
+            .flat_map(|month_offset| {
+                self.dates_for_month(first_of_month + Months::new(month_offset))
+            })
+            .find(|examined_date| examined_date > date || (inclusive && examined_date == date));
     }
 
-    fn start_of_month_from(&self, datetime: &DateTime<Tz>) -> DateTime<Tz> {
-        return datetime
-            .with_day(1) // TODO: could this panic if the first falls on a DST / ST transition
-            .expect("Datetime should be set to the first day of the month")
-            .with_time(self.naive_starts_at_time())
-            .latest()
-            .expect("Datetime should be set to the requested time of day");
+    fn datetime_on_next_date(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        date: &NaiveDate,
+        inclusive: bool,
+    ) -> DateTime<Tz> {
+        // Series restored from schedules serialized before nth weekdays were validated may
+        // have no dates at all; they only ever move forward, a year at a time.
+        let next_date = self
+            .next_date(date, inclusive)
+            .unwrap_or_else(|| date.with_day(1).unwrap() + Months::new(12));
+
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            next_date,
+            self.naive_starts_at_time(),
+        );
     }
 }
 
@@ -117,17 +178,31 @@ impl Recurrable for MonthlyByNthWeekday {
     }
 
     fn next_occurrence_candidate(&self, datetime_cursor: &DateTime<Tz>) -> Option<DateTime<Tz>> {
-        let current_weekdays_in_examined_month =
-            self.identify_all_weekdays_in_month_of(&datetime_cursor);
-
-        return current_weekdays_in_examined_month
-            .get(self.get_unsigned_nth_weekday_index_from_signed(
-                current_weekdays_in_examined_month.len() as i32,
-            ))
-            .cloned();
+        return if self.occurs_on(&datetime_cursor.date_naive()) {
+            Some(datetime_cursor).cloned()
+        } else {
+            None
+        };
     }
 
     fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
-        return self.beginning_of_next_month_from(&datetime_cursor);
+        // Move directly to the next day that the series occurs on.
+        return self.datetime_on_next_date(datetime_cursor, &datetime_cursor.date_naive(), false);
+    }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        return occurrence_candidate.year() as i64 * 12 + occurrence_candidate.month0() as i64;
+    }
+
+    fn period_start_date(&self, occurrence_candidate: &DateTime<Tz>) -> Option<NaiveDate> {
+        return occurrence_candidate.date_naive().with_day(1);
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        return self.datetime_on_next_date(datetime_cursor, &instant.date_naive(), true);
     }
 }
@@ -0,0 +1,123 @@
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::series_options::SeriesOptions;
+use crate::ruby_api::traits::Recurrable;
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Weekday};
+use chrono_tz::Tz;
+use magnus::Symbol;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MonthlyByWeekday {
+    // The series occurs on every one of these weekdays within each month; unlike a weekly
+    // series, its periods (and so its interval and set positions) are months.
+    pub(crate) weekdays: Vec<Weekday>,
+    pub(crate) series_options: SeriesOptions,
+}
+
+impl MonthlyByWeekday {
+    pub(crate) fn new(
+        weekday_symbols: Vec<Symbol>,
+        series_options: SeriesOptions,
+    ) -> Result<MonthlyByWeekday, String> {
+        let weekdays = weekday_symbols
+            .iter()
+            .map(|weekday_symbol| {
+                weekday_symbol
+                    .to_string()
+                    .parse::<Weekday>()
+                    .map_err(|_| format!("Unsupported weekday {}", weekday_symbol))
+            })
+            .collect::<Result<Vec<Weekday>, String>>()?;
+
+        return MonthlyByWeekday::from_weekdays(weekdays, series_options);
+    }
+
+    pub(crate) fn from_weekdays(
+        weekdays: Vec<Weekday>,
+        series_options: SeriesOptions,
+    ) -> Result<MonthlyByWeekday, String> {
+        if weekdays.is_empty() {
+            return Err("At least one weekday is required".to_string());
+        }
+
+        let mut weekdays = weekdays;
+        weekdays.sort_by_key(|weekday| weekday.num_days_from_monday());
+        weekdays.dedup();
+
+        // Only the first three days of the week that a 31-day month begins on recur five times.
+        series_options
+            .validate_set_positions(weekdays.len() * 4 + weekdays.len().min(3), "month")?;
+
+        return Ok(MonthlyByWeekday {
+            weekdays,
+            series_options,
+        });
+    }
+
+    fn occurs_on(&self, date: &NaiveDate) -> bool {
+        return self.weekdays.contains(&date.weekday());
+    }
+
+    // The first date after (or, optionally, on) the given date that the series occurs on.
+    fn next_date(&self, date: &NaiveDate, inclusive: bool) -> Option<NaiveDate> {
+        let first_day_offset = if inclusive { 0 } else { 1 };
+
+        return (first_day_offset..(first_day_offset + 7))
+            .filter_map(|day_offset| date.checked_add_days(Days::new(day_offset)))
+            .find(|examined_date| self.occurs_on(examined_date));
+    }
+
+    fn datetime_on_next_date(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        date: &NaiveDate,
+        inclusive: bool,
+    ) -> DateTime<Tz> {
+        // A series restored without any weekdays has no occurrences; it only ever moves
+        // forward, a month at a time.
+        let next_date = self
+            .next_date(date, inclusive)
+            .unwrap_or_else(|| date.with_day(1).unwrap() + Months::new(1));
+
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            next_date,
+            self.naive_starts_at_time(),
+        );
+    }
+}
+
+impl Recurrable for MonthlyByWeekday {
+    fn get_series_options(&self) -> &SeriesOptions {
+        return &self.series_options;
+    }
+
+    fn next_occurrence_candidate(&self, datetime_cursor: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        return if self.occurs_on(&datetime_cursor.date_naive()) {
+            Some(datetime_cursor).cloned()
+        } else {
+            None
+        };
+    }
+
+    fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
+        // Move directly to the next day that the series occurs on.
+        return self.datetime_on_next_date(datetime_cursor, &datetime_cursor.date_naive(), false);
+    }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        return occurrence_candidate.year() as i64 * 12 + occurrence_candidate.month0() as i64;
+    }
+
+    fn period_start_date(&self, occurrence_candidate: &DateTime<Tz>) -> Option<NaiveDate> {
+        return occurrence_candidate.date_naive().with_day(1);
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        return self.datetime_on_next_date(datetime_cursor, &instant.date_naive(), true);
+    }
+}
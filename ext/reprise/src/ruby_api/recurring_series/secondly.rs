@@ -0,0 +1,3 @@
+use crate::ruby_api::recurring_series::sub_daily::SubDaily;
+
+pub(crate) type Secondly = SubDaily<1>;
@@ -0,0 +1,128 @@
+use crate::ruby_api::clock::advance_time_safely;
+use crate::ruby_api::daily_window::DailyWindow;
+use crate::ruby_api::series_options::SeriesOptions;
+use crate::ruby_api::traits::Recurrable;
+use chrono::{DateTime, TimeDelta};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+pub(crate) fn validate_every_in_seconds(every_in_seconds: Option<i64>) -> Result<(), String> {
+    if let Some(every_in_seconds) =
+        every_in_seconds.filter(|every_in_seconds| *every_in_seconds < 1)
+    {
+        return Err(format!(
+            "The step of {} seconds is out of range; it must be at least 1 second",
+            every_in_seconds
+        ));
+    }
+
+    return Ok(());
+}
+
+// Moves the cursor forward by as many whole steps as it takes to reach the instant, without
+// visiting the candidates in between.
+pub(crate) fn seek_by_steps(
+    datetime_cursor: &DateTime<Tz>,
+    instant: &DateTime<Tz>,
+    step_in_seconds: i64,
+) -> DateTime<Tz> {
+    let elapsed_seconds = instant.timestamp() - datetime_cursor.timestamp();
+    if elapsed_seconds <= 0 {
+        return datetime_cursor.clone();
+    }
+
+    let elapsed_steps = (elapsed_seconds + step_in_seconds - 1) / step_in_seconds;
+
+    return advance_time_safely(
+        datetime_cursor,
+        TimeDelta::seconds(elapsed_steps * step_in_seconds),
+    );
+}
+
+/// A series that repeats at a fixed step of elapsed time, from its first time of day onwards;
+/// the secondly, minutely and hourly series differ only in the step that they take by default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SubDaily<const DEFAULT_STEP_IN_SECONDS: i64> {
+    pub(crate) series_options: SeriesOptions,
+    // The number of seconds between consecutive candidates, if other than the default step.
+    #[serde(default)]
+    pub(crate) every_in_seconds: Option<i64>,
+    // The part of each day that the series is restricted to, if any.
+    #[serde(default)]
+    pub(crate) daily_window: Option<DailyWindow>,
+}
+
+impl<const DEFAULT_STEP_IN_SECONDS: i64> SubDaily<DEFAULT_STEP_IN_SECONDS> {
+    pub(crate) fn new(
+        series_options: SeriesOptions,
+        every_in_seconds: Option<i64>,
+        daily_window: Option<DailyWindow>,
+    ) -> Result<SubDaily<DEFAULT_STEP_IN_SECONDS>, String> {
+        validate_every_in_seconds(every_in_seconds)?;
+
+        return Ok(SubDaily {
+            series_options,
+            every_in_seconds,
+            daily_window,
+        });
+    }
+
+    pub(crate) fn step_in_seconds(&self) -> i64 {
+        return self
+            .every_in_seconds
+            .unwrap_or(DEFAULT_STEP_IN_SECONDS)
+            .max(1);
+    }
+
+    fn seek_into_daily_window(&self, datetime_cursor: DateTime<Tz>) -> DateTime<Tz> {
+        return match &self.daily_window {
+            Some(daily_window) => daily_window.seek_into(&datetime_cursor, self.step_in_seconds()),
+            None => datetime_cursor,
+        };
+    }
+}
+
+impl<const DEFAULT_STEP_IN_SECONDS: i64> Recurrable for SubDaily<DEFAULT_STEP_IN_SECONDS> {
+    fn get_series_options(&self) -> &SeriesOptions {
+        return &self.series_options;
+    }
+
+    fn occurs_at_each_time_of_day(&self) -> bool {
+        return false;
+    }
+
+    fn next_occurrence_candidate(&self, datetime_cursor: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        // We ensure that every time we advance the cursor, we are doing so to the next
+        // valid occurrence, unless the cursor could not be brought within the daily window.
+        return match &self.daily_window {
+            Some(daily_window) if !daily_window.contains(datetime_cursor) => None,
+            _ => Some(datetime_cursor.clone()),
+        };
+    }
+
+    fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
+        return self.seek_into_daily_window(advance_time_safely(
+            datetime_cursor,
+            TimeDelta::seconds(self.step_in_seconds()),
+        ));
+    }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        // Consecutive candidates are exactly one step apart, so each falls in its own period.
+        return occurrence_candidate
+            .timestamp()
+            .div_euclid(self.step_in_seconds());
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        return self.seek_into_daily_window(seek_by_steps(
+            datetime_cursor,
+            instant,
+            self.step_in_seconds(),
+        ));
+    }
+}
@@ -1,25 +1,81 @@
-use crate::ruby_api::clock::advance_time_safely;
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::serialization::one_or_many;
 use crate::ruby_api::series_options::SeriesOptions;
 use crate::ruby_api::traits::Recurrable;
-use chrono::{DateTime, Datelike, TimeDelta, Weekday};
+use chrono::{DateTime, Datelike, Days, NaiveDate, Weekday};
 use chrono_tz::Tz;
 use magnus::Symbol;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct Weekly {
-    pub(crate) weekday: Weekday,
+    // Schedules serialized before weekly series could repeat on several weekdays
+    // recorded a single `weekday`.
+    #[serde(alias = "weekday", deserialize_with = "one_or_many")]
+    pub(crate) weekdays: Vec<Weekday>,
     pub(crate) series_options: SeriesOptions,
+    // The first day of each week, which determines the weeks that an interval skips
+    // (cf. an RRULE's WKST).
+    #[serde(default = "default_week_start")]
+    pub(crate) week_start: Weekday,
+}
+
+fn default_week_start() -> Weekday {
+    return Weekday::Mon;
 }
 
 impl Weekly {
-    pub(crate) fn new(weekday_symbol: Symbol, series_options: SeriesOptions) -> Weekly {
-        let weekday = weekday_symbol.to_string().parse::<Weekday>().unwrap();
+    pub(crate) fn new(
+        weekday_symbols: Vec<Symbol>,
+        week_start_symbol: Symbol,
+        series_options: SeriesOptions,
+    ) -> Result<Weekly, String> {
+        let weekdays = weekday_symbols
+            .iter()
+            .map(|weekday_symbol| {
+                weekday_symbol
+                    .to_string()
+                    .parse::<Weekday>()
+                    .map_err(|_| format!("Unsupported weekday {}", weekday_symbol))
+            })
+            .collect::<Result<Vec<Weekday>, String>>()?;
+        let week_start = week_start_symbol
+            .to_string()
+            .parse::<Weekday>()
+            .map_err(|_| format!("Unsupported weekday {}", week_start_symbol))?;
+
+        return Ok(Weekly::from_weekdays(weekdays, week_start, series_options));
+    }
+
+    pub(crate) fn from_weekdays(
+        weekdays: Vec<Weekday>,
+        week_start: Weekday,
+        series_options: SeriesOptions,
+    ) -> Weekly {
+        let mut weekdays: Vec<Weekday> = weekdays;
+        weekdays.sort_by_key(|weekday| weekday.num_days_from_monday());
+        weekdays.dedup();
 
         return Weekly {
-            weekday,
+            weekdays,
             series_options,
+            week_start,
         };
     }
+
+    fn occurs_on(&self, date: &NaiveDate) -> bool {
+        return self.weekdays.contains(&date.weekday());
+    }
+
+    // The number of days from the given date until the next date that falls on one of
+    // the series' weekdays, optionally including the date itself.
+    fn days_until_next_weekday(&self, date: &NaiveDate, inclusive: bool) -> u64 {
+        let first_examined_day = if inclusive { 0 } else { 1 };
+
+        return (first_examined_day..=7)
+            .find(|days| self.occurs_on(&(*date + Days::new(*days))))
+            .unwrap_or(7);
+    }
 }
 
 impl Recurrable for Weekly {
@@ -36,23 +92,40 @@ impl Recurrable for Weekly {
     }
 
     fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz> {
-        // If the current candidate matches the criteria, we can advance by 1-week moving forward.
-        return if self.occurrence_candidate_matches_criteria(datetime_cursor) {
-            advance_time_safely(
-                datetime_cursor,
-                TimeDelta::days(7),
-                self.naive_starts_at_time(),
-            )
-        } else {
-            advance_time_safely(
-                datetime_cursor,
-                TimeDelta::days(1),
-                self.naive_starts_at_time(),
-            )
-        };
+        // Move directly to the next day that falls on one of the requested weekdays.
+        let date = datetime_cursor.date_naive();
+
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            date + Days::new(self.days_until_next_weekday(&date, false)),
+            self.naive_starts_at_time(),
+        );
+    }
+
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64 {
+        let date = occurrence_candidate.date_naive();
+        let days_since_week_start = (7 + date.weekday().num_days_from_monday()
+            - self.week_start.num_days_from_monday())
+            % 7;
+
+        return (date.num_days_from_ce() as i64 - days_since_week_start as i64).div_euclid(7);
+    }
+
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz> {
+        let instant_date = instant.date_naive();
+
+        return datetime_on_date_safely(
+            &datetime_cursor.timezone(),
+            instant_date + Days::new(self.days_until_next_weekday(&instant_date, true)),
+            self.naive_starts_at_time(),
+        );
     }
 
     fn occurrence_candidate_matches_criteria(&self, occurrence_candidate: &DateTime<Tz>) -> bool {
-        return occurrence_candidate.weekday() == self.weekday;
+        return self.occurs_on(&occurrence_candidate.date_naive());
     }
 }
@@ -1,4 +1,4 @@
-use magnus::{value::Lazy, Module, RModule, Ruby};
+use magnus::{value::Lazy, Error, ExceptionClass, Module, RModule, Ruby};
 
 static REPRISE: Lazy<RModule> = Lazy::new(|ruby| ruby.define_module("Reprise").unwrap());
 
@@ -12,3 +12,67 @@ static CORE: Lazy<RModule> =
 pub(crate) fn reprise_core() -> RModule {
     Ruby::get().unwrap().get_inner(&CORE)
 }
+
+// N.B. `Reprise::Error` is reopened by the Ruby side of the gem; its superclass
+// must stay in sync with the definition in `lib/reprise/schedule.rb`.
+static ERROR: Lazy<ExceptionClass> = Lazy::new(|ruby| {
+    ruby.get_inner(&REPRISE)
+        .define_error("Error", ruby.exception_standard_error())
+        .unwrap()
+});
+
+static UNSUPPORTED_RRULE_ERROR: Lazy<ExceptionClass> = Lazy::new(|ruby| {
+    ruby.get_inner(&REPRISE)
+        .define_error("UnsupportedRRuleError", ruby.get_inner(&ERROR))
+        .unwrap()
+});
+
+pub(crate) fn unsupported_rrule_error() -> ExceptionClass {
+    Ruby::get().unwrap().get_inner(&UNSUPPORTED_RRULE_ERROR)
+}
+
+static UNSUPPORTED_ICAL_ERROR: Lazy<ExceptionClass> = Lazy::new(|ruby| {
+    ruby.get_inner(&REPRISE)
+        .define_error("UnsupportedICalError", ruby.get_inner(&ERROR))
+        .unwrap()
+});
+
+pub(crate) fn unsupported_ical_error() -> ExceptionClass {
+    Ruby::get().unwrap().get_inner(&UNSUPPORTED_ICAL_ERROR)
+}
+
+static DESERIALIZATION_ERROR: Lazy<ExceptionClass> = Lazy::new(|ruby| {
+    ruby.get_inner(&REPRISE)
+        .define_error("DeserializationError", ruby.get_inner(&ERROR))
+        .unwrap()
+});
+
+pub(crate) fn deserialization_error() -> ExceptionClass {
+    Ruby::get().unwrap().get_inner(&DESERIALIZATION_ERROR)
+}
+
+static UNBOUNDED_SCHEDULE_ERROR: Lazy<ExceptionClass> = Lazy::new(|ruby| {
+    ruby.get_inner(&REPRISE)
+        .define_error("UnboundedScheduleError", ruby.get_inner(&ERROR))
+        .unwrap()
+});
+
+pub(crate) fn unbounded_schedule_error() -> ExceptionClass {
+    Ruby::get().unwrap().get_inner(&UNBOUNDED_SCHEDULE_ERROR)
+}
+
+pub(crate) fn argument_error() -> ExceptionClass {
+    Ruby::get().unwrap().exception_arg_error()
+}
+
+pub fn init() -> Result<(), Error> {
+    // Error classes are defined eagerly, so that callers can rescue them
+    // before any of them has ever been raised.
+    let ruby = Ruby::get().unwrap();
+    Lazy::force(&UNSUPPORTED_RRULE_ERROR, &ruby);
+    Lazy::force(&UNSUPPORTED_ICAL_ERROR, &ruby);
+    Lazy::force(&DESERIALIZATION_ERROR, &ruby);
+    Lazy::force(&UNBOUNDED_SCHEDULE_ERROR, &ruby);
+
+    Ok(())
+}
@@ -1,39 +1,216 @@
+use crate::ruby_api::business_calendar::BusinessCalendar;
+use crate::ruby_api::daily_window::DailyWindow;
+use crate::ruby_api::derived_series::{DerivedSeries, OccurrenceOffset};
 use crate::ruby_api::exclusion::Exclusion;
+use crate::ruby_api::holiday_calendar::{HolidayCalendar, HolidayExclusions};
+use crate::ruby_api::ical::export;
+use crate::ruby_api::ical::reader;
+use crate::ruby_api::ical::rrule::RRule;
 use crate::ruby_api::interval::Interval;
 use crate::ruby_api::occurrence::Occurrence;
+use crate::ruby_api::occurrence_iterator::{DerivedSeriesCursor, ScheduleOccurrences};
 use crate::ruby_api::recurring_series::annually_by_day::AnnuallyByDay;
+use crate::ruby_api::recurring_series::annually_by_iso_week::AnnuallyByIsoWeek;
+use crate::ruby_api::recurring_series::annually_by_month_day::AnnuallyByMonthDay;
+use crate::ruby_api::recurring_series::annually_by_nth_weekday::AnnuallyByNthWeekday;
+use crate::ruby_api::recurring_series::business_daily::BusinessDaily;
 use crate::ruby_api::recurring_series::daily::Daily;
 use crate::ruby_api::recurring_series::hourly::Hourly;
 use crate::ruby_api::recurring_series::minutely::Minutely;
-use crate::ruby_api::recurring_series::monthly_by_day::MonthlyByDay;
+use crate::ruby_api::recurring_series::monthly_by_day::{MissingDayPolicy, MonthlyByDay};
+use crate::ruby_api::recurring_series::monthly_by_nth_business_day::MonthlyByNthBusinessDay;
 use crate::ruby_api::recurring_series::monthly_by_nth_weekday::MonthlyByNthWeekday;
+use crate::ruby_api::recurring_series::monthly_by_weekday::MonthlyByWeekday;
+use crate::ruby_api::recurring_series::secondly::Secondly;
 use crate::ruby_api::recurring_series::weekly::Weekly;
 use crate::ruby_api::ruby_modules;
+use crate::ruby_api::serialization::{binary, json};
 use crate::ruby_api::series_options::SeriesOptions;
 use crate::ruby_api::sorted_exclusions::SortedExclusions;
+use crate::ruby_api::time_of_day::TimeOfDay;
 use crate::ruby_api::traits::{HasOverlapAwareness, Recurrable, RecurringSeries};
 use chrono::{DateTime, TimeDelta};
 use chrono_tz::Tz;
+use magnus::block::Yield;
 use magnus::prelude::*;
+use magnus::typed_data::Obj;
 use magnus::{class, function, method};
-use magnus::{scan_args, Error, Module, RHash, Symbol};
+use magnus::{scan_args, Error, Module, RHash, RString, Ruby, Symbol};
 use parking_lot::RwLock;
-use rayon::prelude::ParallelSliceMut;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 pub(crate) type UnixTimestamp = i64;
 type Second = i64;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct Schedule {
     pub(crate) starts_at_unix_timestamp: UnixTimestamp,
-    pub(crate) local_starts_at_datetime: DateTime<Tz>,
-    pub(crate) ends_at_unix_timestamp: UnixTimestamp,
-    pub(crate) local_ends_at_datetime: DateTime<Tz>,
+    // Open-ended schedules have no end; their occurrences can only be
+    // expanded within an explicit window, or lazily.
+    pub(crate) ends_at_unix_timestamp: Option<UnixTimestamp>,
     pub(crate) time_zone: Tz,
+    #[serde(skip)]
     pub(crate) occurrences: Vec<Occurrence>,
     pub(crate) sorted_exclusions: SortedExclusions,
     pub(crate) recurring_series: Vec<RecurringSeries>,
+    // The calendar that business day series are added with; each series keeps a copy of it.
+    #[serde(default)]
+    pub(crate) business_calendar: BusinessCalendar,
+    // Occurrences that fall on the holidays of any of these calendars are excluded, alongside
+    // those that overlap with the sorted exclusions.
+    #[serde(default)]
+    pub(crate) holiday_calendars: Vec<HolidayCalendar>,
+    // Series whose occurrences are moved from those of the recurring series that they refer to.
+    #[serde(default)]
+    pub(crate) derived_series: Vec<DerivedSeries>,
+    // Occurrences that were added one at a time, rather than as part of a series; they are
+    // kept in ascending order of their start times.
+    #[serde(default)]
+    pub(crate) one_off_occurrences: Vec<Occurrence>,
+}
+
+impl Schedule {
+    pub(crate) fn occurrence_iterator(
+        &self,
+        starts_at: Option<DateTime<Tz>>,
+        ends_at: Option<DateTime<Tz>>,
+    ) -> ScheduleOccurrences {
+        let ends_at = match (ends_at, self.local_ends_at_datetime()) {
+            (Some(ends_at), Some(schedule_ends_at)) => Some(ends_at.min(schedule_ends_at)),
+            (ends_at, schedule_ends_at) => ends_at.or(schedule_ends_at),
+        };
+
+        // Every series is expanded from its own start (or the schedule's), and then sought
+        // to the start of the requested window, so that a window only ever contains the
+        // occurrences that the full expansion of the schedule would.
+        return ScheduleOccurrences::new(
+            self.recurring_series.clone(),
+            self.derived_series_cursors(ends_at),
+            self.one_off_occurrences.clone(),
+            self.sorted_exclusions.clone(),
+            self.holiday_exclusions(),
+            self.local_starts_at_datetime(),
+            ends_at,
+            starts_at,
+        );
+    }
+
+    fn derived_series_cursors(&self, ends_at: Option<DateTime<Tz>>) -> Vec<DerivedSeriesCursor> {
+        return self
+            .derived_series
+            .iter()
+            .flat_map(|derived_series| {
+                DerivedSeriesCursor::for_base_series(
+                    derived_series,
+                    &self.recurring_series,
+                    self.local_starts_at_datetime(),
+                    self.local_ends_at_datetime(),
+                    ends_at,
+                )
+            })
+            .collect();
+    }
+
+    /// The occurrences of a derived series up until the given time, including those that the
+    /// schedule's exclusions (rather than those of its base series) would exclude.
+    pub(crate) fn derived_occurrences(
+        &self,
+        derived_series: &DerivedSeries,
+        ends_at: DateTime<Tz>,
+    ) -> Vec<Occurrence> {
+        let mut holiday_exclusions = self.holiday_exclusions();
+        let mut occurrences = Vec::new();
+
+        for mut derived_series_cursor in DerivedSeriesCursor::for_base_series(
+            derived_series,
+            &self.recurring_series,
+            self.local_starts_at_datetime(),
+            self.local_ends_at_datetime(),
+            Some(ends_at),
+        ) {
+            while let Some(occurrence) = derived_series_cursor.next_occurrence(
+                &self.recurring_series,
+                &self.sorted_exclusions,
+                &mut holiday_exclusions,
+            ) {
+                occurrences.push(occurrence);
+            }
+        }
+
+        occurrences.sort_by_key(|o| o.starts_at_unix_timestamp);
+
+        return occurrences;
+    }
+
+    pub(crate) fn holiday_exclusions(&self) -> HolidayExclusions {
+        return HolidayExclusions::new(self.holiday_calendars.clone(), self.time_zone);
+    }
+
+    pub(crate) fn next_occurrence(&self, after: DateTime<Tz>) -> Option<Occurrence> {
+        return self
+            .occurrence_iterator(Some(after + TimeDelta::seconds(1)), None)
+            .next();
+    }
+
+    pub(crate) fn previous_occurrence(&self, before: DateTime<Tz>) -> Option<Occurrence> {
+        let earliest_starts_at = self
+            .recurring_series
+            .iter()
+            .filter_map(|series| series.get_series_options().local_starts_at_datetime())
+            .fold(self.local_starts_at_datetime(), |earliest, starts_at| {
+                earliest.min(starts_at)
+            });
+
+        // Series can only be expanded forwards; look back over a window that doubles
+        // in size until it contains an occurrence, or reaches the start of every series.
+        let mut lookback = TimeDelta::days(1);
+
+        loop {
+            let window_starts_at = before - lookback;
+            let previous_occurrence = self
+                .occurrence_iterator(Some(window_starts_at), None)
+                .take_while(|o| o.starts_at_unix_timestamp < before.timestamp())
+                .last();
+
+            if previous_occurrence.is_some() || window_starts_at <= earliest_starts_at {
+                return previous_occurrence;
+            }
+
+            lookback = lookback * 2;
+        }
+    }
+
+    /// Adds an occurrence after any others that start at the same time, keeping the one-off
+    /// occurrences in ascending order of their start times.
+    pub(crate) fn add_one_off_occurrence(&mut self, occurrence: Occurrence) {
+        let index = self
+            .one_off_occurrences
+            .partition_point(|o| o.starts_at_unix_timestamp <= occurrence.starts_at_unix_timestamp);
+        self.one_off_occurrences.insert(index, occurrence);
+    }
+
+    /// Whether every occurrence of the schedule can be expanded; i.e. the schedule has an
+    /// end, or each of its series is bounded by its own count or end bookend.
+    pub(crate) fn is_bounded(&self) -> bool {
+        return self.ends_at_unix_timestamp.is_some()
+            || self
+                .recurring_series
+                .iter()
+                .all(|series| series.is_bounded());
+    }
+
+    pub(crate) fn local_starts_at_datetime(&self) -> DateTime<Tz> {
+        let starts_at_utc = DateTime::from_timestamp(self.starts_at_unix_timestamp, 0).unwrap();
+        return starts_at_utc.with_timezone(&self.time_zone);
+    }
+
+    pub(crate) fn local_ends_at_datetime(&self) -> Option<DateTime<Tz>> {
+        return self.ends_at_unix_timestamp.map(|ends_at_unix_timestamp| {
+            let ends_at_utc = DateTime::from_timestamp(ends_at_unix_timestamp, 0).unwrap();
+            ends_at_utc.with_timezone(&self.time_zone)
+        });
+    }
 }
 
 #[derive(Debug)]
@@ -43,41 +220,122 @@ struct MutSchedule(Arc<RwLock<Schedule>>);
 impl MutSchedule {
     pub(crate) fn new(
         starts_at_unix_timestamp: UnixTimestamp,
-        ends_at_unix_timestamp: UnixTimestamp,
+        ends_at_unix_timestamp: Option<UnixTimestamp>,
         time_zone: String,
     ) -> MutSchedule {
         let parsed_time_zone: Tz = time_zone.parse().expect("Cannot parse time zone");
-        let starts_at_utc = DateTime::from_timestamp(starts_at_unix_timestamp, 0).unwrap();
-        let local_starts_at_datetime = starts_at_utc.with_timezone(&parsed_time_zone);
-        let ends_at_utc = DateTime::from_timestamp(ends_at_unix_timestamp, 0).unwrap();
-        let local_ends_at_datetime = ends_at_utc.with_timezone(&parsed_time_zone);
 
         Self(Arc::new(RwLock::new(Schedule {
             starts_at_unix_timestamp,
-            local_starts_at_datetime,
             ends_at_unix_timestamp,
-            local_ends_at_datetime,
             time_zone: parsed_time_zone,
             occurrences: Vec::new(),
             sorted_exclusions: SortedExclusions::new(),
             recurring_series: Vec::new(),
+            business_calendar: BusinessCalendar::default(),
+            holiday_calendars: Vec::new(),
+            derived_series: Vec::new(),
+            one_off_occurrences: Vec::new(),
         })))
     }
 
+    fn from_schedule(schedule: Schedule) -> MutSchedule {
+        return Self(Arc::new(RwLock::new(schedule)));
+    }
+
+    pub(crate) fn from_json(json: String) -> Result<MutSchedule, Error> {
+        return json::from_json(&json)
+            .map(MutSchedule::from_schedule)
+            .map_err(|e| Error::new(ruby_modules::deserialization_error(), e.to_string()));
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        return json::to_json(&self.0.read());
+    }
+
+    pub(crate) fn load(snapshot: RString) -> Result<MutSchedule, Error> {
+        // SAFETY: the slice is not held across any call back into Ruby.
+        let bytes = unsafe { snapshot.as_slice() };
+
+        return binary::load(bytes)
+            .map(MutSchedule::from_schedule)
+            .map_err(|e| Error::new(ruby_modules::deserialization_error(), e.to_string()));
+    }
+
+    pub(crate) fn dump(&self) -> RString {
+        return RString::from_slice(&binary::dump(&self.0.read()));
+    }
+
+    // Marshal support: `Marshal.dump` calls `_dump(depth)`, and `Marshal.load` calls
+    // `Schedule._load` with the string that `_dump` returned.
+    pub(crate) fn marshal_dump(&self, _depth: i64) -> RString {
+        return self.dump();
+    }
+
+    pub(crate) fn starts_at_unix_timestamp(&self) -> UnixTimestamp {
+        return self.0.read().starts_at_unix_timestamp;
+    }
+
+    pub(crate) fn ends_at_unix_timestamp(&self) -> Option<UnixTimestamp> {
+        return self.0.read().ends_at_unix_timestamp;
+    }
+
     pub(crate) fn time_zone(&self) -> Tz {
         return self.0.read().time_zone;
     }
 
+    pub(crate) fn time_zone_identifier(&self) -> String {
+        return self.time_zone().name().to_string();
+    }
+
+    fn series_options(&self, kw: RHash) -> Result<SeriesOptions, Error> {
+        let schedule = self.0.read();
+
+        return SeriesOptions::new(schedule.time_zone, &schedule.business_calendar, kw);
+    }
+
     fn longest_occurrence_duration_in_seconds(&self) -> Option<i64> {
-        return self
-            .0
-            .read()
+        let schedule = self.0.read();
+
+        return schedule
             .recurring_series
             .iter()
             .map(|s| s.get_occurrence_duration_in_seconds())
+            .chain(
+                schedule
+                    .derived_series
+                    .iter()
+                    .map(|d| d.duration_in_seconds),
+            )
+            .chain(
+                schedule
+                    .one_off_occurrences
+                    .iter()
+                    .map(|o| o.ends_at_unix_timestamp - o.starts_at_unix_timestamp),
+            )
             .max();
     }
 
+    pub(crate) fn set_business_calendar(
+        &self,
+        weekend_symbols: Vec<Symbol>,
+        holiday_dates: Vec<String>,
+    ) -> Result<(), Error> {
+        let business_calendar = BusinessCalendar::new(weekend_symbols, holiday_dates)
+            .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
+        self.0.write().business_calendar = business_calendar;
+
+        Ok(())
+    }
+
+    pub(crate) fn add_holiday_calendar(&self, holidays_json: String) -> Result<(), Error> {
+        let holiday_calendar = HolidayCalendar::from_json(&holidays_json)
+            .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
+        self.0.write().holiday_calendars.push(holiday_calendar);
+
+        Ok(())
+    }
+
     pub(crate) fn add_exclusions(&self, exclusions: Vec<(i64, i64)>) {
         let mut converted_exclusions = exclusions
             .iter()
@@ -105,75 +363,331 @@ impl MutSchedule {
         });
     }
 
-    pub(crate) fn repeat_minutely(&self, kw: RHash) {
-        let series_options = SeriesOptions::new(self.time_zone().clone(), kw);
-        let minutely_series = Minutely::new(series_options);
+    pub(crate) fn add_occurrence(
+        &self,
+        starts_at_unix_timestamp: i64,
+        duration_in_seconds: i64,
+        label: Option<String>,
+    ) {
+        self.0.write().add_one_off_occurrence(Occurrence::new(
+            starts_at_unix_timestamp,
+            starts_at_unix_timestamp + duration_in_seconds,
+            label,
+        ));
+    }
+
+    pub(crate) fn repeat_secondly(
+        &self,
+        every_in_seconds: Option<i64>,
+        window_starts_at: Option<RHash>,
+        window_ends_at: Option<RHash>,
+        window_weekday_symbols: Vec<Symbol>,
+        kw: RHash,
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let daily_window =
+            daily_window_from(window_starts_at, window_ends_at, window_weekday_symbols)?;
+        let secondly_series = Secondly::new(series_options, every_in_seconds, daily_window)
+            .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
+        self.0
+            .write()
+            .recurring_series
+            .push(RecurringSeries::Secondly(secondly_series));
+
+        Ok(())
+    }
+
+    pub(crate) fn repeat_minutely(
+        &self,
+        every_in_seconds: Option<i64>,
+        window_starts_at: Option<RHash>,
+        window_ends_at: Option<RHash>,
+        window_weekday_symbols: Vec<Symbol>,
+        kw: RHash,
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let daily_window =
+            daily_window_from(window_starts_at, window_ends_at, window_weekday_symbols)?;
+        let minutely_series = Minutely::new(series_options, every_in_seconds, daily_window)
+            .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
         self.0
             .write()
             .recurring_series
             .push(RecurringSeries::Minutely(minutely_series));
+
+        Ok(())
     }
 
-    pub(crate) fn repeat_hourly(&self, kw: RHash) {
-        let series_options = SeriesOptions::new(self.time_zone().clone(), kw);
-        let hourly_series = Hourly::new(series_options);
+    pub(crate) fn repeat_hourly(
+        &self,
+        every_in_seconds: Option<i64>,
+        window_starts_at: Option<RHash>,
+        window_ends_at: Option<RHash>,
+        window_weekday_symbols: Vec<Symbol>,
+        kw: RHash,
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let daily_window =
+            daily_window_from(window_starts_at, window_ends_at, window_weekday_symbols)?;
+        let hourly_series = Hourly::new(series_options, every_in_seconds, daily_window)
+            .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
         self.0
             .write()
             .recurring_series
             .push(RecurringSeries::Hourly(hourly_series));
+
+        Ok(())
     }
 
-    pub(crate) fn repeat_daily(&self, kw: RHash) {
-        let series_options = SeriesOptions::new(self.time_zone().clone(), kw);
+    pub(crate) fn repeat_daily(&self, kw: RHash) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
         let daily_series = Daily::new(series_options);
         self.0
             .write()
             .recurring_series
             .push(RecurringSeries::Daily(daily_series));
+
+        Ok(())
+    }
+
+    pub(crate) fn repeat_business_daily(&self, kw: RHash) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let business_calendar = self.0.read().business_calendar.clone();
+        let business_daily_series = BusinessDaily::new(business_calendar, series_options);
+        self.0
+            .write()
+            .recurring_series
+            .push(RecurringSeries::BusinessDaily(business_daily_series));
+
+        Ok(())
     }
 
-    pub(crate) fn repeat_weekly(&self, weekday_symbol: Symbol, kw: RHash) {
-        let series_options = SeriesOptions::new(self.time_zone().clone(), kw);
-        let weekly_series = Weekly::new(weekday_symbol, series_options);
+    pub(crate) fn repeat_weekly(
+        &self,
+        weekday_symbols: Vec<Symbol>,
+        week_start_symbol: Symbol,
+        kw: RHash,
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let weekly_series = Weekly::new(weekday_symbols, week_start_symbol, series_options)
+            .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
         self.0
             .write()
             .recurring_series
             .push(RecurringSeries::Weekly(weekly_series));
+
+        Ok(())
     }
 
-    pub(crate) fn repeat_monthly_by_day(&self, day_number: u32, kw: RHash) {
-        let series_options = SeriesOptions::new(self.time_zone().clone(), kw);
-        let monthly_series = MonthlyByDay::new(day_number, series_options);
+    pub(crate) fn repeat_monthly_by_day(
+        &self,
+        day_numbers: Vec<i32>,
+        missing_day_symbol: Symbol,
+        kw: RHash,
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let monthly_series = MonthlyByDay::new(
+            day_numbers,
+            MissingDayPolicy::from_symbol(missing_day_symbol)?,
+            series_options,
+        )
+        .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
         self.0
             .write()
             .recurring_series
             .push(RecurringSeries::MonthlyByDay(monthly_series));
+
+        Ok(())
     }
 
     pub(crate) fn repeat_monthly_by_nth_weekday(
         &self,
-        weekday_symbol: Symbol,
-        nth_day: i32,
+        nth_weekday_symbols: Vec<(Symbol, i32)>,
         kw: RHash,
-    ) {
-        let series_options = SeriesOptions::new(self.time_zone().clone(), kw);
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
         let monthly_by_nth_weekday_series =
-            MonthlyByNthWeekday::new(weekday_symbol, nth_day, series_options);
+            MonthlyByNthWeekday::new(nth_weekday_symbols, series_options)
+                .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
         self.0
             .write()
             .recurring_series
             .push(RecurringSeries::MonthlyByNthWeekday(
                 monthly_by_nth_weekday_series,
             ));
+
+        Ok(())
     }
 
-    pub(crate) fn repeat_annually_by_day(&self, day_number: u32, kw: RHash) {
-        let series_options = SeriesOptions::new(self.time_zone().clone(), kw);
-        let annual_series = AnnuallyByDay::new(day_number, series_options);
+    pub(crate) fn repeat_monthly_by_weekday(
+        &self,
+        weekday_symbols: Vec<Symbol>,
+        kw: RHash,
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let monthly_by_weekday_series = MonthlyByWeekday::new(weekday_symbols, series_options)
+            .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
+        self.0
+            .write()
+            .recurring_series
+            .push(RecurringSeries::MonthlyByWeekday(monthly_by_weekday_series));
+
+        Ok(())
+    }
+
+    pub(crate) fn repeat_monthly_by_nth_business_day(
+        &self,
+        nth_business_day: i32,
+        kw: RHash,
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let business_calendar = self.0.read().business_calendar.clone();
+        let monthly_series =
+            MonthlyByNthBusinessDay::new(nth_business_day, business_calendar, series_options)
+                .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
+        self.0
+            .write()
+            .recurring_series
+            .push(RecurringSeries::MonthlyByNthBusinessDay(monthly_series));
+
+        Ok(())
+    }
+
+    pub(crate) fn repeat_annually_by_day(&self, day_number: u32, kw: RHash) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let annual_series = AnnuallyByDay::new(day_number, series_options)
+            .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
         self.0
             .write()
             .recurring_series
             .push(RecurringSeries::AnnuallyByDay(annual_series));
+
+        Ok(())
+    }
+
+    pub(crate) fn repeat_annually_by_month_day(
+        &self,
+        month: u32,
+        day: u32,
+        missing_day_symbol: Symbol,
+        kw: RHash,
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let annual_series = AnnuallyByMonthDay::new(
+            month,
+            day,
+            MissingDayPolicy::from_symbol(missing_day_symbol)?,
+            series_options,
+        )
+        .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
+        self.0
+            .write()
+            .recurring_series
+            .push(RecurringSeries::AnnuallyByMonthDay(annual_series));
+
+        Ok(())
+    }
+
+    pub(crate) fn repeat_annually_by_nth_weekday(
+        &self,
+        month: u32,
+        weekday_symbol: Symbol,
+        nth_weekday: i32,
+        kw: RHash,
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let annual_series =
+            AnnuallyByNthWeekday::new(month, weekday_symbol, nth_weekday, series_options)
+                .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
+        self.0
+            .write()
+            .recurring_series
+            .push(RecurringSeries::AnnuallyByNthWeekday(annual_series));
+
+        Ok(())
+    }
+
+    pub(crate) fn repeat_annually_by_iso_week(
+        &self,
+        week_numbers: Vec<i32>,
+        weekday_symbols: Vec<Symbol>,
+        kw: RHash,
+    ) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let annual_series = AnnuallyByIsoWeek::new(week_numbers, weekday_symbols, series_options)
+            .map_err(|e| Error::new(ruby_modules::argument_error(), e))?;
+        self.0
+            .write()
+            .recurring_series
+            .push(RecurringSeries::AnnuallyByIsoWeek(annual_series));
+
+        Ok(())
+    }
+
+    pub(crate) fn repeat_relative_to(
+        &self,
+        base_label: String,
+        offset_parts: (i32, i64, i64),
+        duration_in_seconds: i64,
+        label: Option<String>,
+    ) -> Result<(), Error> {
+        let mut schedule = self.0.write();
+
+        if !schedule
+            .recurring_series
+            .iter()
+            .any(|series| series.get_series_options().label.as_ref() == Some(&base_label))
+        {
+            return Err(Error::new(
+                ruby_modules::argument_error(),
+                format!("No series is labelled {:?}", base_label),
+            ));
+        }
+
+        let (months, days, seconds) = offset_parts;
+        schedule.derived_series.push(DerivedSeries {
+            base_label,
+            offset: OccurrenceOffset {
+                months,
+                days,
+                seconds,
+            },
+            duration_in_seconds,
+            label,
+        });
+
+        Ok(())
+    }
+
+    pub(crate) fn repeat_rrule(&self, rrule: String, kw: RHash) -> Result<(), Error> {
+        let series_options = self.series_options(kw)?;
+        let dtstart = series_options
+            .local_starts_at_datetime()
+            .unwrap_or(self.0.read().local_starts_at_datetime());
+
+        let recurring_series = RRule::parse(&rrule, &self.time_zone())
+            .and_then(|rrule| rrule.to_recurring_series(series_options, dtstart))
+            .map_err(|e| Error::new(ruby_modules::unsupported_rrule_error(), e.to_string()))?;
+
+        self.0.write().recurring_series.extend(recurring_series);
+
+        Ok(())
+    }
+
+    pub(crate) fn add_ical(&self, ics: String) -> Result<(), Error> {
+        let imported_calendar = reader::read_vcalendar(&ics, &self.time_zone())
+            .map_err(|e| Error::new(ruby_modules::unsupported_ical_error(), e.to_string()))?;
+
+        let mut self_reference = self.0.write();
+        self_reference
+            .recurring_series
+            .extend(imported_calendar.recurring_series);
+
+        for occurrence in imported_calendar.occurrences {
+            self_reference.add_one_off_occurrence(occurrence);
+        }
+
+        Ok(())
     }
 
     pub fn occurrences_contained_within_interval(
@@ -222,8 +736,20 @@ impl MutSchedule {
             .collect();
     }
 
-    pub fn occurrences(&self) -> Vec<Occurrence> {
-        return self.generate_occurrences(None, None);
+    pub fn occurrences(&self) -> Result<Vec<Occurrence>, Error> {
+        if !self.0.read().is_bounded() {
+            return Err(Error::new(
+                ruby_modules::unbounded_schedule_error(),
+                "Cannot expand all of the occurrences of an open-ended schedule; \
+                query a window of time or enumerate its occurrences lazily instead",
+            ));
+        }
+
+        return Ok(self.generate_occurrences(None, None));
+    }
+
+    pub(crate) fn to_ical(&self) -> String {
+        return export::to_vcalendar(&self.0.read());
     }
 
     fn generate_occurrences(
@@ -231,30 +757,97 @@ impl MutSchedule {
         starts_at: Option<DateTime<Tz>>,
         ends_at: Option<DateTime<Tz>>,
     ) -> Vec<Occurrence> {
-        let self_reference = self.0.read();
-        let starts_at = starts_at.unwrap_or(self_reference.local_starts_at_datetime);
-        let ends_at = ends_at.unwrap_or(self_reference.local_ends_at_datetime);
+        return self
+            .0
+            .read()
+            .occurrence_iterator(starts_at, ends_at)
+            .collect();
+    }
 
-        let mut occurrences = self_reference
-            .recurring_series
-            .iter()
-            .flat_map(|series| series.generate_occurrences(starts_at, ends_at))
-            .filter(|o| !self_reference.sorted_exclusions.is_occurrence_excluded(o))
-            .collect::<Vec<Occurrence>>();
+    pub(crate) fn next_occurrence(
+        &self,
+        after_unix_timestamp: UnixTimestamp,
+    ) -> Option<Occurrence> {
+        let schedule = self.0.read();
+        let after = DateTime::from_timestamp(after_unix_timestamp, 0)
+            .unwrap()
+            .with_timezone(&schedule.time_zone);
 
-        occurrences.par_sort_unstable_by(|a, b| {
-            a.starts_at_unix_timestamp.cmp(&b.starts_at_unix_timestamp)
-        });
+        return schedule.next_occurrence(after);
+    }
 
-        return occurrences;
+    pub(crate) fn previous_occurrence(
+        &self,
+        before_unix_timestamp: UnixTimestamp,
+    ) -> Option<Occurrence> {
+        let schedule = self.0.read();
+        let before = DateTime::from_timestamp(before_unix_timestamp, 0)
+            .unwrap()
+            .with_timezone(&schedule.time_zone);
+
+        return schedule.previous_occurrence(before);
+    }
+
+    pub(crate) fn each_occurrence(
+        ruby: &Ruby,
+        rb_self: Obj<MutSchedule>,
+    ) -> Yield<ScheduleOccurrences> {
+        if !ruby.block_given() {
+            return Yield::Enumerator(rb_self.enumeratorize("each_occurrence", ()));
+        }
+
+        // The iterator owns copies of the schedule's series and exclusions, so that the
+        // schedule is not locked while the caller consumes occurrences.
+        return Yield::Iter(rb_self.0.read().occurrence_iterator(None, None));
     }
 }
 
+// A sub-daily series is only restricted to a daily window when at least one of its bounds,
+// or of its weekdays, is given.
+fn daily_window_from(
+    window_starts_at: Option<RHash>,
+    window_ends_at: Option<RHash>,
+    window_weekday_symbols: Vec<Symbol>,
+) -> Result<Option<DailyWindow>, Error> {
+    if window_starts_at.is_none() && window_ends_at.is_none() && window_weekday_symbols.is_empty() {
+        return Ok(None);
+    }
+
+    return DailyWindow::new(
+        window_starts_at.map(TimeOfDay::new_from_ruby_hash),
+        window_ends_at.map(TimeOfDay::new_from_ruby_hash),
+        window_weekday_symbols,
+    )
+    .map(Some)
+    .map_err(|e| Error::new(ruby_modules::argument_error(), e));
+}
+
 pub fn init() -> Result<(), Error> {
     let class = ruby_modules::reprise_core().define_class("Schedule", class::object())?;
 
     class.define_singleton_method("new", function!(MutSchedule::new, 3))?;
+    class.define_singleton_method("from_json", function!(MutSchedule::from_json, 1))?;
+    class.define_method(
+        "starts_at_unix_timestamp",
+        method!(MutSchedule::starts_at_unix_timestamp, 0),
+    )?;
+    class.define_method(
+        "ends_at_unix_timestamp",
+        method!(MutSchedule::ends_at_unix_timestamp, 0),
+    )?;
+    class.define_method("time_zone", method!(MutSchedule::time_zone_identifier, 0))?;
+    class.define_method("to_json", method!(MutSchedule::to_json, 0))?;
+    class.define_singleton_method("load", function!(MutSchedule::load, 1))?;
+    class.define_singleton_method("_load", function!(MutSchedule::load, 1))?;
+    class.define_method("dump", method!(MutSchedule::dump, 0))?;
+    class.define_method("_dump", method!(MutSchedule::marshal_dump, 1))?;
     class.define_method("occurrences", method!(MutSchedule::occurrences, 0))?;
+    class.define_method("each_occurrence", method!(MutSchedule::each_occurrence, 0))?;
+    class.define_method("next_occurrence", method!(MutSchedule::next_occurrence, 1))?;
+    class.define_method(
+        "previous_occurrence",
+        method!(MutSchedule::previous_occurrence, 1),
+    )?;
     class.define_method(
         "occurrences_contained_within_interval",
         method!(MutSchedule::occurrences_contained_within_interval, 2),
@@ -263,24 +856,65 @@ pub fn init() -> Result<(), Error> {
         "occurrences_overlapping_with_interval",
         method!(MutSchedule::occurrences_overlapping_with_interval, 2),
     )?;
+    class.define_method(
+        "set_business_calendar",
+        method!(MutSchedule::set_business_calendar, 2),
+    )?;
     class.define_method("add_exclusion", method!(MutSchedule::add_exclusion, 1))?;
+    class.define_method("add_occurrence", method!(MutSchedule::add_occurrence, 3))?;
+    class.define_method(
+        "add_holiday_calendar",
+        method!(MutSchedule::add_holiday_calendar, 1),
+    )?;
     class.define_method("add_exclusions", method!(MutSchedule::add_exclusions, 1))?;
-    class.define_method("repeat_minutely", method!(MutSchedule::repeat_minutely, 1))?;
-    class.define_method("repeat_hourly", method!(MutSchedule::repeat_hourly, 1))?;
+    class.define_method("repeat_secondly", method!(MutSchedule::repeat_secondly, 5))?;
+    class.define_method("repeat_minutely", method!(MutSchedule::repeat_minutely, 5))?;
+    class.define_method("repeat_hourly", method!(MutSchedule::repeat_hourly, 5))?;
     class.define_method("repeat_daily", method!(MutSchedule::repeat_daily, 1))?;
-    class.define_method("repeat_weekly", method!(MutSchedule::repeat_weekly, 2))?;
+    class.define_method(
+        "repeat_business_daily",
+        method!(MutSchedule::repeat_business_daily, 1),
+    )?;
+    class.define_method("repeat_weekly", method!(MutSchedule::repeat_weekly, 3))?;
     class.define_method(
         "repeat_monthly_by_day",
-        method!(MutSchedule::repeat_monthly_by_day, 2),
+        method!(MutSchedule::repeat_monthly_by_day, 3),
     )?;
     class.define_method(
         "repeat_monthly_by_nth_weekday",
-        method!(MutSchedule::repeat_monthly_by_nth_weekday, 3),
+        method!(MutSchedule::repeat_monthly_by_nth_weekday, 2),
+    )?;
+    class.define_method(
+        "repeat_monthly_by_weekday",
+        method!(MutSchedule::repeat_monthly_by_weekday, 2),
+    )?;
+    class.define_method(
+        "repeat_monthly_by_nth_business_day",
+        method!(MutSchedule::repeat_monthly_by_nth_business_day, 2),
     )?;
     class.define_method(
         "repeat_annually_by_day",
         method!(MutSchedule::repeat_annually_by_day, 2),
     )?;
+    class.define_method(
+        "repeat_annually_by_month_day",
+        method!(MutSchedule::repeat_annually_by_month_day, 4),
+    )?;
+    class.define_method(
+        "repeat_annually_by_nth_weekday",
+        method!(MutSchedule::repeat_annually_by_nth_weekday, 4),
+    )?;
+    class.define_method(
+        "repeat_annually_by_iso_week",
+        method!(MutSchedule::repeat_annually_by_iso_week, 3),
+    )?;
+    class.define_method(
+        "repeat_relative_to",
+        method!(MutSchedule::repeat_relative_to, 4),
+    )?;
+    class.define_method("repeat_rrule", method!(MutSchedule::repeat_rrule, 2))?;
+    class.define_method("add_ical", method!(MutSchedule::add_ical, 1))?;
+    class.define_method("to_ical", method!(MutSchedule::to_ical, 0))?;
 
     Ok(())
 }
@@ -0,0 +1,35 @@
+use crate::ruby_api::schedule::Schedule;
+use crate::ruby_api::serialization::{check_schema_version, DeserializationError, SCHEMA_VERSION};
+
+const MAGIC_BYTES: &[u8; 4] = b"RPRS";
+
+/// Serializes a schedule into a compact binary snapshot: the magic bytes `RPRS`, the schema
+/// version as a little-endian `u32`, then the schedule itself as MessagePack, with structs
+/// encoded as arrays rather than maps to avoid repeating field names.
+pub(crate) fn dump(schedule: &Schedule) -> Vec<u8> {
+    let mut bytes = MAGIC_BYTES.to_vec();
+    bytes.extend_from_slice(&SCHEMA_VERSION.to_le_bytes());
+    rmp_serde::encode::write(&mut bytes, schedule)
+        .expect("Schedule should serialize to MessagePack");
+
+    return bytes;
+}
+
+pub(crate) fn load(bytes: &[u8]) -> Result<Schedule, DeserializationError> {
+    let invalid_snapshot =
+        || DeserializationError::new("Invalid schedule snapshot: missing header");
+
+    let versioned_payload = bytes
+        .strip_prefix(MAGIC_BYTES)
+        .ok_or_else(invalid_snapshot)?;
+    let (version_bytes, payload) = versioned_payload
+        .split_first_chunk::<4>()
+        .ok_or_else(invalid_snapshot)?;
+    check_schema_version(u32::from_le_bytes(*version_bytes))?;
+
+    let mut schedule: Schedule = rmp_serde::from_slice(payload)
+        .map_err(|e| DeserializationError::new(format!("Invalid schedule snapshot: {}", e)))?;
+    schedule.sorted_exclusions.reorder_exclusions();
+
+    return Ok(schedule);
+}
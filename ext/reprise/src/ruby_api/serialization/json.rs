@@ -0,0 +1,39 @@
+use crate::ruby_api::schedule::Schedule;
+use crate::ruby_api::serialization::{check_schema_version, DeserializationError, SCHEMA_VERSION};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+struct VersionedSchedule<'a> {
+    version: u32,
+    schedule: &'a Schedule,
+}
+
+// The schedule is only deserialized once its schema version is known to be readable,
+// so that payloads from newer versions fail with a version error rather than a
+// (misleading) complaint about their shape.
+#[derive(Deserialize)]
+struct VersionedPayload {
+    version: u32,
+    schedule: serde_json::Value,
+}
+
+/// Serializes a schedule as `{"version": <SCHEMA_VERSION>, "schedule": {...}}`.
+pub(crate) fn to_json(schedule: &Schedule) -> String {
+    return serde_json::to_string(&VersionedSchedule {
+        version: SCHEMA_VERSION,
+        schedule,
+    })
+    .expect("Schedule should serialize to JSON");
+}
+
+pub(crate) fn from_json(json: &str) -> Result<Schedule, DeserializationError> {
+    let payload: VersionedPayload = serde_json::from_str(json)
+        .map_err(|e| DeserializationError::new(format!("Invalid schedule JSON: {}", e)))?;
+    check_schema_version(payload.version)?;
+
+    let mut schedule: Schedule = serde_json::from_value(payload.schedule)
+        .map_err(|e| DeserializationError::new(format!("Invalid schedule JSON: {}", e)))?;
+    schedule.sorted_exclusions.reorder_exclusions();
+
+    return Ok(schedule);
+}
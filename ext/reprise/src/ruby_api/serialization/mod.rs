@@ -0,0 +1,73 @@
+use serde::{de, Deserialize, Deserializer};
+use std::fmt;
+
+pub mod binary;
+pub mod json;
+
+/// The version of the serialized form of a `Schedule`, recorded alongside every payload.
+/// Fields added to any serialized struct must be given a `#[serde(default)]` so that
+/// payloads written by earlier versions of the gem remain readable; any change that
+/// older payloads cannot be read into must increment the version instead, and teach
+/// `check_schema_version` how to accept (or upgrade) the payloads that preceded it.
+pub(crate) const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub(crate) struct DeserializationError(String);
+
+impl DeserializationError {
+    pub(crate) fn new<T: Into<String>>(message: T) -> DeserializationError {
+        return DeserializationError(message.into());
+    }
+}
+
+impl fmt::Display for DeserializationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.0);
+    }
+}
+
+pub(crate) fn check_schema_version(version: u32) -> Result<(), DeserializationError> {
+    if version == 0 || version > SCHEMA_VERSION {
+        return Err(DeserializationError::new(format!(
+            "Schema version {} is not supported; this version of Reprise reads schema versions 1 through {}",
+            version, SCHEMA_VERSION
+        )));
+    }
+
+    return Ok(());
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+/// Reads a list that may have been serialized as a single value, so that a field can be
+/// widened into a list without breaking the payloads written before it was.
+pub(crate) fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    return match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => Ok(vec![value]),
+        OneOrMany::Many(values) => Ok(values),
+    };
+}
+
+/// Reads a list as `one_or_many` does, rejecting an empty list; for fields that the rest
+/// of the crate relies on having at least one value.
+pub(crate) fn one_or_more<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let values = one_or_many(deserializer)?;
+    if values.is_empty() {
+        return Err(de::Error::invalid_length(0, &"at least one value"));
+    }
+
+    return Ok(values);
+}
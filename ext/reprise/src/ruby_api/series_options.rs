@@ -1,13 +1,23 @@
+use crate::ruby_api::business_calendar::{BusinessCalendar, BusinessDayRoll, RollConvention};
+use crate::ruby_api::clock::datetime_on_date_safely;
+use crate::ruby_api::occurrence::Occurrence;
 use crate::ruby_api::schedule::UnixTimestamp;
+use crate::ruby_api::serialization::one_or_more;
 use crate::ruby_api::time_of_day::TimeOfDay;
-use chrono::DateTime;
+use chrono::{DateTime, TimeDelta};
 use chrono_tz::Tz;
-use magnus::{scan_args, RHash};
+use magnus::{scan_args, Error, RHash, Symbol};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct SeriesOptions {
     time_zone: Tz,
-    pub(crate) time_of_day: TimeOfDay,
+    // Series that repeat at most once per day occur at each of these times on every day
+    // that they occur on; they are kept in ascending order. Schedules serialized before a
+    // series could carry several times of day recorded a single `time_of_day`.
+    #[serde(alias = "time_of_day", deserialize_with = "one_or_more")]
+    pub(crate) times_of_day: Vec<TimeOfDay>,
     pub(crate) duration_in_seconds: i64,
     pub(crate) interval: i64,
     // Callers can specify their own start & end datetime bookends
@@ -17,10 +27,24 @@ pub(crate) struct SeriesOptions {
     pub(crate) ends_at_unix_timestamp: Option<UnixTimestamp>,
     pub(crate) count: Option<u64>,
     pub(crate) label: Option<String>,
+    // Positions (1-indexed, or counting back from the end if negative) of the candidates to
+    // keep within each of the series' periods; if empty, every candidate is kept.
+    #[serde(default)]
+    pub(crate) set_positions: Vec<i32>,
+    // If given, the series' occurrences that fall on days that are not worked are moved onto
+    // business days. The series' interval and set positions apply to the days that it would
+    // otherwise have occurred on, and its bookends and count to the moved occurrences.
+    #[serde(default)]
+    pub(crate) roll: Option<BusinessDayRoll>,
+    // The start times of occurrences that are removed from this series alone (e.g. the EXDATEs
+    // of an imported iCalendar event), rather than from the whole schedule. Like those that the
+    // schedule's exclusions remove, they still count towards the series' count.
+    #[serde(default)]
+    pub(crate) excluded_starts_at_unix_timestamps: BTreeSet<UnixTimestamp>,
 }
 
 type RubySeriesOptionsKwargs = (
-    RHash,
+    Vec<RHash>,
     i64,
     i64,
     Option<UnixTimestamp>,
@@ -29,24 +53,31 @@ type RubySeriesOptionsKwargs = (
     Option<String>,
 );
 
+type RubySeriesOptionsOptionalKwargs = (Option<Vec<i32>>, Option<Symbol>);
+
 impl SeriesOptions {
-    pub(crate) fn new(time_zone: Tz, kw: RHash) -> SeriesOptions {
-        let args: scan_args::KwArgs<RubySeriesOptionsKwargs, (), ()> = scan_args::get_kwargs(
-            kw,
-            &[
-                "time_of_day",
-                "duration_in_seconds",
-                "interval",
-                "starts_at_unix_timestamp",
-                "ends_at_unix_timestamp",
-                "count",
-                "label",
-            ],
-            &[],
-        )
-        .unwrap();
+    pub(crate) fn new(
+        time_zone: Tz,
+        business_calendar: &BusinessCalendar,
+        kw: RHash,
+    ) -> Result<SeriesOptions, Error> {
+        let args: scan_args::KwArgs<RubySeriesOptionsKwargs, RubySeriesOptionsOptionalKwargs, ()> =
+            scan_args::get_kwargs(
+                kw,
+                &[
+                    "times_of_day",
+                    "duration_in_seconds",
+                    "interval",
+                    "starts_at_unix_timestamp",
+                    "ends_at_unix_timestamp",
+                    "count",
+                    "label",
+                ],
+                &["set_positions", "roll"],
+            )
+            .unwrap();
         let (
-            time_of_day,
+            times_of_day,
             duration_in_seconds,
             interval,
             starts_at_unix_timestamp,
@@ -54,22 +85,81 @@ impl SeriesOptions {
             count,
             label,
         ): RubySeriesOptionsKwargs = args.required;
-        let time_of_day = TimeOfDay::new_from_ruby_hash(time_of_day);
+        let (set_positions, roll_convention_symbol): RubySeriesOptionsOptionalKwargs =
+            args.optional;
+        let times_of_day = times_of_day
+            .into_iter()
+            .map(TimeOfDay::new_from_ruby_hash)
+            .collect();
 
-        return SeriesOptions {
+        let roll = match roll_convention_symbol {
+            Some(roll_convention_symbol) => Some(BusinessDayRoll {
+                roll_convention: RollConvention::from_symbol(roll_convention_symbol)?,
+                business_calendar: business_calendar.clone(),
+            }),
+            None => None,
+        };
+
+        return Ok(SeriesOptions {
             time_zone,
-            time_of_day,
+            times_of_day: SeriesOptions::sorted_times_of_day(times_of_day),
             duration_in_seconds,
             interval,
             starts_at_unix_timestamp,
             ends_at_unix_timestamp,
             count,
             label,
+            set_positions: set_positions.unwrap_or_default(),
+            roll,
+            excluded_starts_at_unix_timestamps: BTreeSet::new(),
+        });
+    }
+
+    /// Options for a series anchored to (and first occurring at) `starts_at`, repeating
+    /// at the same local time of day with no count or end of its own.
+    pub(crate) fn new_starting_at(
+        time_zone: Tz,
+        starts_at: DateTime<Tz>,
+        duration_in_seconds: i64,
+        label: Option<String>,
+    ) -> SeriesOptions {
+        return SeriesOptions {
+            time_zone,
+            times_of_day: vec![TimeOfDay::new_from_local_time(
+                starts_at.with_timezone(&time_zone),
+            )],
+            duration_in_seconds,
+            interval: 1,
+            starts_at_unix_timestamp: Some(starts_at.timestamp()),
+            ends_at_unix_timestamp: None,
+            count: None,
+            label,
+            set_positions: Vec::new(),
+            roll: None,
+            excluded_starts_at_unix_timestamps: BTreeSet::new(),
         };
     }
 
+    pub(crate) fn sorted_times_of_day(times_of_day: Vec<TimeOfDay>) -> Vec<TimeOfDay> {
+        let mut times_of_day = times_of_day;
+        times_of_day.sort();
+        times_of_day.dedup();
+
+        return times_of_day;
+    }
+
+    /// The time zone in which the series is expanded (its local times of day and dates).
+    pub fn time_zone(&self) -> Tz {
+        return self.time_zone;
+    }
+
+    /// The earliest of the series' times of day.
     pub fn time_of_day(&self) -> &TimeOfDay {
-        return &self.time_of_day;
+        return &self.times_of_day[0];
+    }
+
+    pub fn times_of_day(&self) -> &[TimeOfDay] {
+        return &self.times_of_day;
     }
 
     pub fn duration_in_seconds(&self) -> i64 {
@@ -88,6 +178,72 @@ impl SeriesOptions {
         return self.label.clone();
     }
 
+    pub fn set_positions(&self) -> &[i32] {
+        return &self.set_positions;
+    }
+
+    /// Rejects set positions that no period of the series can reach, given the most days that
+    /// the series can occur on within one of its periods (e.g. a "month").
+    pub(crate) fn validate_set_positions(
+        &self,
+        max_days_in_period: usize,
+        period_name: &str,
+    ) -> Result<(), String> {
+        let max_candidates_in_period = (max_days_in_period * self.times_of_day.len()) as i32;
+
+        return match self
+            .set_positions
+            .iter()
+            .find(|set_position| set_position.abs() > max_candidates_in_period)
+        {
+            Some(set_position) => Err(format!(
+                "The set position {} is out of range; the series occurs at most {} times a {}",
+                set_position, max_candidates_in_period, period_name
+            )),
+            None => Ok(()),
+        };
+    }
+
+    /// Whether the occurrence is one that is removed from this series alone.
+    pub(crate) fn is_occurrence_excluded(&self, occurrence: &Occurrence) -> bool {
+        return self
+            .excluded_starts_at_unix_timestamps
+            .contains(&occurrence.starts_at_unix_timestamp);
+    }
+
+    /// The furthest that the series' occurrences can be moved from the days that they would
+    /// otherwise have occurred on.
+    pub(crate) fn max_roll(&self) -> TimeDelta {
+        return self
+            .roll
+            .as_ref()
+            .map_or(TimeDelta::zero(), |roll| roll.business_calendar.max_roll());
+    }
+
+    /// Moves the given candidate onto a business day, if the series is rolled; the candidate
+    /// keeps the time of day that it was placed at, rather than any that a gap in local time
+    /// moved it to.
+    pub(crate) fn rolled_datetime(&self, occurrence_candidate: &DateTime<Tz>) -> DateTime<Tz> {
+        let Some(roll) = &self.roll else {
+            return *occurrence_candidate;
+        };
+
+        let naive_time = self
+            .times_of_day
+            .iter()
+            .map(|time_of_day| time_of_day.to_naive_time())
+            .find(|naive_time| {
+                datetime_on_date_safely(
+                    &occurrence_candidate.timezone(),
+                    occurrence_candidate.date_naive(),
+                    *naive_time,
+                ) == *occurrence_candidate
+            })
+            .unwrap_or(occurrence_candidate.time());
+
+        return roll.roll(occurrence_candidate, naive_time);
+    }
+
     pub fn local_starts_at_datetime(&self) -> Option<DateTime<Tz>> {
         return match self.starts_at_unix_timestamp {
             None => None,
@@ -1,8 +1,9 @@
 use crate::ruby_api::exclusion::Exclusion;
 use crate::ruby_api::occurrence::Occurrence;
 use crate::ruby_api::traits::HasOverlapAwareness;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct SortedExclusions {
     pub(crate) exclusions: Vec<Exclusion>,
 }
@@ -28,7 +29,7 @@ impl SortedExclusions {
         return self.exclusions.iter().any(|e| e.overlaps_with(occurrence));
     }
 
-    fn reorder_exclusions(&mut self) {
+    pub(crate) fn reorder_exclusions(&mut self) {
         // Maintain an ascending end time sort order to simplify comparisons
         // against occurrences.
         self.exclusions.sort_by_key(|e| e.ends_at_unix_timestamp)
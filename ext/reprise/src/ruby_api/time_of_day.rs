@@ -1,9 +1,10 @@
-use chrono::{DateTime, Timelike};
+use chrono::{DateTime, NaiveTime, Timelike};
 use chrono_tz::Tz;
 use magnus::symbol::IntoSymbol;
 use magnus::RHash;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub(crate) struct TimeOfDay {
     pub(crate) hour: u32,
     pub(crate) minute: u32,
@@ -34,4 +35,8 @@ impl TimeOfDay {
             second: local_time.second(),
         };
     }
+
+    pub(crate) fn to_naive_time(&self) -> NaiveTime {
+        return NaiveTime::from_hms_opt(self.hour, self.minute, self.second).unwrap();
+    }
 }
@@ -1,17 +1,25 @@
-use crate::ruby_api::clock::set_datetime_cursor_safely;
 use crate::ruby_api::occurrence::Occurrence;
+use crate::ruby_api::occurrence_iterator::SeriesCursor;
 use crate::ruby_api::recurring_series::annually_by_day::AnnuallyByDay;
+use crate::ruby_api::recurring_series::annually_by_iso_week::AnnuallyByIsoWeek;
+use crate::ruby_api::recurring_series::annually_by_month_day::AnnuallyByMonthDay;
+use crate::ruby_api::recurring_series::annually_by_nth_weekday::AnnuallyByNthWeekday;
+use crate::ruby_api::recurring_series::business_daily::BusinessDaily;
 use crate::ruby_api::recurring_series::daily::Daily;
 use crate::ruby_api::recurring_series::hourly::Hourly;
 use crate::ruby_api::recurring_series::minutely::Minutely;
 use crate::ruby_api::recurring_series::monthly_by_day::MonthlyByDay;
+use crate::ruby_api::recurring_series::monthly_by_nth_business_day::MonthlyByNthBusinessDay;
 use crate::ruby_api::recurring_series::monthly_by_nth_weekday::MonthlyByNthWeekday;
+use crate::ruby_api::recurring_series::monthly_by_weekday::MonthlyByWeekday;
+use crate::ruby_api::recurring_series::secondly::Secondly;
 use crate::ruby_api::recurring_series::weekly::Weekly;
 use crate::ruby_api::series_options::SeriesOptions;
 use crate::ruby_api::time_of_day::TimeOfDay;
-use chrono::{DateTime, Duration, NaiveTime};
+use chrono::{DateTime, NaiveDate, NaiveTime};
 use chrono_tz::Tz;
 use enum_dispatch::enum_dispatch;
+use serde::{Deserialize, Serialize};
 
 pub(crate) trait HasOverlapAwareness {
     fn get_starts_at_unix_timestamp(&self) -> i64;
@@ -29,15 +37,23 @@ pub(crate) trait HasOverlapAwareness {
 }
 
 #[enum_dispatch]
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum RecurringSeries {
+    Secondly,
     Minutely,
     Hourly,
     Daily,
+    BusinessDaily,
     Weekly,
     MonthlyByDay,
     MonthlyByNthWeekday,
+    MonthlyByWeekday,
+    MonthlyByNthBusinessDay,
     AnnuallyByDay,
+    AnnuallyByMonthDay,
+    AnnuallyByNthWeekday,
+    AnnuallyByIsoWeek,
 }
 
 #[enum_dispatch(RecurringSeries)]
@@ -61,89 +77,66 @@ pub(crate) trait Recurrable: std::fmt::Debug {
         .unwrap();
     }
 
-    fn is_occurrence_count_reached(&self, occurrences: &Vec<Occurrence>) -> bool {
-        return if self.get_series_options().count.is_none() {
-            false
-        } else {
-            occurrences.len() as u64 >= self.get_series_options().count.unwrap()
-        }
+    // Whether the series occurs at each of its times of day on every day that it occurs on.
+    // Series that repeat within a day instead count their steps from their first time of day.
+    fn occurs_at_each_time_of_day(&self) -> bool {
+        return true;
+    }
+
+    fn is_occurrence_count_reached(&self, occurrence_count: u64) -> bool {
+        return match self.get_series_options().count {
+            None => false,
+            Some(count) => occurrence_count >= count,
+        };
     }
 
+    // N.B. Without an `ends_at`, only series that are bounded by their own
+    // count or end bookend can be expanded.
     fn generate_occurrences(
         &self,
         starts_at: DateTime<Tz>,
-        ends_at: DateTime<Tz>,
+        ends_at: Option<DateTime<Tz>>,
     ) -> Vec<Occurrence> {
+        let mut series_cursor = SeriesCursor::new(self, starts_at, ends_at);
         let mut occurrences = Vec::new();
 
-        // If the series itself has its own defined bookends, respect those; otherwise, fall back to the
-        // bookends passed by the parent schedule.
-        let starts_at = self
-            .get_series_options()
-            .local_starts_at_datetime()
-            .unwrap_or(starts_at);
-        let ends_at = self
-            .get_series_options()
-            .local_ends_at_datetime()
-            .unwrap_or(ends_at);
-
-        let mut datetime_cursor =
-            set_datetime_cursor_safely(starts_at, self.naive_starts_at_time());
-
-        while datetime_cursor < ends_at && !self.is_occurrence_count_reached(&occurrences) {
-            let occurrence_candidate_datetime_option =
-                self.next_occurrence_candidate(&datetime_cursor);
-
-            if let Some(occurrence_candidate_datetime) = occurrence_candidate_datetime_option {
-                if occurrence_candidate_datetime >= starts_at
-                    && occurrence_candidate_datetime <= ends_at
-                {
-                    occurrences.push(Occurrence {
-                        starts_at_unix_timestamp: occurrence_candidate_datetime.timestamp(),
-                        ends_at_unix_timestamp: (occurrence_candidate_datetime
-                            + Duration::seconds(self.get_occurrence_duration_in_seconds()))
-                        .timestamp(),
-                        label: self.get_series_options().label(),
-                    });
-                }
-            }
-
-            let considered_datetime_cursor = datetime_cursor;
-            datetime_cursor = self.advance_datetime_cursor(&datetime_cursor);
-
-            if datetime_cursor == considered_datetime_cursor {
-                // If we fail to advance, and the next cursor is identical to the
-                // last, there is an edge case in a series' advance cursor
-                // logic that would, if left to its own devices, result in an
-                // infinite loop that would never terminate. We explicitly panic
-                // here as there is no other way to recover, callers need to be
-                // informed via an exception.
-                panic!(
-                    "{}",
-                    format!(
-                        "Infinite loop prevented during schedule expansion.
-                        This is a bug in Reprise, please share with the maintainers. \
-                        Affected series: {:?}",
-                        dbg!(self)
-                    )
-                );
-            }
+        while let Some(occurrence) = series_cursor.next_occurrence(self) {
+            occurrences.push(occurrence);
         }
 
-        // Only collect every Nth occurrence if an interval has been requested.
-        if self.get_series_options().interval > 1 {
-            occurrences
-                .into_iter()
-                .step_by(self.get_series_options().interval as usize)
-                .collect()
-        } else {
-            occurrences
-        }
+        return occurrences;
+    }
+
+    fn is_bounded(&self) -> bool {
+        return self.get_series_options().ends_at_unix_timestamp.is_some()
+            || self.get_series_options().count.is_some();
     }
 
     fn next_occurrence_candidate(&self, datetime_cursor: &DateTime<Tz>) -> Option<DateTime<Tz>>;
     fn advance_datetime_cursor(&self, datetime_cursor: &DateTime<Tz>) -> DateTime<Tz>;
 
+    // The index of the period (e.g. the day, week or month) that contains the given
+    // candidate. A series' interval selects every Nth period, counted from the period
+    // that contains the series' first candidate.
+    fn period_index(&self, occurrence_candidate: &DateTime<Tz>) -> i64;
+
+    // The first day of the period that contains the given candidate, for series whose periods
+    // span whole months or years; a series' set positions are counted from the first of its
+    // candidates on or after this day. Series without such periods return `None`.
+    fn period_start_date(&self, _occurrence_candidate: &DateTime<Tz>) -> Option<NaiveDate> {
+        return None;
+    }
+
+    // Moves the cursor forward to (or just before) the first candidate on or after the
+    // given instant, without visiting the candidates in between; expanding the series from
+    // the returned cursor must yield the same candidates on or after the instant as
+    // expanding it from the original cursor would.
+    fn seek_datetime_cursor(
+        &self,
+        datetime_cursor: &DateTime<Tz>,
+        instant: &DateTime<Tz>,
+    ) -> DateTime<Tz>;
+
     fn occurrence_candidate_matches_criteria(&self, _occurrence_candidate: &DateTime<Tz>) -> bool {
         return true;
     }
@@ -9,6 +9,8 @@ end
 require "active_support"
 require "active_support/core_ext/integer/time"
 require "active_support/core_ext/time"
+require "reprise/business_calendar"
+require "reprise/holiday_calendar"
 require "reprise/schedule"
 require "reprise/time_of_day"
 require "reprise/time_zone_identifier"
@@ -0,0 +1,38 @@
+# frozen_string_literal: true
+
+require "active_support"
+require "active_support/core_ext"
+
+module Reprise
+  # Describes the days that are worked: every day, other than those of the weekend and
+  # the holidays. A schedule's business day series (e.g. {Reprise::Schedule#repeat_business_daily})
+  # only occur on, and count, the business days of the schedule's calendar.
+  class BusinessCalendar
+    WEEKDAYS = %i[monday tuesday wednesday thursday friday saturday sunday].freeze
+    DEFAULT_WEEKEND = %i[saturday sunday].freeze
+
+    attr_reader :weekend, :holidays
+
+    # @param weekend [Symbol, Array<Symbol>] The weekdays that are not worked; any of +:monday+, +:tuesday+,
+    #   +:wednesday+, +:thursday+, +:friday+, +:saturday+, or +:sunday+, except all of them.
+    # @param holidays [Array<Date, Time>] The dates that are not worked, regardless of their weekday.
+    # @raise [ArgumentError] if a weekday is not supported, or every day of the week is part of the weekend.
+    # @example
+    #   Reprise::BusinessCalendar.new(holidays: [Date.new(2024, 12, 25), Date.new(2024, 12, 26)])
+    def initialize(weekend: DEFAULT_WEEKEND, holidays: [])
+      @weekend = Array(weekend).uniq.freeze
+      @holidays = holidays.map(&:to_date).uniq.sort.freeze
+
+      unsupported_weekdays = @weekend - WEEKDAYS
+      raise ArgumentError, "Unsupported weekday #{unsupported_weekdays.first.inspect}" if unsupported_weekdays.any?
+      raise ArgumentError, "A business calendar must have at least one business day a week" if @weekend.size == WEEKDAYS.size
+    end
+
+    # @param date [Date, Time]
+    # @return [Boolean]
+    def business_day?(date)
+      date = date.to_date
+      weekend.exclude?(WEEKDAYS[date.cwday - 1]) && holidays.exclude?(date)
+    end
+  end
+end
@@ -0,0 +1,107 @@
+# frozen_string_literal: true
+
+require "json"
+require "yaml"
+require "active_support"
+require "active_support/core_ext"
+
+module Reprise
+  # Describes a set of public holidays, each placed in every year that it is held in by a rule rather
+  # than listed date by date. Occurrences that fall on the holidays of a calendar that has been added
+  # to a schedule (see {Reprise::Schedule#add_holiday_calendar}) are excluded from it.
+  #
+  # Each holiday is a hash with a +:name+, and one of:
+  # - a +:month+ and +:day+, for holidays held on the same date every year;
+  # - a +:month+, +:weekday+ and +:nth_day+, for holidays held on the nth weekday of a month; +:nth_day+ is
+  #   0-indexed (e.g. 3 for the fourth thursday), or counts back from the end of the month if negative
+  #   (e.g. -1 for the last monday);
+  # - an +:easter_offset+, for holidays held a number of days before (if negative) or after Easter Sunday.
+  #
+  # Holidays may also give the day that they are +:observed+ on when they fall on a weekend (see
+  # {OBSERVED_SHIFTS}), and the +:first_year+ and +:last_year+ that they are held in. The calendars of
+  # {COUNTRIES} are defined in the same format, in YAML files that can be extended or replaced with your own.
+  class HolidayCalendar
+    DIRECTORY = File.expand_path("holiday_calendars", __dir__)
+    COUNTRIES = Dir[File.join(DIRECTORY, "*.yml")].map { |path| File.basename(path, ".yml").to_sym }.sort.freeze
+    # +:nearest_weekday+ observes a holiday that falls on a saturday on the friday before it, and one
+    # that falls on a sunday on the monday after it. +:next_weekday+ observes it on the first weekday after
+    # it that is not already a holiday (e.g. Christmas Day and Boxing Day on the following monday and tuesday).
+    # In either case, the holiday itself is also excluded.
+    OBSERVED_SHIFTS = %i[nearest_weekday next_weekday].freeze
+
+    attr_reader :name, :holidays
+
+    # @param country [Symbol] One of {COUNTRIES}; e.g. +:us+.
+    # @return [Reprise::HolidayCalendar] The calendar of the country's national public holidays.
+    # @raise [ArgumentError] if the country is not supported.
+    def self.country(country)
+      raise ArgumentError, "Unsupported country #{country.inspect}" unless COUNTRIES.include?(country)
+
+      load_file(File.join(DIRECTORY, "#{country}.yml"))
+    end
+
+    # @param path [String] The path of a YAML file with a +name+ and a list of +holidays+.
+    # @return [Reprise::HolidayCalendar]
+    # @raise [ArgumentError] if a holiday is not supported.
+    def self.load_file(path)
+      calendar = YAML.safe_load_file(path, symbolize_names: true)
+
+      new(name: calendar[:name], holidays: calendar[:holidays])
+    end
+
+    # @param holidays [Array<Hash>] The holidays of the calendar; see {Reprise::HolidayCalendar}.
+    # @param name [String, nil]
+    # @raise [ArgumentError] if a holiday is not supported.
+    # @example
+    #   Reprise::HolidayCalendar.new(
+    #     name: "Company holidays",
+    #     holidays: Reprise::HolidayCalendar.country(:us).holidays + [
+    #       { name: "Day after Thanksgiving", month: 11, weekday: :friday, nth_day: 3 },
+    #       { name: "Founders' Day", month: 3, day: 15, observed: :nearest_weekday, first_year: 2020 }
+    #     ]
+    #   )
+    def initialize(holidays:, name: nil)
+      @name = name
+      @holidays = holidays.map { |holiday| holiday.to_h.symbolize_keys.freeze }.freeze
+      @internal_holidays = @holidays.map do |holiday|
+        {
+          name: holiday[:name].to_s,
+          date: date_of(holiday),
+          observed: observed_of(holiday),
+          first_year: holiday[:first_year],
+          last_year: holiday[:last_year]
+        }
+      end
+    end
+
+    # @private
+    def to_internal_json
+      @internal_holidays.to_json
+    end
+
+    private
+
+    def date_of(holiday)
+      if holiday.key?(:easter_offset)
+        { kind: :easter, offset_in_days: holiday[:easter_offset] }
+      elsif holiday.key?(:month) && holiday.key?(:weekday) && holiday.key?(:nth_day)
+        weekday = holiday[:weekday].to_sym
+        raise ArgumentError, "Unsupported weekday #{weekday.inspect}" unless BusinessCalendar::WEEKDAYS.include?(weekday)
+
+        { kind: :nth_weekday, month: holiday[:month], weekday:, nth_weekday: holiday[:nth_day] }
+      elsif holiday.key?(:month) && holiday.key?(:day)
+        { kind: :fixed, month: holiday[:month], day: holiday[:day] }
+      else
+        raise ArgumentError, "Unsupported holiday #{holiday.inspect}"
+      end
+    end
+
+    def observed_of(holiday)
+      observed = holiday[:observed]&.to_sym
+      return :none if observed.nil?
+      raise ArgumentError, "Unsupported observed shift #{observed.inspect}" unless OBSERVED_SHIFTS.include?(observed)
+
+      observed
+    end
+  end
+end
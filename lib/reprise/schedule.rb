@@ -3,6 +3,10 @@
 module Reprise
   class Error < StandardError; end
   class InvalidRangeError < Error; end
+  class UnsupportedRRuleError < Error; end
+  class UnsupportedICalError < Error; end
+  class DeserializationError < Error; end
+  class UnboundedScheduleError < Error; end
 
   # The +Reprise::Schedule+ class is the primary interface of the Reprise gem.
   #
@@ -20,39 +24,133 @@ module Reprise
   #   For any changes in the implementation of the interface, prefer DevX over DRY and save our
   #   sophistication budget for the underlying Rust extension.
   class Schedule
-    # All schedules must be constructed with a valid +starts_at+ and +ends_at+ time.
-    # Reprise does not support infinitely-recurring schedules, or the bounding
-    # of schedules on the basis of a maximum occurrence count.
+    # All schedules must be constructed with a valid +starts_at+ time. Schedules without an +ends_at+
+    # time are open-ended: their occurrences can be queried within any interval of time, or enumerated
+    # lazily via {#each_occurrence}, but cannot all be expanded at once via {#occurrences}.
+    # Reprise does not support the bounding of schedules on the basis of a maximum occurrence count.
     #
     # @param starts_at [Time, ActiveSupport::TimeWithZone]
     #   The beginning of the schedule; the earliest possible moment for a valid occurrence.
     #   If no +time_zone+ is given, the schedule's time zone will be inferred from +starts_at+,
     #   defaulting to UTC if it lacks time zone information (e.g. it is a plain +Time+).
-    # @param ends_at [Time, ActiveSupport::TimeWithZone]
+    # @param ends_at [Time, ActiveSupport::TimeWithZone, nil]
     #   The end of the schedule; the latest possible moment for a valid occurrence.
+    #   If +nil+, the schedule is open-ended.
     # @param time_zone [String]
     #   Must be an unambiguous, valid Rails time zone string or IANA time-zone identifier
     #   according to +ActiveSupport::TimeZone::find_tzinfo+.
     #   See https://github.com/tzinfo/tzinfo/issues/53
+    # @param business_calendar [Reprise::BusinessCalendar, nil]
+    #   The weekend and holidays that the schedule's business day series (e.g. {#repeat_business_daily})
+    #   skip over, and that series with a +roll+ convention move their occurrences off of.
+    #   If +nil+, saturdays and sundays are the only days that are not worked.
     # @raise [Reprise::InvalidTimeZoneError] if the time zone is ambiguous or invalid.
-    def initialize(starts_at:, ends_at:, time_zone: nil)
-      raise InvalidRangeError, "The end time cannot precede the start time" if ends_at < starts_at
+    def initialize(starts_at:, ends_at: nil, time_zone: nil, business_calendar: nil)
+      raise InvalidRangeError, "The end time cannot precede the start time" if ends_at && ends_at < starts_at
 
       @starts_at = starts_at
       @ends_at = ends_at
       @time_zone = TimeZoneIdentifier.new(time_zone:, datetime_source: starts_at).to_s
       @default_time_of_day = TimeOfDay.new(starts_at)
+      @business_calendar = business_calendar
     end
 
+    # Builds a schedule from the events of an iCalendar (RFC 5545) document.
+    # @param ical [String] A +VCALENDAR+ document, e.g. the contents of an +.ics+ file.
+    # @param starts_at [Time, ActiveSupport::TimeWithZone] The beginning of the schedule.
+    # @param ends_at [Time, ActiveSupport::TimeWithZone, nil] The end of the schedule, if any.
+    # @param time_zone [String] The time zone of the schedule; see {#initialize}.
+    # @return [Reprise::Schedule]
+    # @raise [Reprise::UnsupportedICalError] see {#add_ical}.
+    # @example
+    #   Reprise::Schedule.from_ical(File.read("team.ics"), starts_at: Time.current, ends_at: 1.year.from_now)
+    def self.from_ical(ical, starts_at:, ends_at: nil, time_zone: nil)
+      new(starts_at:, ends_at:, time_zone:).tap { |schedule| schedule.add_ical(ical) }
+    end
+
+    # Restores a schedule from the JSON produced by {#to_json}.
+    # Payloads written by earlier versions of Reprise remain readable by later ones.
+    # @param json [String]
+    # @return [Reprise::Schedule]
+    # @raise [Reprise::DeserializationError] if the JSON is not a valid schedule, or was
+    #   written by a newer version of Reprise that uses an unsupported schema version.
+    # @example
+    #   Reprise::Schedule.from_json(redis.get("schedule:42"))
+    def self.from_json(json)
+      from_internal_schedule(::Reprise::Core::Schedule.from_json(json))
+    end
+
+    # Restores a schedule from the binary snapshot produced by {#dump}.
+    # Snapshots written by earlier versions of Reprise remain readable by later ones.
+    # @param snapshot [String] A binary (+ASCII-8BIT+) string.
+    # @return [Reprise::Schedule]
+    # @raise [Reprise::DeserializationError] if the string is not a valid snapshot, or was
+    #   written by a newer version of Reprise that uses an unsupported schema version.
+    def self.load(snapshot)
+      from_internal_schedule(::Reprise::Core::Schedule.load(snapshot))
+    end
+
+    # @private
+    def self.from_internal_schedule(internal_schedule)
+      time_zone = internal_schedule.time_zone
+
+      new(
+        starts_at: Time.at(internal_schedule.starts_at_unix_timestamp).in_time_zone(time_zone),
+        ends_at: internal_schedule.ends_at_unix_timestamp&.then { |ends_at| Time.at(ends_at).in_time_zone(time_zone) },
+        time_zone:
+      ).tap { |schedule| schedule.instance_variable_set(:@_internal_schedule, internal_schedule) }
+    end
+    private_class_method :from_internal_schedule
+
     # Returns an array of occurrences sorted in order of ascending occurrence start time.
     # This method is not cached; on every call, it will recompute all of the schedule's occurrences.
     # @return [Array<Reprise::Core::Occurrence>]
+    # @raise [Reprise::UnboundedScheduleError] if the schedule is open-ended and has one or more
+    #   series that are bounded by neither a +count+ nor an +ends_at+ time of their own.
     def occurrences
       internal_schedule.occurrences
     end
 
+    # Yields the schedule's occurrences in order of ascending start time, expanding the schedule
+    # only as far as the occurrences that are consumed. Unlike {#occurrences}, this never holds
+    # all of the schedule's occurrences in memory at once, so it can be stopped early cheaply;
+    # e.g. to find the first few occurrences of a schedule that spans many years.
+    # @yieldparam occurrence [Reprise::Core::Occurrence]
+    # @return [Enumerator::Lazy<Reprise::Core::Occurrence>, Reprise::Schedule] a lazy enumerator
+    #   if no block is given; otherwise, the schedule itself.
+    # @example
+    #   schedule.each_occurrence.reject { |o| o.starts_at.saturday? }.first(10)
+    def each_occurrence(&block)
+      return internal_schedule.each_occurrence.lazy unless block
+
+      internal_schedule.each_occurrence(&block)
+      self
+    end
+
+    # Returns the first occurrence that starts strictly after the given time, skipping any
+    # occurrences that overlap with an exclusion. Series are expanded from their start, so
+    # a series' +count+ and +interval+ are respected exactly as they are by {#occurrences}.
+    # @param after [Time, ActiveSupport::TimeWithZone]
+    # @return [Reprise::Core::Occurrence, nil] +nil+ if the schedule has no later occurrences.
+    # @example
+    #   schedule.next_occurrence(after: Time.current)
+    def next_occurrence(after:)
+      internal_schedule.next_occurrence(after.to_i)
+    end
+
+    # Returns the last occurrence that starts strictly before the given time, skipping any
+    # occurrences that overlap with an exclusion.
+    # @param before [Time, ActiveSupport::TimeWithZone]
+    # @return [Reprise::Core::Occurrence, nil] +nil+ if the schedule has no earlier occurrences.
+    # @example
+    #   schedule.previous_occurrence(before: Time.current)
+    def previous_occurrence(before:)
+      internal_schedule.previous_occurrence(before.to_i)
+    end
+
     # @!macro [new] weekday
-    #   @param weekday [Symbol] Accepts +:monday+, +:tuesday+, +:wednesday+, +:thursday+, or +:friday+.
+    #   @param weekday [Symbol] Accepts +:monday+, +:tuesday+, +:wednesday+, +:thursday+, +:friday+,
+    #     +:saturday+, or +:sunday+.
 
     # @!macro [new] time_of_day
     #   @param time_of_day [Hash,Time,nil]
@@ -67,11 +165,28 @@ module Reprise
     #   @raise [InvalidHashError] if the hash representation of the time is invalid.
     #   @raise [RangeError] if either the hour, minute, or second is out-of-range.
 
+    # @!macro [new] times_of_day
+    #   @param time_of_day [Hash,Time,Array<Hash,Time>,nil]
+    #     Either a local time value from which the hour, minute, and second
+    #     should be derived, or a hash containing at least one of +hour+, +minute+,
+    #     or +second+. If +nil+, the time of day will be inferred from the schedule's
+    #     +starts_at+ value. Given an array of either, the series occurs at each of the
+    #     times on every day that it occurs on; its +count+ and +interval+ apply to all of
+    #     those occurrences together.
+    #   @option time_of_day [Integer] :hour, >= 0 && <= 23
+    #   @option time_of_day [Integer] :minute, >= 0 && <= 59
+    #   @option time_of_day [Integer] :second, >= 0 && <= 59
+    #   @raise [UnsupportedTypeError] if +time_of_day+ is neither a +Hash+ nor a +Time+.
+    #   @raise [InvalidHashError] if the hash representation of the time is invalid.
+    #   @raise [RangeError] if either the hour, minute, or second is out-of-range.
+    #   @raise [ArgumentError] if +time_of_day+ is an empty array.
+
     # @!macro [new] interval
     #   @param interval [Integer]
-    #     This determines whether or not occurrences should be skipped.
-    #     A value of +1+ means that every occurrence for the series should be returned;
-    #     +2+, every other occurrence should be returned, etc.
+    #     This determines whether or not the series' periods (e.g. the weeks of a weekly series)
+    #     should be skipped. A value of +1+ means that the occurrences in every period should be
+    #     returned; +2+, those in every other period, etc. Periods are counted from the one that
+    #     contains the series' first occurrence, regardless of the window being queried.
 
     # @!macro [new] recurring_series_start_and_end_times
     #   @param starts_at [Time, nil] The time that the series should begin. If left blank,
@@ -89,7 +204,42 @@ module Reprise
     #   @param count [Integer, nil] An optional count limit to apply to the occurrences
     #     of a series; once the schedule has generated the requested number of occurrences,
     #     it will halt further expansion of that specific series. The count takes precedence
-    #     over the optional +ends_at+ param.
+    #     over the optional +ends_at+ param. Occurrences are counted from the start of the
+    #     series (after its +interval+ is applied), regardless of the window being queried.
+
+    # @!macro [new] set_positions
+    #   @param set_positions [Integer, Array<Integer>, nil] An optional list of positions, like an iCalendar
+    #     +BYSETPOS+, that keeps only some of the series' occurrences within each of its periods (e.g. each month):
+    #     1 keeps the first, 2 the second, -1 the last, -2 the second-to-last, and so on. Positions are counted
+    #     among all of a period's occurrences, before the series' +starts_at+, +ends_at+ and +interval+ apply.
+    #   @raise [ArgumentError] if a set position is zero, or beyond the occurrences that any period can have.
+
+    # @!macro [new] every
+    #   @param every [Integer, nil] An optional step, in seconds, between the series' consecutive occurrences,
+    #     if other than the series' own unit of time (e.g. +15+ for every fifteen seconds). Steps are measured
+    #     in elapsed time, so they are unaffected by Daylight Savings Time transitions, and any +interval+ is
+    #     applied to the steps themselves.
+    #   @raise [ArgumentError] if the step is less than one second.
+
+    # @!macro [new] daily_window
+    #   @param window_start [Hash, Time, nil] An optional local time of day, given like +time_of_day+,
+    #     before which the series does not occur on any day.
+    #   @param window_end [Hash, Time, nil] An optional local time of day at (and after) which the series
+    #     does not occur on any day.
+    #   @param weekdays [Symbol, Array<Symbol>, nil] An optional list of the weekdays (e.g. +:monday+) that the
+    #     series occurs on; every day, if left blank. The series' steps are not affected by the window; it only
+    #     skips over the steps that fall outside of it, without generating them.
+    #   @raise [ArgumentError] if the window ends before it starts.
+
+    # @!macro [new] roll
+    #   @param roll [Symbol, nil] An optional convention for moving the series' occurrences that fall on
+    #     days that are not worked, according to the schedule's business calendar (see {#initialize}),
+    #     onto business days instead: +:following+ moves them to the next business day; +:preceding+, to
+    #     the previous one; +:modified_following+ and +:modified_preceding+ do the same, unless that would
+    #     move them into another month, in which case they are moved the other way. Occurrences keep their
+    #     local time of day, and occurrences that are moved onto the same time are merged. The series'
+    #     +interval+ and +set_positions+ apply to the days that it would otherwise have occurred on.
+    #   @raise [ArgumentError] if the convention is not supported.
 
     # @!macro [new] label
     #   @param label [String, nil] An optional label to apply to all of the occurrences
@@ -97,14 +247,47 @@ module Reprise
 
     # @!macro time_of_day
     # @!macro duration_in_seconds
+    # @!macro every
+    # @!macro daily_window
+    # @!macro interval
+    # @!macro recurring_series_start_and_end_times
+    # @!macro count
+    # @!macro label
+    # @return [void]
+    # @example Every fifteen seconds through the business day
+    #   schedule.repeat_secondly(every: 15, duration_in_seconds: 5, window_start: { hour: 9 }, window_end: { hour: 17 })
+    def repeat_secondly(time_of_day: nil, duration_in_seconds:, every: nil, window_start: nil, window_end: nil, weekdays: nil, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+      internal_schedule.repeat_secondly(
+        every&.to_i,
+        window_start&.then { |t| TimeOfDay.new(t).to_h },
+        window_end&.then { |t| TimeOfDay.new(t).to_h },
+        Array(weekdays),
+        times_of_day: [TimeOfDay.new(time_of_day || self.starts_at).to_h],
+        duration_in_seconds:,
+        interval:,
+        starts_at_unix_timestamp: starts_at.presence&.to_i,
+        ends_at_unix_timestamp: ends_at.presence&.to_i,
+        count:,
+        label:
+      )
+    end
+
+    # @!macro time_of_day
+    # @!macro duration_in_seconds
+    # @!macro every
+    # @!macro daily_window
     # @!macro interval
     # @!macro recurring_series_start_and_end_times
     # @!macro count
     # @!macro label
     # @return [void]
-    def repeat_minutely(time_of_day: nil, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+    def repeat_minutely(time_of_day: nil, duration_in_seconds:, every: nil, window_start: nil, window_end: nil, weekdays: nil, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
       internal_schedule.repeat_minutely(
-        time_of_day: TimeOfDay.new(time_of_day || self.starts_at).to_h,
+        every&.to_i,
+        window_start&.then { |t| TimeOfDay.new(t).to_h },
+        window_end&.then { |t| TimeOfDay.new(t).to_h },
+        Array(weekdays),
+        times_of_day: [TimeOfDay.new(time_of_day || self.starts_at).to_h],
         duration_in_seconds:,
         interval:,
         starts_at_unix_timestamp: starts_at.presence&.to_i,
@@ -116,14 +299,20 @@ module Reprise
 
     # @!macro time_of_day
     # @!macro duration_in_seconds
+    # @!macro every
+    # @!macro daily_window
     # @!macro interval
     # @!macro recurring_series_start_and_end_times
     # @!macro count
     # @!macro label
     # @return [void]
-    def repeat_hourly(time_of_day: nil, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+    def repeat_hourly(time_of_day: nil, duration_in_seconds:, every: nil, window_start: nil, window_end: nil, weekdays: nil, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
       internal_schedule.repeat_hourly(
-        time_of_day: TimeOfDay.new(time_of_day || self.starts_at).to_h,
+        every&.to_i,
+        window_start&.then { |t| TimeOfDay.new(t).to_h },
+        window_end&.then { |t| TimeOfDay.new(t).to_h },
+        Array(weekdays),
+        times_of_day: [TimeOfDay.new(time_of_day || self.starts_at).to_h],
         duration_in_seconds:,
         interval:,
         starts_at_unix_timestamp: starts_at.presence&.to_i,
@@ -133,7 +322,7 @@ module Reprise
       )
     end
 
-    # @!macro time_of_day
+    # @!macro times_of_day
     # @!macro duration_in_seconds
     # @!macro interval
     # @!macro recurring_series_start_and_end_times
@@ -142,7 +331,7 @@ module Reprise
     # @return [void]
     def repeat_daily(time_of_day: nil, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
       internal_schedule.repeat_daily(
-        time_of_day: TimeOfDay.new(time_of_day || self.starts_at).to_h,
+        times_of_day: times_of_day_for(time_of_day),
         duration_in_seconds:,
         interval:,
         starts_at_unix_timestamp: starts_at.presence&.to_i,
@@ -152,23 +341,59 @@ module Reprise
       )
     end
 
-    # @!macro weekday
-    # @!macro time_of_day
+    # Repeats on every business day of the schedule's business calendar (see {#initialize}).
+    # @!macro times_of_day
+    # @!macro duration_in_seconds
+    # @param interval [Integer] A value of +1+ means that the series occurs on every business day;
+    #   +2+, on every other business day, etc. Business days are counted from the series' first
+    #   occurrence, skipping over weekends and holidays.
+    # @!macro recurring_series_start_and_end_times
+    # @!macro count
+    # @!macro label
+    # @return [void]
+    # @example on every business day
+    #   schedule.repeat_business_daily(time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    # @example on every fifth business day
+    #   schedule.repeat_business_daily(interval: 5, time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    def repeat_business_daily(time_of_day: nil, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+      internal_schedule.repeat_business_daily(
+        times_of_day: times_of_day_for(time_of_day),
+        duration_in_seconds:,
+        interval:,
+        starts_at_unix_timestamp: starts_at.presence&.to_i,
+        ends_at_unix_timestamp: ends_at.presence&.to_i,
+        count:,
+        label:
+      )
+    end
+
+    # @param weekdays [Symbol, Array<Symbol>] One or more of +:monday+, +:tuesday+, +:wednesday+,
+    #   +:thursday+, +:friday+, +:saturday+, or +:sunday+.
+    # @param week_start [Symbol] The first day of each week, which determines the weeks that
+    #   an +interval+ greater than 1 skips (cf. an RRULE's +WKST+).
+    # @!macro times_of_day
     # @!macro duration_in_seconds
     # @!macro interval
     # @!macro recurring_series_start_and_end_times
     # @!macro count
     # @!macro label
     # @return [void]
+    # @raise [ArgumentError] if no weekdays are given, or a weekday or +week_start+ is not supported.
     # @example with a +time_of_day+ hash
     #   schedule.repeat_weekly(:monday, time_of_day: { hour: 6 }, duration_in_seconds: 30)
     # @example with a local time for +time_of_day+
     #   local_time = Time.current.in_time_zone(my_current_time_zone)
     #   schedule.repeat_weekly(:monday, time_of_day: local_time, duration_in_seconds: 30)
-    def repeat_weekly(weekday, time_of_day: nil, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+    # @example on several weekdays, every other week
+    #   schedule.repeat_weekly(%i[monday wednesday friday], interval: 2, duration_in_seconds: 30)
+    def repeat_weekly(weekdays, week_start: :monday, time_of_day: nil, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+      weekdays = Array(weekdays)
+      raise ArgumentError, "At least one weekday is required" if weekdays.empty?
+
       internal_schedule.repeat_weekly(
-        weekday,
-        time_of_day: TimeOfDay.new(time_of_day || self.starts_at).to_h,
+        weekdays,
+        week_start,
+        times_of_day: times_of_day_for(time_of_day),
         duration_in_seconds:,
         interval:,
         starts_at_unix_timestamp: starts_at.presence&.to_i,
@@ -178,43 +403,137 @@ module Reprise
       )
     end
 
-    # @param day_number [Integer] The number of the day in the month; >= 1 && <= 31
-    # @!macro time_of_day
+    # @param day_numbers [Integer, Array<Integer>] One or more days of the month; either >= 1 && <= 31,
+    #   or counted back from the end of the month, from -1 (the last day) to -31.
+    # @param missing_day [Symbol] What to do in months that lack one of the days (e.g. the 31st of
+    #   April): +:skip+ the day in that month, +:clamp+ it to the closest day of the month (e.g. the
+    #   30th of April), or +:roll+ it over to the first day of the following month.
+    # @!macro times_of_day
     # @!macro duration_in_seconds
     # @!macro interval
     # @!macro recurring_series_start_and_end_times
     # @!macro count
     # @!macro label
+    # @!macro set_positions
+    # @!macro roll
     # @return [void]
+    # @raise [ArgumentError] if no days are given, a day is out of range, or +missing_day+ is not supported.
     # @example
     #   schedule.repeat_monthly_by_day(15, time_of_day: { hour: 9 }, duration_in_seconds: 30)
-    def repeat_monthly_by_day(day_number, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+    # @example on the 15th and the last day of every month
+    #   schedule.repeat_monthly_by_day([15, -1], time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    # @example on the 15th and the last day of every month, or the business day before
+    #   schedule.repeat_monthly_by_day([15, -1], roll: :preceding, time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    def repeat_monthly_by_day(day_numbers, missing_day: :skip, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil, set_positions: nil, roll: nil)
+      day_numbers = Array(day_numbers)
+      raise ArgumentError, "At least one day number is required" if day_numbers.empty?
+
       internal_schedule.repeat_monthly_by_day(
-        day_number,
-        time_of_day: TimeOfDay.new(time_of_day || self.starts_at).to_h,
+        day_numbers,
+        missing_day,
+        times_of_day: times_of_day_for(time_of_day),
         duration_in_seconds:,
         interval:,
         starts_at_unix_timestamp: starts_at.presence&.to_i,
         ends_at_unix_timestamp: ends_at.presence&.to_i,
         count:,
-        label:
+        label:,
+        set_positions: set_positions_for(set_positions),
+        roll:
       )
     end
 
-    # @!macro weekday
-    # @param nth_day [Integer] The nth weekday, 0-indexed; e.g. 0 might represent the first wednesday
-    # @!macro time_of_day
+    # @param weekday [Symbol, Array<Array(Symbol, Integer)>] Accepts +:monday+, +:tuesday+,
+    #   +:wednesday+, +:thursday+, +:friday+, +:saturday+, or +:sunday+; or a list of
+    #   +[weekday, nth_day]+ pairs, for a series that repeats on several weekdays each month.
+    # @param nth_day [Integer, nil] The nth weekday, 0-indexed; e.g. 0 might represent the first wednesday.
+    #   Negative values count back from the end of the month; e.g. -1 represents the last wednesday.
+    #   Must be between -5 and 4; omitted when +weekday+ is a list of pairs.
+    # @!macro times_of_day
     # @!macro duration_in_seconds
     # @!macro interval
     # @!macro recurring_series_start_and_end_times
     # @!macro count
     # @!macro label
+    # @!macro set_positions
     # @return [void]
-    def repeat_monthly_by_nth_weekday(weekday, nth_day, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+    # @raise [ArgumentError] if no weekdays are given, or an +nth_day+ is out of range.
+    # @example on the last friday of every month
+    #   schedule.repeat_monthly_by_nth_weekday(:friday, -1, time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    # @example on the first and third tuesday of every month
+    #   schedule.repeat_monthly_by_nth_weekday([[:tuesday, 0], [:tuesday, 2]], time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    def repeat_monthly_by_nth_weekday(weekday, nth_day = nil, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil, set_positions: nil)
+      nth_weekdays = weekday.is_a?(Array) ? weekday : [[weekday, nth_day]]
+      raise ArgumentError, "At least one weekday is required" if nth_weekdays.empty?
+
       internal_schedule.repeat_monthly_by_nth_weekday(
-        weekday,
+        nth_weekdays,
+        times_of_day: times_of_day_for(time_of_day),
+        duration_in_seconds:,
+        interval:,
+        starts_at_unix_timestamp: starts_at.presence&.to_i,
+        ends_at_unix_timestamp: ends_at.presence&.to_i,
+        count:,
+        label:,
+        set_positions: set_positions_for(set_positions)
+      )
+    end
+
+    # @param weekdays [Symbol, Array<Symbol>] One or more of +:monday+, +:tuesday+, +:wednesday+,
+    #   +:thursday+, +:friday+, +:saturday+, or +:sunday+. The series occurs on every one of these
+    #   weekdays in each month; unlike {#repeat_weekly}, its periods are months.
+    # @!macro times_of_day
+    # @!macro duration_in_seconds
+    # @!macro interval
+    # @!macro recurring_series_start_and_end_times
+    # @!macro count
+    # @!macro label
+    # @!macro set_positions
+    # @return [void]
+    # @raise [ArgumentError] if no weekdays are given.
+    # @example on the last weekday of every month
+    #   schedule.repeat_monthly_by_weekday(%i[monday tuesday wednesday thursday friday], set_positions: -1, time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    # @example on every monday of every other month
+    #   schedule.repeat_monthly_by_weekday(:monday, interval: 2, time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    def repeat_monthly_by_weekday(weekdays, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil, set_positions: nil)
+      weekdays = Array(weekdays)
+      raise ArgumentError, "At least one weekday is required" if weekdays.empty?
+
+      internal_schedule.repeat_monthly_by_weekday(
+        weekdays,
+        times_of_day: times_of_day_for(time_of_day),
+        duration_in_seconds:,
+        interval:,
+        starts_at_unix_timestamp: starts_at.presence&.to_i,
+        ends_at_unix_timestamp: ends_at.presence&.to_i,
+        count:,
+        label:,
+        set_positions: set_positions_for(set_positions)
+      )
+    end
+
+    # Repeats on the nth business day of every month, going by the schedule's business calendar
+    # (see {#initialize}); months with fewer business days are skipped.
+    # @param nth_day [Integer] The nth business day of the month, 0-indexed; e.g. 0 represents the first
+    #   business day. Negative values count back from the end of the month; e.g. -1 represents the last
+    #   business day. Must be within the most business days that the calendar's weekend leaves a month;
+    #   e.g. between -23 and 22 with a Saturday and Sunday weekend.
+    # @!macro times_of_day
+    # @!macro duration_in_seconds
+    # @!macro interval
+    # @!macro recurring_series_start_and_end_times
+    # @!macro count
+    # @!macro label
+    # @return [void]
+    # @raise [ArgumentError] if +nth_day+ is out of range.
+    # @example on the third business day of every month
+    #   schedule.repeat_monthly_by_nth_business_day(2, time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    # @example on the last business day of every third month
+    #   schedule.repeat_monthly_by_nth_business_day(-1, interval: 3, time_of_day: { hour: 17 }, duration_in_seconds: 30)
+    def repeat_monthly_by_nth_business_day(nth_day, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+      internal_schedule.repeat_monthly_by_nth_business_day(
         nth_day,
-        time_of_day: TimeOfDay.new(time_of_day || self.starts_at).to_h,
+        times_of_day: times_of_day_for(time_of_day),
         duration_in_seconds:,
         interval:,
         starts_at_unix_timestamp: starts_at.presence&.to_i,
@@ -225,28 +544,223 @@ module Reprise
     end
 
     # @param day_number [Integer] The number of the day in the year; >= 1 && <= 366
-    # @!macro time_of_day
+    # @!macro times_of_day
     # @!macro duration_in_seconds
     # @!macro interval
     # @!macro recurring_series_start_and_end_times
     # @!macro count
     # @!macro label
+    # @!macro roll
     # @return [void]
+    # @raise [ArgumentError] if the day is out of range.
     # @example
     #   schedule.repeat_annually_by_day(200, duration_in_seconds: 30)
-    def repeat_annually_by_day(day_number, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+    def repeat_annually_by_day(day_number, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil, roll: nil)
       internal_schedule.repeat_annually_by_day(
         day_number,
-        time_of_day: TimeOfDay.new(time_of_day || self.starts_at).to_h,
+        times_of_day: times_of_day_for(time_of_day),
+        duration_in_seconds:,
+        interval:,
+        starts_at_unix_timestamp: starts_at.presence&.to_i,
+        ends_at_unix_timestamp: ends_at.presence&.to_i,
+        count:,
+        label:,
+        roll:
+      )
+    end
+
+    # @param month [Integer] The month of the year; >= 1 && <= 12
+    # @param day [Integer] The day of the month; it must exist in that month, at least in leap years.
+    # @param missing_day [Symbol] What happens to the 29th of February in common years: +:skip+
+    #   the year (the default), +:clamp+ it to the 28th of February, or +:roll+ it over to the 1st of March.
+    # @!macro times_of_day
+    # @!macro duration_in_seconds
+    # @!macro interval
+    # @!macro recurring_series_start_and_end_times
+    # @!macro count
+    # @!macro label
+    # @!macro roll
+    # @return [void]
+    # @raise [ArgumentError] if the day does not exist in the month, or +missing_day+ is not supported.
+    # @example on the 15th of March
+    #   schedule.repeat_annually_by_month_day(3, 15, time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    # @example on the 29th of February, or the 28th in common years
+    #   schedule.repeat_annually_by_month_day(2, 29, missing_day: :clamp, time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    def repeat_annually_by_month_day(month, day, missing_day: :skip, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil, roll: nil)
+      internal_schedule.repeat_annually_by_month_day(
+        month,
+        day,
+        missing_day,
+        times_of_day: times_of_day_for(time_of_day),
+        duration_in_seconds:,
+        interval:,
+        starts_at_unix_timestamp: starts_at.presence&.to_i,
+        ends_at_unix_timestamp: ends_at.presence&.to_i,
+        count:,
+        label:,
+        roll:
+      )
+    end
+
+    # @param month [Integer] The month of the year; >= 1 && <= 12
+    # @!macro weekday
+    # @param nth_day [Integer] The nth weekday of the month, 0-indexed; e.g. 3 might represent the fourth thursday.
+    #   Negative values count back from the end of the month; e.g. -1 represents the last monday.
+    #   Must be between -5 and 4.
+    # @!macro times_of_day
+    # @!macro duration_in_seconds
+    # @!macro interval
+    # @!macro recurring_series_start_and_end_times
+    # @!macro count
+    # @!macro label
+    # @return [void]
+    # @raise [ArgumentError] if the month or +nth_day+ is out of range.
+    # @example on the fourth thursday of November
+    #   schedule.repeat_annually_by_nth_weekday(11, :thursday, 3, time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    def repeat_annually_by_nth_weekday(month, weekday, nth_day, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil)
+      internal_schedule.repeat_annually_by_nth_weekday(
+        month,
+        weekday,
+        nth_day,
+        times_of_day: times_of_day_for(time_of_day),
+        duration_in_seconds:,
+        interval:,
+        starts_at_unix_timestamp: starts_at.presence&.to_i,
+        ends_at_unix_timestamp: ends_at.presence&.to_i,
+        count:,
+        label:
+      )
+    end
+
+    # @param week_numbers [Integer, Array<Integer>, Range] One or more ISO 8601 week numbers; >= 1 && <= 53,
+    #   or counting back from the last week of the ISO year if negative (e.g. -1 for the last week).
+    #   Week 53 only exists in some ISO years, which are skipped otherwise.
+    # @param weekdays [Symbol, Array<Symbol>] One or more of +:monday+, +:tuesday+, +:wednesday+,
+    #   +:thursday+, +:friday+, +:saturday+, or +:sunday+.
+    # @!macro times_of_day
+    # @!macro duration_in_seconds
+    # @!macro interval
+    # @!macro recurring_series_start_and_end_times
+    # @!macro count
+    # @!macro label
+    # @!macro set_positions
+    # @return [void]
+    # @raise [ArgumentError] if no week numbers or weekdays are given, or a week number is out of range.
+    # @example on the mondays of ISO weeks 1 to 26
+    #   schedule.repeat_annually_by_iso_week(1..26, :monday, time_of_day: { hour: 9 }, duration_in_seconds: 30)
+    def repeat_annually_by_iso_week(week_numbers, weekdays, time_of_day:, duration_in_seconds:, interval: 1, starts_at: nil, ends_at: nil, count: nil, label: nil, set_positions: nil)
+      week_numbers = Array(week_numbers)
+      weekdays = Array(weekdays)
+      raise ArgumentError, "At least one week number is required" if week_numbers.empty?
+      raise ArgumentError, "At least one weekday is required" if weekdays.empty?
+
+      internal_schedule.repeat_annually_by_iso_week(
+        week_numbers,
+        weekdays,
+        times_of_day: times_of_day_for(time_of_day),
         duration_in_seconds:,
         interval:,
         starts_at_unix_timestamp: starts_at.presence&.to_i,
         ends_at_unix_timestamp: ends_at.presence&.to_i,
         count:,
+        label:,
+        set_positions: set_positions_for(set_positions)
+      )
+    end
+
+    # Adds the recurring series described by an iCalendar recurrence rule (RFC 5545).
+    # The rule's +INTERVAL+, +COUNT+ and +UNTIL+ parts take the place of the +interval+, +count+
+    # and +ends_at+ options of the other +#repeat_*+ methods; when a rule leaves the day of its
+    # occurrences implicit (e.g. +FREQ=WEEKLY+ without +BYDAY+), it is taken from +starts_at+.
+    # @param rrule [String] The recurrence rule, with or without its +RRULE:+ prefix.
+    #   Supports the +FREQ+, +INTERVAL+, +COUNT+, +UNTIL+, +BYDAY+, +BYMONTHDAY+, +BYYEARDAY+,
+    #   +BYMONTH+, +BYWEEKNO+, +BYSETPOS+, +BYHOUR+, +BYMINUTE+, +BYSECOND+ and +WKST+ rule parts. +BYHOUR+, +BYMINUTE+
+    #   and +BYSECOND+ replace the corresponding parts of +time_of_day+. The +INTERVAL+ of a +SECONDLY+, +MINUTELY+
+    #   or +HOURLY+ rule becomes the +every+ step of the corresponding series, and its +BYDAY+ and +BYHOUR+
+    #   parts its +weekdays+ and daily window; the hours must be consecutive.
+    # @!macro times_of_day
+    # @!macro duration_in_seconds
+    # @!macro recurring_series_start_and_end_times
+    # @!macro label
+    # @return [void]
+    # @raise [Reprise::UnsupportedRRuleError] if the rule is malformed, or cannot be represented
+    #   by Reprise's recurring series.
+    # @example
+    #   schedule.repeat_rrule("FREQ=MONTHLY;BYDAY=-1FR;COUNT=6", time_of_day: { hour: 17 }, duration_in_seconds: 3600)
+    def repeat_rrule(rrule, time_of_day: nil, duration_in_seconds:, starts_at: nil, ends_at: nil, label: nil)
+      internal_schedule.repeat_rrule(
+        rrule,
+        times_of_day: times_of_day_for(time_of_day),
+        duration_in_seconds:,
+        interval: 1,
+        starts_at_unix_timestamp: starts_at.presence&.to_i,
+        ends_at_unix_timestamp: ends_at.presence&.to_i,
+        count: nil,
         label:
       )
     end
 
+    # Adds a series whose occurrences are those of the series labelled +base_label+, each moved by
+    # +offset+; e.g. a reminder two days before each occurrence of a monthly board meeting. Occurrences
+    # of the base series that are excluded from the schedule are not moved, and the moved occurrences
+    # are themselves subject to the schedule's bookends and exclusions. Every series with the label is
+    # a base of the derived series, including those added after it; derived series cannot be the base
+    # of other derived series.
+    # @param base_label [String] The label of the series to move the occurrences of.
+    # @param offset [Integer, ActiveSupport::Duration] How far to move each occurrence; earlier if negative.
+    #   Integers are a number of seconds of elapsed time. The months (and years) and days (and weeks)
+    #   of a duration move occurrences to another date at the same local time, even across a transition
+    #   in UTC offset (e.g. +-1.day+ always occurs on the previous day at the same time), whereas
+    #   its hours, minutes and seconds are elapsed time (e.g. +-24.hours+).
+    # @param duration_in_seconds [Integer] The duration of each derived occurrence.
+    # @!macro label
+    # @return [void]
+    # @raise [ArgumentError] if no series is labelled +base_label+, or the offset is not a duration.
+    # @example a reminder one day before each occurrence of a monthly board meeting
+    #   schedule.repeat_monthly_by_nth_weekday(:tuesday, 0, time_of_day: { hour: 9 }, duration_in_seconds: 3600, label: "Board meeting")
+    #   schedule.repeat_relative_to("Board meeting", offset: -1.day, duration_in_seconds: 600, label: "Board meeting reminder")
+    def repeat_relative_to(base_label, offset:, duration_in_seconds:, label: nil)
+      internal_schedule.repeat_relative_to(
+        base_label.to_s,
+        offset_parts_for(offset),
+        duration_in_seconds.to_i,
+        label
+      )
+    end
+
+    # Adds the events of an iCalendar (RFC 5545) document to the schedule. Each +VEVENT+'s
+    # +RRULE+ is added as by {#repeat_rrule}, anchored at its +DTSTART+; events without a rule,
+    # and any +RDATE+s, are added as single occurrences (as by {#add_occurrence}). +EXDATE+s, and instances cancelled or
+    # moved by a +RECURRENCE-ID+ override, remove only the event's own occurrences that start at exactly those times.
+    # Event durations come from +DTEND+ or +DURATION+, and labels from +SUMMARY+.
+    #
+    # +TZID+s are resolved as IANA time zone identifiers when possible, and otherwise through
+    # the +VTIMEZONE+ definitions embedded in the document (e.g. +"Eastern Standard Time"+ in
+    # calendars exported by Outlook). Recurring events are expanded in the time zone of their
+    # +DTSTART+, so that they keep its local time of day across its own DST changes, and are
+    # exported (see {#to_ical}) in that time zone too.
+    # @param ical [String] A +VCALENDAR+ document, e.g. the contents of an +.ics+ file.
+    # @return [void]
+    # @raise [Reprise::UnsupportedICalError] if the document is malformed, refers to an unknown
+    #   time zone (or, for a recurring event, to a +VTIMEZONE+ that no known time zone matches),
+    #   or contains a recurrence rule that cannot be represented (see {#repeat_rrule}).
+    def add_ical(ical)
+      internal_schedule.add_ical(ical)
+    end
+
+    # Adds a single occurrence to the schedule, outside of any recurring series; e.g. a one-off
+    # session that is rescheduled from its usual time. Like the occurrences of a series, it is held
+    # to the schedule's bookends, and removed if it overlaps with an exclusion.
+    # @param starts_at [Time, ActiveSupport::TimeWithZone] The time that the occurrence starts at.
+    # @param duration_in_seconds [Integer] The duration of the occurrence.
+    # @param label [String, nil] An optional label for the occurrence. See {Reprise::Core::Occurrence#label}.
+    # @return [void]
+    # @example
+    #   schedule.add_occurrence(starts_at: Time.new(2024, 7, 4, 19, 0, 0), duration_in_seconds: 2.hours, label: "Fireworks")
+    def add_occurrence(starts_at:, duration_in_seconds:, label: nil)
+      internal_schedule.add_occurrence(starts_at.to_i, duration_in_seconds.to_i, label)
+    end
+
     # Add a time interval between which no occurrences are valid.
     # Any occurrences that overlap with an exclusion are removed from the schedule's occurrences.
     # @param starts_at [Time] The time that the exclusion starts at
@@ -258,6 +772,19 @@ module Reprise
       )
     end
 
+    # Excludes the occurrences that overlap with any of the holidays of the given calendar, or with the
+    # days that they are observed on, in the schedule's time zone. Unlike {#add_exclusions}, the holidays
+    # are placed in each year that the schedule's occurrences are examined in, so they apply to open-ended
+    # schedules as well.
+    # @param holiday_calendar [Reprise::HolidayCalendar]
+    # @return [void]
+    # @raise [ArgumentError] if the month, day, +nth_day+ or +easter_offset+ of a holiday is out of range.
+    # @example
+    #   schedule.add_holiday_calendar(Reprise::HolidayCalendar.country(:us))
+    def add_holiday_calendar(holiday_calendar)
+      internal_schedule.add_holiday_calendar(holiday_calendar.to_internal_json)
+    end
+
     # Add time intervals between which no occurrences are valid.
     # Any occurrences that overlap with an exclusion are removed from the schedule's occurrences.
     # @param exclusions [Array<Array<Time,Time>>] An array of exclusion arrays, consisting of start
@@ -274,6 +801,38 @@ module Reprise
       )
     end
 
+    # Serializes the schedule into an iCalendar (RFC 5545) document that can be shared
+    # with calendar clients. Each recurring series becomes a +VEVENT+ with its own +RRULE+
+    # (or, if the series cannot be described by one, an explicit list of +RDATE+s), and each
+    # derived series (see {#repeat_relative_to}) a +VEVENT+ with a list of +RDATE+s, as do
+    # the one-off occurrences of each duration and label (see {#add_occurrence}); any
+    # occurrences removed by the schedule's exclusions are listed as +EXDATE+s.
+    # @return [String] A +VCALENDAR+ document.
+    # @example
+    #   File.write("schedule.ics", schedule.to_ical)
+    def to_ical
+      internal_schedule.to_ical
+    end
+
+    # Serializes the schedule, including all of its recurring series and exclusions, into a
+    # versioned JSON document that can be restored with {Reprise::Schedule.from_json}.
+    # @return [String]
+    # @example
+    #   redis.set("schedule:42", schedule.to_json)
+    def to_json(*_args)
+      internal_schedule.to_json
+    end
+
+    # Serializes the schedule into a compact, versioned binary snapshot that can be restored
+    # with {Reprise::Schedule.load}; prefer it over {#to_json} when storing schedules in bulk.
+    # Schedules can also be passed to +Marshal.dump+, which uses the same snapshot format.
+    # @return [String] A binary (+ASCII-8BIT+) string.
+    # @example
+    #   Reprise::Schedule.load(schedule.dump)
+    def dump
+      internal_schedule.dump
+    end
+
     # @!macro [new] include_overlapping
     #   @param include_overlapping [Boolean] when true, the query will also consider
     #     occurrences that partially overlap with the given interval, not just the occurrences
@@ -289,6 +848,9 @@ module Reprise
     end
 
     # This method efficiently queries your schedule for occurrences that fall within a given interval.
+    # Series without a +count+ skip straight to the start of the interval, so a query far from the
+    # start of the schedule costs no more than one near it; a series with a +count+ is still expanded
+    # from its start, because every occurrence before the interval counts towards the +count+.
     # @param starts_at [Time] The start of the interval to query
     # @param ends_at [Time] The end of the interval to query
     # @!macro include_overlapping
@@ -304,16 +866,51 @@ module Reprise
 
     private
 
-    attr_reader :starts_at, :ends_at, :time_zone, :default_time_of_day
+    attr_reader :starts_at, :ends_at, :time_zone, :default_time_of_day, :business_calendar
+
+    def times_of_day_for(time_of_day)
+      times_of_day = time_of_day.is_a?(Array) ? time_of_day : [time_of_day || starts_at]
+      raise ArgumentError, "At least one time of day is required" if times_of_day.empty?
+
+      times_of_day.map { |t| TimeOfDay.new(t).to_h }
+    end
+
+    def set_positions_for(set_positions)
+      set_positions = Array(set_positions)
+      raise ArgumentError, "Set positions must be non-zero" if set_positions.any?(&:zero?)
+
+      set_positions
+    end
+
+    # Splits an offset into its calendar months, calendar days and elapsed seconds.
+    def offset_parts_for(offset)
+      case offset
+      when ActiveSupport::Duration
+        parts = offset.parts
+        [
+          (parts.fetch(:years, 0) * 12) + parts.fetch(:months, 0),
+          (parts.fetch(:weeks, 0) * 7) + parts.fetch(:days, 0),
+          ((parts.fetch(:hours, 0) * 3600) + (parts.fetch(:minutes, 0) * 60) + parts.fetch(:seconds, 0)).to_i
+        ]
+      when Integer
+        [0, 0, offset]
+      else
+        raise ArgumentError, "Unsupported offset #{offset.inspect}"
+      end
+    end
 
     def internal_schedule
       return @_internal_schedule if defined?(@_internal_schedule)
 
       @_internal_schedule = ::Reprise::Core::Schedule.new(
         starts_at.to_i,
-        ends_at.to_i,
+        ends_at&.to_i,
         time_zone
-      )
+      ).tap do |schedule|
+        next unless business_calendar
+
+        schedule.set_business_calendar(business_calendar.weekend, business_calendar.holidays.map(&:iso8601))
+      end
     end
   end
 end
@@ -0,0 +1,29 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe Reprise::BusinessCalendar do
+  describe "#initialize" do
+    describe "errors" do
+      it "raises ArgumentError when a weekday is not supported" do
+        expect { described_class.new(weekend: %i[saturday caturday]) }
+          .to raise_error(ArgumentError, "Unsupported weekday :caturday")
+      end
+
+      it "raises ArgumentError when every day of the week is part of the weekend" do
+        expect { described_class.new(weekend: described_class::WEEKDAYS) }
+          .to raise_error(ArgumentError, "A business calendar must have at least one business day a week")
+      end
+    end
+  end
+
+  describe "#business_day?" do
+    subject(:business_calendar) { described_class.new(weekend: %i[friday saturday], holidays: [Date.new(2024, 12, 25)]) }
+
+    it "returns false for the days of the weekend and the holidays" do
+      expect(business_calendar.business_day?(Date.new(2024, 12, 27))).to eq(false)
+      expect(business_calendar.business_day?(Time.new(2024, 12, 25, 9, 0, 0, "-10:00"))).to eq(false)
+      expect(business_calendar.business_day?(Date.new(2024, 12, 29))).to eq(true)
+    end
+  end
+end
@@ -0,0 +1,65 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+require "tempfile"
+
+RSpec.describe Reprise::HolidayCalendar do
+  describe ".country" do
+    it "returns the calendar of the country's national holidays" do
+      holiday_calendar = described_class.country(:us)
+
+      expect(holiday_calendar.name).to eq("United States")
+      expect(holiday_calendar.holidays).to include(
+        { name: "Thanksgiving Day", month: 11, weekday: "thursday", nth_day: 3 }
+      )
+    end
+
+    it "supports a handful of countries" do
+      expect(described_class::COUNTRIES).to eq(%i[au ca de fr gb us])
+    end
+
+    it "raises ArgumentError when the country is not supported" do
+      expect { described_class.country(:atlantis) }.to raise_error(ArgumentError, "Unsupported country :atlantis")
+    end
+  end
+
+  describe ".load_file" do
+    it "returns the calendar defined by the YAML file" do
+      Tempfile.create(%w[holidays .yml]) do |file|
+        file.write(<<~YAML)
+          name: Company holidays
+          holidays:
+            - name: Founders' Day
+              month: 3
+              day: 15
+              observed: nearest_weekday
+        YAML
+        file.flush
+
+        holiday_calendar = described_class.load_file(file.path)
+
+        expect(holiday_calendar.name).to eq("Company holidays")
+        expect(holiday_calendar.holidays).to eq([{ name: "Founders' Day", month: 3, day: 15, observed: "nearest_weekday" }])
+      end
+    end
+  end
+
+  describe "#initialize" do
+    describe "errors" do
+      it "raises ArgumentError when a holiday has neither a date, an nth weekday, nor an Easter offset" do
+        expect { described_class.new(holidays: [{ name: "Someday", month: 3 }]) }
+          .to raise_error(ArgumentError, /\AUnsupported holiday/)
+      end
+
+      it "raises ArgumentError when a weekday is not supported" do
+        expect { described_class.new(holidays: [{ name: "Caturday", month: 3, weekday: :caturday, nth_day: 0 }]) }
+          .to raise_error(ArgumentError, "Unsupported weekday :caturday")
+      end
+
+      it "raises ArgumentError when an observed shift is not supported" do
+        expect { described_class.new(holidays: [{ name: "Founders' Day", month: 3, day: 15, observed: :sometime }]) }
+          .to raise_error(ArgumentError, "Unsupported observed shift :sometime")
+      end
+    end
+  end
+end
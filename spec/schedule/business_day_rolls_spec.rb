@@ -0,0 +1,154 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "business day rolls", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:, business_calendar:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { Time.new(2024, 12, 30, 0, 0, 0, "-10:00") }
+  let(:time_zone) { "Hawaii" }
+  let(:business_calendar) do
+    Reprise::BusinessCalendar.new(
+      holidays: [Date.new(2024, 7, 4), Date.new(2024, 9, 2), Date.new(2024, 11, 28), Date.new(2024, 12, 25)]
+    )
+  end
+
+  def occurrence_starts_ats
+    schedule.occurrences.map { |o| localized_occurrence_starts_at(o) }
+  end
+
+  context "when the roll convention is :following" do
+    it "moves the occurrences that fall on days that are not worked to the next business day" do
+      schedule.repeat_monthly_by_day(15, roll: :following, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+      expect(occurrence_starts_ats).to eq([
+        "Mon Jul 15 2024 09:00AM -1000",
+        "Thu Aug 15 2024 09:00AM -1000",
+        "Mon Sep 16 2024 09:00AM -1000",
+        "Tue Oct 15 2024 09:00AM -1000",
+        "Fri Nov 15 2024 09:00AM -1000",
+        "Mon Dec 16 2024 09:00AM -1000"
+      ])
+    end
+
+    it "moves the occurrences that fall on holidays" do
+      schedule.repeat_annually_by_month_day(12, 25, roll: :following, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+      expect(occurrence_starts_ats).to eq(["Thu Dec 26 2024 09:00AM -1000"])
+    end
+
+    it "merges the occurrences that are moved onto the same business day" do
+      schedule.repeat_monthly_by_day([6, 7], roll: :following, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes, count: 3)
+
+      # N.B. Sat Jul 6 and Sun Jul 7 are both moved to Mon Jul 8.
+      expect(occurrence_starts_ats).to eq([
+        "Mon Jul  8 2024 09:00AM -1000",
+        "Tue Aug  6 2024 09:00AM -1000",
+        "Wed Aug  7 2024 09:00AM -1000"
+      ])
+    end
+
+    it "includes the occurrences that are moved into the schedule from before its start" do
+      schedule.repeat_monthly_by_day(29, roll: :following, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes, count: 2)
+
+      # N.B. Sat Jun 29 precedes the start of the schedule.
+      expect(occurrence_starts_ats).to eq([
+        "Mon Jul  1 2024 09:00AM -1000",
+        "Mon Jul 29 2024 09:00AM -1000"
+      ])
+    end
+
+    context "when the holidays close the business for weeks" do
+      let(:starts_at) { Time.new(2024, 7, 8, 0, 0, 0, "-10:00") }
+      let(:business_calendar) do
+        Reprise::BusinessCalendar.new(holidays: (Date.new(2024, 6, 17)..Date.new(2024, 7, 5)).reject { |date| date.saturday? || date.sunday? })
+      end
+
+      it "includes the occurrences that are moved over the whole closure" do
+        schedule.repeat_monthly_by_day(15, roll: :following, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes, count: 2)
+
+        # N.B. Sat Jun 15 is moved past the closure, more than three weeks later.
+        expect(occurrence_starts_ats).to eq([
+          "Mon Jul  8 2024 09:00AM -1000",
+          "Mon Jul 15 2024 09:00AM -1000"
+        ])
+      end
+    end
+  end
+
+  context "when the roll convention is :modified_following" do
+    it "moves the occurrences to the previous business day instead of into the following month" do
+      schedule.repeat_monthly_by_day(-1, roll: :modified_following, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+      expect(occurrence_starts_ats).to eq([
+        "Wed Jul 31 2024 09:00AM -1000",
+        "Fri Aug 30 2024 09:00AM -1000",
+        "Mon Sep 30 2024 09:00AM -1000",
+        "Thu Oct 31 2024 09:00AM -1000",
+        "Fri Nov 29 2024 09:00AM -1000"
+      ])
+    end
+  end
+
+  context "when the roll convention is :preceding" do
+    it "moves the occurrences that fall on days that are not worked to the previous business day" do
+      schedule.repeat_monthly_by_day(1, roll: :preceding, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+      expect(occurrence_starts_ats).to eq([
+        "Mon Jul  1 2024 09:00AM -1000",
+        "Thu Aug  1 2024 09:00AM -1000",
+        "Fri Aug 30 2024 09:00AM -1000",
+        "Tue Oct  1 2024 09:00AM -1000",
+        "Fri Nov  1 2024 09:00AM -1000",
+        "Fri Nov 29 2024 09:00AM -1000"
+      ])
+    end
+  end
+
+  context "when the roll convention is :modified_preceding" do
+    it "moves the occurrences to the next business day instead of into the previous month" do
+      schedule.repeat_monthly_by_day(1, roll: :modified_preceding, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+      # N.B. Mon Sep 2 is a holiday.
+      expect(occurrence_starts_ats).to eq([
+        "Mon Jul  1 2024 09:00AM -1000",
+        "Thu Aug  1 2024 09:00AM -1000",
+        "Tue Sep  3 2024 09:00AM -1000",
+        "Tue Oct  1 2024 09:00AM -1000",
+        "Fri Nov  1 2024 09:00AM -1000",
+        "Mon Dec  2 2024 09:00AM -1000"
+      ])
+    end
+  end
+
+  it "returns the moved occurrences from queries of the days that they are moved to" do
+    schedule.repeat_monthly_by_day(1, roll: :preceding, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+    occurrences = schedule.occurrences_between(Time.new(2024, 8, 30, 0, 0, 0, "-10:00"), Time.new(2024, 8, 31, 0, 0, 0, "-10:00"))
+    expect(occurrences.map { |o| localized_occurrence_starts_at(o) }).to eq(["Fri Aug 30 2024 09:00AM -1000"])
+  end
+
+  it "serializes the moved occurrences as RDATEs" do
+    schedule.repeat_monthly_by_day(15, roll: :following, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes, count: 3)
+
+    content_lines = schedule.to_ical.split("\r\n")
+    expect(content_lines).to include("RDATE;TZID=Pacific/Honolulu:20240815T090000,20240916T090000")
+    expect(content_lines.grep(/\ARRULE/)).to be_empty
+  end
+
+  context "when the roll convention is not supported" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_monthly_by_day(15, roll: :sideways, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes) }
+        .to raise_error(ArgumentError, "Unsupported roll :sideways")
+    end
+
+    it "does not fall back to another convention for a misspelled one" do
+      expect { schedule.repeat_annually_by_day(200, roll: :modified_folowing, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes) }
+        .to raise_error(ArgumentError, "Unsupported roll :modified_folowing")
+      expect(schedule.occurrences).to be_empty
+    end
+  end
+end
@@ -0,0 +1,65 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "count anchoring", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 2.years }
+  let(:time_zone) { "Hawaii" }
+
+  before do
+    schedule.repeat_weekly(:monday, time_of_day: { hour: 9 }, duration_in_seconds: 1.hour, count: 10, label: "Weekly")
+  end
+
+  it "caps the series at the requested number of occurrences" do
+    expect(schedule.occurrences.size).to eq(10)
+    expect(localized_occurrence_starts_at(schedule.occurrences.last)).to eq("Mon Sep  2 2024 09:00AM -1000")
+  end
+
+  it "does not return occurrences from windows beyond the series' final occurrence" do
+    next_year = [starts_at + 1.year, starts_at + 2.years]
+
+    expect(schedule.occurrences_between(*next_year)).to be_empty
+    expect(schedule.occurs_between?(*next_year)).to eq(false)
+    expect(schedule.occurs_between?(*next_year, include_overlapping: true)).to eq(false)
+    expect(schedule.next_occurrence(after: starts_at + 3.months)).to be_nil
+  end
+
+  it "returns the remainder of the series from windows that begin part-way through it" do
+    occurrences_between = schedule.occurrences_between(starts_at + 6.weeks, starts_at + 1.year)
+
+    expect(occurrences_between.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Mon Aug 12 2024 09:00AM -1000",
+        "Mon Aug 19 2024 09:00AM -1000",
+        "Mon Aug 26 2024 09:00AM -1000",
+        "Mon Sep  2 2024 09:00AM -1000"
+      ])
+  end
+
+  it "counts the occurrences that remain after the interval has been applied" do
+    other_schedule = Reprise::Schedule.new(starts_at:, ends_at:, time_zone:)
+    other_schedule.repeat_daily(time_of_day: { hour: 9 }, duration_in_seconds: 1.hour, interval: 3, count: 4)
+
+    expect(other_schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Sun Jun 30 2024 09:00AM -1000",
+        "Wed Jul  3 2024 09:00AM -1000",
+        "Sat Jul  6 2024 09:00AM -1000",
+        "Tue Jul  9 2024 09:00AM -1000"
+      ])
+    expect(other_schedule.occurrences_between(starts_at + 5.days, starts_at + 1.year).size).to eq(2)
+  end
+
+  context "when the schedule is open-ended" do
+    let(:ends_at) { nil }
+
+    it "stops lazy enumeration after the series' final occurrence" do
+      expect(schedule.each_occurrence.to_a.size).to eq(10)
+    end
+  end
+end
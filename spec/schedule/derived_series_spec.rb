@@ -0,0 +1,112 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "derived series", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 2, 20, 0, 0, 0, "-08:00") }
+  let(:ends_at) { Time.new(2024, 4, 1, 0, 0, 0, "-07:00") }
+  let(:time_zone) { "America/Los_Angeles" }
+
+  def occurrences_labelled(label)
+    schedule.occurrences.select { |o| o.label == label }
+  end
+
+  before do
+    schedule.repeat_weekly(:sunday, time_of_day: { hour: 9 }, duration_in_seconds: 1.hour, label: "Service")
+  end
+
+  describe "#repeat_relative_to" do
+    it "moves each occurrence of the base series to the same local time on another day" do
+      schedule.repeat_relative_to("Service", offset: -1.day, duration_in_seconds: 10.minutes, label: "Rehearsal")
+
+      expect(occurrences_labelled("Rehearsal").map { |o| localized_occurrence_start_and_end_time(o) }).to eq([
+        "Sat Feb 24 2024 09:00AM -0800 - Sat Feb 24 2024 09:10AM -0800",
+        "Sat Mar  2 2024 09:00AM -0800 - Sat Mar  2 2024 09:10AM -0800",
+        "Sat Mar  9 2024 09:00AM -0800 - Sat Mar  9 2024 09:10AM -0800",
+        "Sat Mar 16 2024 09:00AM -0700 - Sat Mar 16 2024 09:10AM -0700",
+        "Sat Mar 23 2024 09:00AM -0700 - Sat Mar 23 2024 09:10AM -0700",
+        "Sat Mar 30 2024 09:00AM -0700 - Sat Mar 30 2024 09:10AM -0700"
+      ])
+    end
+
+    it "moves each occurrence of the base series by elapsed time when the offset is given in hours or seconds" do
+      schedule.repeat_relative_to("Service", offset: -24.hours, duration_in_seconds: 10.minutes, label: "Reminder")
+      schedule.repeat_relative_to("Service", offset: 90.minutes.to_i, duration_in_seconds: 10.minutes, label: "Follow-up")
+
+      expect(occurrences_labelled("Reminder").map { |o| localized_occurrence_starts_at(o) }).to eq([
+        "Sat Feb 24 2024 09:00AM -0800",
+        "Sat Mar  2 2024 09:00AM -0800",
+        "Sat Mar  9 2024 08:00AM -0800", # N.B. 24 hours before the first service of daylight saving time
+        "Sat Mar 16 2024 09:00AM -0700",
+        "Sat Mar 23 2024 09:00AM -0700",
+        "Sat Mar 30 2024 09:00AM -0700"
+      ])
+      expect(occurrences_labelled("Follow-up").map { |o| localized_occurrence_starts_at(o) }.first)
+        .to eq("Sun Feb 25 2024 10:30AM -0800")
+    end
+
+    it "moves each occurrence of the base series to the same day of another month" do
+      schedule.repeat_relative_to("Service", offset: 1.month, duration_in_seconds: 10.minutes, label: "Anniversary")
+
+      expect(occurrences_labelled("Anniversary").map { |o| localized_occurrence_starts_at(o) }).to eq([
+        "Mon Mar 25 2024 09:00AM -0700"
+      ])
+    end
+
+    it "holds the derived occurrences to the schedule's bookends" do
+      schedule.repeat_relative_to("Service", offset: -30.days, duration_in_seconds: 10.minutes, label: "Early notice")
+
+      expect(occurrences_labelled("Early notice").map { |o| localized_occurrence_starts_at(o) }).to eq([
+        "Fri Feb 23 2024 09:00AM -0800",
+        "Fri Mar  1 2024 09:00AM -0800"
+      ])
+    end
+
+    it "does not move the excluded occurrences of the base series, and excludes derived occurrences in turn" do
+      schedule.repeat_relative_to("Service", offset: -1.day, duration_in_seconds: 10.minutes, label: "Rehearsal")
+      schedule.add_exclusion(starts_at: Time.new(2024, 3, 17, 0, 0, 0, "-07:00"), ends_at: Time.new(2024, 3, 18, 0, 0, 0, "-07:00"))
+      schedule.add_exclusion(starts_at: Time.new(2024, 3, 2, 0, 0, 0, "-08:00"), ends_at: Time.new(2024, 3, 3, 0, 0, 0, "-08:00"))
+
+      expect(occurrences_labelled("Rehearsal").map { |o| localized_occurrence_starts_at(o) }).to eq([
+        "Sat Feb 24 2024 09:00AM -0800",
+        "Sat Mar  9 2024 09:00AM -0800",
+        "Sat Mar 23 2024 09:00AM -0700",
+        "Sat Mar 30 2024 09:00AM -0700"
+      ])
+      expect(occurrences_labelled("Service").size).to eq(5)
+    end
+
+    it "returns the derived occurrences among those within a given interval" do
+      schedule.repeat_relative_to("Service", offset: -1.day, duration_in_seconds: 10.minutes, label: "Rehearsal")
+
+      occurrences = schedule.occurrences_between(Time.new(2024, 3, 9, 0, 0, 0, "-08:00"), Time.new(2024, 3, 11, 0, 0, 0, "-07:00"))
+
+      expect(occurrences.map { |o| [o.label, localized_occurrence_starts_at(o)] }).to eq([
+        ["Rehearsal", "Sat Mar  9 2024 09:00AM -0800"],
+        ["Service", "Sun Mar 10 2024 09:00AM -0700"]
+      ])
+    end
+
+    it "lists the derived occurrences as RDATEs" do
+      schedule.repeat_relative_to("Service", offset: -1.day, duration_in_seconds: 10.minutes, label: "Rehearsal")
+
+      content_lines = schedule.to_ical.split("\r\n")
+      expect(content_lines).to include("SUMMARY:Rehearsal")
+      expect(content_lines).to include("DTSTART;TZID=America/Los_Angeles:20240224T090000")
+    end
+
+    it "raises ArgumentError when no series has the base label" do
+      expect { schedule.repeat_relative_to("Sermon", offset: -1.day, duration_in_seconds: 10.minutes) }
+        .to raise_error(ArgumentError, 'No series is labelled "Sermon"')
+    end
+
+    it "raises ArgumentError when the offset is not a duration" do
+      expect { schedule.repeat_relative_to("Service", offset: "1 day", duration_in_seconds: 10.minutes) }
+        .to raise_error(ArgumentError, 'Unsupported offset "1 day"')
+    end
+  end
+end
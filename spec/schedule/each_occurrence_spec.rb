@@ -0,0 +1,59 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#each_occurrence", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 10.years }
+  let(:time_zone) { "Hawaii" }
+
+  before do
+    schedule.repeat_minutely(duration_in_seconds: 30, label: "Minutely")
+    schedule.repeat_hourly(time_of_day: { minute: 30 }, duration_in_seconds: 60, label: "Hourly")
+    schedule.add_exclusion(starts_at: starts_at + 1.minute, ends_at: starts_at + 2.minutes)
+  end
+
+  it "returns a lazy enumerator of the schedule's occurrences in ascending order" do
+    occurrences = schedule.each_occurrence
+
+    expect(occurrences).to be_a(Enumerator::Lazy)
+    expect(occurrences.first(4).map { |o| [localized_occurrence_starts_at(o), o.label] })
+      .to eq([
+        ["Sun Jun 30 2024 12:00AM -1000", "Minutely"],
+        ["Sun Jun 30 2024 12:02AM -1000", "Minutely"],
+        ["Sun Jun 30 2024 12:03AM -1000", "Minutely"],
+        ["Sun Jun 30 2024 12:04AM -1000", "Minutely"]
+      ])
+  end
+
+  it "merges the occurrences of every series" do
+    expect(schedule.each_occurrence.select { |o| o.label == "Hourly" }.first.starts_at)
+      .to eq(starts_at + 30.minutes)
+    expect(schedule.each_occurrence.take_while { |o| o.starts_at <= starts_at + 30.minutes }.map(&:label).last(2))
+      .to eq(%w[Minutely Hourly])
+  end
+
+  it "yields each occurrence to a given block" do
+    yielded_occurrences = []
+
+    schedule.each_occurrence do |occurrence|
+      yielded_occurrences << occurrence
+      break if yielded_occurrences.size == 3
+    end
+
+    expect(yielded_occurrences.map(&:starts_at)).to eq([starts_at, starts_at + 2.minutes, starts_at + 3.minutes])
+  end
+
+  context "when the schedule is short enough to expand in full" do
+    let(:ends_at) { starts_at + 1.day }
+
+    it "yields the same occurrences as #occurrences" do
+      expect(schedule.each_occurrence.map { |o| [o.starts_at, o.label] }.to_a)
+        .to eq(schedule.occurrences.map { |o| [o.starts_at, o.label] })
+    end
+  end
+end
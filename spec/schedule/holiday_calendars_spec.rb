@@ -0,0 +1,152 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "holiday calendars", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:time_zone) { "Hawaii" }
+
+  def excluded_dates(from, to)
+    occurrence_dates = schedule.occurrences.map { |o| o.starts_at.in_time_zone(time_zone).to_date }
+
+    (from..to).to_a - occurrence_dates
+  end
+
+  describe "#add_holiday_calendar" do
+    let(:starts_at) { Time.new(2021, 1, 1, 0, 0, 0, "-10:00") }
+    let(:ends_at) { Time.new(2023, 1, 1, 0, 0, 0, "-10:00") }
+
+    it "excludes the occurrences on the holidays, and on the days that they are observed on" do
+      schedule.repeat_daily(time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+      schedule.add_holiday_calendar(Reprise::HolidayCalendar.country(:us))
+
+      expect(excluded_dates(Date.new(2021, 1, 1), Date.new(2022, 12, 31))).to eq([
+        Date.new(2021, 1, 1),
+        Date.new(2021, 1, 18),
+        Date.new(2021, 2, 15),
+        Date.new(2021, 5, 31),
+        Date.new(2021, 6, 18), # Juneteenth (a saturday), observed
+        Date.new(2021, 6, 19),
+        Date.new(2021, 7, 4),
+        Date.new(2021, 7, 5), # Independence Day (a sunday), observed
+        Date.new(2021, 9, 6),
+        Date.new(2021, 10, 11),
+        Date.new(2021, 11, 11),
+        Date.new(2021, 11, 25),
+        Date.new(2021, 12, 24), # Christmas Day (a saturday), observed
+        Date.new(2021, 12, 25),
+        Date.new(2021, 12, 31), # New Year's Day of 2022 (a saturday), observed
+        Date.new(2022, 1, 1),
+        Date.new(2022, 1, 17),
+        Date.new(2022, 2, 21),
+        Date.new(2022, 5, 30),
+        Date.new(2022, 6, 19),
+        Date.new(2022, 6, 20), # Juneteenth (a sunday), observed
+        Date.new(2022, 7, 4),
+        Date.new(2022, 9, 5),
+        Date.new(2022, 10, 10),
+        Date.new(2022, 11, 11),
+        Date.new(2022, 11, 24),
+        Date.new(2022, 12, 25),
+        Date.new(2022, 12, 26) # Christmas Day (a sunday), observed
+      ])
+    end
+
+    it "observes consecutive holidays that fall on a weekend on consecutive weekdays" do
+      schedule.repeat_daily(time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+      schedule.add_holiday_calendar(Reprise::HolidayCalendar.country(:gb))
+
+      expect(excluded_dates(Date.new(2021, 12, 20), Date.new(2022, 1, 5))).to eq([
+        Date.new(2021, 12, 25),
+        Date.new(2021, 12, 26),
+        Date.new(2021, 12, 27),
+        Date.new(2021, 12, 28),
+        Date.new(2022, 1, 1),
+        Date.new(2022, 1, 3)
+      ])
+    end
+
+    it "places holidays relative to Easter" do
+      schedule.repeat_daily(time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+      schedule.add_holiday_calendar(Reprise::HolidayCalendar.country(:de))
+
+      expect(excluded_dates(Date.new(2022, 4, 1), Date.new(2022, 6, 30))).to eq([
+        Date.new(2022, 4, 15), # Good Friday
+        Date.new(2022, 4, 18), # Easter Monday
+        Date.new(2022, 5, 1),
+        Date.new(2022, 5, 26), # Ascension Day
+        Date.new(2022, 6, 6) # Whit Monday
+      ])
+    end
+
+    it "excludes the occurrences of the holidays of a custom calendar, in the years that they are held" do
+      schedule.repeat_daily(time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+      schedule.add_holiday_calendar(
+        Reprise::HolidayCalendar.new(
+          holidays: [
+            { name: "Founders' Day", month: 3, day: 15, observed: :nearest_weekday, first_year: 2022 },
+            { name: "Day after Thanksgiving", month: 11, weekday: :friday, nth_day: 3, last_year: 2021 }
+          ]
+        )
+      )
+
+      expect(excluded_dates(Date.new(2021, 1, 1), Date.new(2022, 12, 31))).to eq([
+        Date.new(2021, 11, 26),
+        Date.new(2022, 3, 15)
+      ])
+    end
+
+    it "excludes the occurrences that overlap with a holiday in the schedule's time zone" do
+      schedule.repeat_daily(time_of_day: { hour: 23 }, duration_in_seconds: 2.hours, label: "Overnight")
+      schedule.repeat_daily(time_of_day: { hour: 22 }, duration_in_seconds: 2.hours, label: "Until midnight")
+      schedule.add_holiday_calendar(Reprise::HolidayCalendar.country(:us))
+
+      occurrences = schedule.occurrences_between(Time.new(2022, 7, 3, 0, 0, 0, "-10:00"), Time.new(2022, 7, 6, 12, 0, 0, "-10:00"))
+
+      # N.B. Occurrences that end at midnight on the eve of a holiday do not overlap with it.
+      expect(occurrences.map { |o| [o.label, localized_occurrence_starts_at(o)] }).to eq([
+        ["Until midnight", "Sun Jul  3 2022 10:00PM -1000"],
+        ["Until midnight", "Tue Jul  5 2022 10:00PM -1000"],
+        ["Overnight", "Tue Jul  5 2022 11:00PM -1000"]
+      ])
+    end
+
+    it "lists the occurrences on the holidays as EXDATEs" do
+      schedule.repeat_weekly(:monday, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes, count: 4)
+      schedule.add_holiday_calendar(Reprise::HolidayCalendar.country(:us))
+
+      content_lines = schedule.to_ical.split("\r\n")
+      expect(content_lines).to include("EXDATE;TZID=Pacific/Honolulu:20210118T090000")
+    end
+
+    it "raises ArgumentError when a holiday is out of range" do
+      holiday_calendar = Reprise::HolidayCalendar.new(holidays: [{ name: "Nonexistent Day", month: 2, day: 30 }])
+
+      expect { schedule.add_holiday_calendar(holiday_calendar) }
+        .to raise_error(ArgumentError, "The day 30 is out of range for the month 2")
+    end
+  end
+
+  context "when the schedule is open-ended" do
+    let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+    let(:ends_at) { nil }
+
+    it "excludes the holidays of every year" do
+      schedule.repeat_annually_by_month_day(12, 25, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+      schedule.repeat_annually_by_month_day(12, 27, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+      schedule.add_holiday_calendar(Reprise::HolidayCalendar.country(:gb))
+
+      expect(schedule.each_occurrence.first(4).map { |o| localized_occurrence_starts_at(o) }).to eq([
+        "Fri Dec 27 2024 09:00AM -1000",
+        "Sat Dec 27 2025 09:00AM -1000",
+        "Sun Dec 27 2026 09:00AM -1000",
+        "Wed Dec 27 2028 09:00AM -1000"
+      ])
+      expect(localized_occurrence_starts_at(schedule.next_occurrence(after: Time.new(2027, 1, 1, 0, 0, 0, "-10:00"))))
+        .to eq("Wed Dec 27 2028 09:00AM -1000")
+    end
+  end
+end
@@ -0,0 +1,145 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#to_ical", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 3.months }
+  let(:time_zone) { "Hawaii" }
+  let(:content_lines) { schedule.to_ical.split("\r\n") }
+
+  it "returns a VCALENDAR document with the schedule's time zone" do
+    expect(content_lines.first).to eq("BEGIN:VCALENDAR")
+    expect(content_lines.last).to eq("END:VCALENDAR")
+    expect(content_lines).to include("TZID:Pacific/Honolulu", "TZOFFSETTO:-1000")
+  end
+
+  it "serializes each recurring series as a VEVENT with an RRULE" do
+    schedule.repeat_weekly(:tuesday, time_of_day: { hour: 9 }, duration_in_seconds: 90.minutes, label: "Standup")
+    schedule.repeat_monthly_by_nth_weekday(:friday, -1, time_of_day: { hour: 17 }, duration_in_seconds: 1.hour, count: 2)
+
+    expect(content_lines.count("BEGIN:VEVENT")).to eq(2)
+    expect(content_lines).to include(
+      "DTSTART;TZID=Pacific/Honolulu:20240702T090000",
+      "DURATION:PT1H30M",
+      "RRULE:FREQ=WEEKLY;BYDAY=TU;UNTIL=20240930T100000Z",
+      "SUMMARY:Standup",
+      "DTSTART;TZID=Pacific/Honolulu:20240726T170000",
+      "RRULE:FREQ=MONTHLY;BYDAY=-1FR;COUNT=2"
+    )
+  end
+
+  it "serializes each nth weekday of a monthly series" do
+    schedule.repeat_monthly_by_nth_weekday([[:friday, -1], [:monday, 0]], time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+    expect(content_lines).to include("RRULE:FREQ=MONTHLY;BYDAY=-1FR,1MO;UNTIL=20240930T100000Z")
+  end
+
+  context "when the schedule spans several years" do
+    let(:ends_at) { starts_at + 5.years }
+
+    it "serializes the month of an annual series" do
+      schedule.repeat_annually_by_month_day(2, 29, missing_day: :clamp, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+      schedule.repeat_annually_by_nth_weekday(11, :thursday, 3, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+      expect(content_lines).to include(
+        "DTSTART;TZID=Pacific/Honolulu:20250228T090000",
+        "RRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29;RSCALE=GREGORIAN;SKIP=BACKWARD;UNTIL=20290630T100000Z",
+        "DTSTART;TZID=Pacific/Honolulu:20241128T090000",
+        "RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=4TH;UNTIL=20290630T100000Z"
+      )
+    end
+  end
+
+  it "serializes the week numbers and weekdays of an ISO week series" do
+    schedule.repeat_annually_by_iso_week([28, 30], %i[monday friday], time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+    expect(content_lines).to include("RRULE:FREQ=YEARLY;BYWEEKNO=28,30;BYDAY=MO,FR;UNTIL=20240930T100000Z")
+  end
+
+  it "serializes the weekdays and week start of a weekly series" do
+    schedule.repeat_weekly(%i[sunday tuesday], week_start: :sunday, interval: 2, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+    expect(content_lines).to include("RRULE:FREQ=WEEKLY;BYDAY=TU,SU;WKST=SU;INTERVAL=2;UNTIL=20240930T100000Z")
+  end
+
+  it "serializes the days and missing day policy of a monthly series" do
+    schedule.repeat_monthly_by_day([15, -1], missing_day: :roll, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+
+    expect(content_lines)
+      .to include("RRULE:FREQ=MONTHLY;BYMONTHDAY=15,-1;RSCALE=GREGORIAN;SKIP=FORWARD;UNTIL=20240930T100000Z")
+  end
+
+  it "serializes the set positions of a series" do
+    schedule.repeat_monthly_by_weekday(
+      %i[monday tuesday wednesday thursday friday], set_positions: -1, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes
+    )
+
+    expect(content_lines).to include("RRULE:FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;UNTIL=20240930T100000Z")
+  end
+
+  it "serializes the step of a fixed-step series in its coarsest frequency" do
+    schedule.repeat_minutely(every: 90.minutes, time_of_day: { hour: 9 }, duration_in_seconds: 5.minutes, count: 3)
+    schedule.repeat_minutely(every: 2.hours, time_of_day: { hour: 9 }, duration_in_seconds: 5.minutes, count: 3)
+    schedule.repeat_secondly(every: 15, interval: 2, time_of_day: { hour: 9 }, duration_in_seconds: 5, count: 3)
+
+    expect(content_lines).to include(
+      "RRULE:FREQ=MINUTELY;INTERVAL=90;COUNT=3",
+      "RRULE:FREQ=HOURLY;INTERVAL=2;COUNT=3",
+      "RRULE:FREQ=SECONDLY;INTERVAL=30;COUNT=3"
+    )
+  end
+
+  it "serializes the daily window of a fixed-step series" do
+    schedule.repeat_minutely(
+      every: 15.minutes, window_start: { hour: 9 }, window_end: { hour: 12 }, weekdays: %i[monday friday],
+      time_of_day: { hour: 9 }, duration_in_seconds: 5.minutes
+    )
+
+    expect(content_lines)
+      .to include("RRULE:FREQ=MINUTELY;BYDAY=MO,FR;BYHOUR=9,10,11;INTERVAL=15;UNTIL=20240930T100000Z")
+  end
+
+  context "when a series cannot be described by an RRULE" do
+    subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:, business_calendar:) }
+
+    let(:business_calendar) { Reprise::BusinessCalendar.new(holidays: [Date.new(2024, 7, 4)]) }
+
+    it "serializes its occurrences as RDATEs" do
+      schedule.repeat_business_daily(
+        time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes, starts_at: Time.new(2024, 7, 3, 0, 0, 0, "-10:00"), count: 3
+      )
+
+      expect(content_lines).to include(
+        "DTSTART;TZID=Pacific/Honolulu:20240703T090000",
+        "RDATE;TZID=Pacific/Honolulu:20240705T090000,20240708T090000"
+      )
+      expect(content_lines.grep(/\ARRULE/)).to be_empty
+    end
+  end
+
+  it "serializes excluded occurrences as EXDATEs" do
+    schedule.repeat_weekly(:tuesday, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes)
+    schedule.add_exclusion(starts_at: Time.new(2024, 7, 9, 0, 0, 0, "-10:00"), ends_at: Time.new(2024, 7, 10, 0, 0, 0, "-10:00"))
+
+    expect(content_lines).to include("EXDATE;TZID=Pacific/Honolulu:20240709T090000")
+  end
+
+  context "when a series repeats at an interval" do
+    let(:ends_at) { starts_at + 6.months }
+
+    it "carries the interval over to the RRULE" do
+      schedule.repeat_monthly_by_day(31, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes, interval: 2)
+
+      expect(content_lines).to include(
+        "DTSTART;TZID=Pacific/Honolulu:20240731T090000",
+        "RRULE:FREQ=MONTHLY;BYMONTHDAY=31;INTERVAL=2;UNTIL=20241230T100000Z"
+      )
+      expect(content_lines.grep(/\ARDATE/)).to be_empty
+    end
+  end
+end
@@ -0,0 +1,297 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#add_ical", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 3.months }
+  let(:time_zone) { "Hawaii" }
+  let(:vtimezone) do
+    <<~ICS
+      BEGIN:VTIMEZONE
+      TZID:Eastern Standard Time
+      BEGIN:STANDARD
+      DTSTART:16010101T020000
+      TZOFFSETFROM:-0400
+      TZOFFSETTO:-0500
+      RRULE:FREQ=YEARLY;BYDAY=1SU;BYMONTH=11
+      END:STANDARD
+      BEGIN:DAYLIGHT
+      DTSTART:16010101T020000
+      TZOFFSETFROM:-0500
+      TZOFFSETTO:-0400
+      RRULE:FREQ=YEARLY;BYDAY=2SU;BYMONTH=3
+      END:DAYLIGHT
+      END:VTIMEZONE
+    ICS
+  end
+
+  it "adds recurring events, resolving their time zone from an embedded VTIMEZONE" do
+    schedule.add_ical(<<~ICS)
+      BEGIN:VCALENDAR
+      VERSION:2.0
+      #{vtimezone.chomp}
+      BEGIN:VEVENT
+      UID:standup@example.com
+      DTSTART;TZID=Eastern Standard Time:20240701T120000
+      DTEND;TZID=Eastern Standard Time:20240701T123000
+      RRULE:FREQ=WEEKLY;COUNT=4
+      EXDATE;TZID=Eastern Standard Time:20240715T120000
+      SUMMARY:Standup
+      END:VEVENT
+      END:VCALENDAR
+    ICS
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Mon Jul  1 2024 06:00AM -1000",
+        "Mon Jul  8 2024 06:00AM -1000",
+        "Mon Jul 22 2024 06:00AM -1000"
+      )
+    expect(schedule.occurrences.map { |o| o.ends_at - o.starts_at }.uniq).to eq([30.minutes])
+    expect(schedule.occurrences.map(&:label).uniq).to eq(["Standup"])
+  end
+
+  it "removes an EXDATE from an event without a DTEND" do
+    schedule.add_ical(<<~ICS)
+      BEGIN:VCALENDAR
+      BEGIN:VEVENT
+      DTSTART;TZID=America/New_York:20240701T120000
+      RRULE:FREQ=DAILY;COUNT=3
+      EXDATE;TZID=America/New_York:20240702T120000
+      END:VEVENT
+      END:VCALENDAR
+    ICS
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Mon Jul  1 2024 06:00AM -1000",
+        "Wed Jul  3 2024 06:00AM -1000"
+      )
+  end
+
+  it "only removes an EXDATE from the event that it belongs to" do
+    schedule.add_ical(<<~ICS)
+      BEGIN:VCALENDAR
+      BEGIN:VEVENT
+      UID:standup@example.com
+      DTSTART;TZID=America/New_York:20240701T120000
+      DURATION:PT15M
+      RRULE:FREQ=DAILY;COUNT=2
+      EXDATE;TZID=America/New_York:20240702T120000
+      END:VEVENT
+      BEGIN:VEVENT
+      UID:lunch@example.com
+      DTSTART;TZID=America/New_York:20240702T120000
+      DURATION:PT1H
+      END:VEVENT
+      END:VCALENDAR
+    ICS
+
+    expect(schedule.occurrences.map { |o| [localized_occurrence_starts_at(o), o.ends_at - o.starts_at] })
+      .to contain_exactly(
+        ["Mon Jul  1 2024 06:00AM -1000", 15.minutes],
+        ["Tue Jul  2 2024 06:00AM -1000", 1.hour]
+      )
+  end
+
+  it "replaces instances that are moved by a RECURRENCE-ID override" do
+    schedule.add_ical(<<~ICS)
+      BEGIN:VCALENDAR
+      BEGIN:VEVENT
+      UID:standup@example.com
+      DTSTART;TZID=America/New_York:20240701T120000
+      DURATION:PT15M
+      RRULE:FREQ=WEEKLY;COUNT=2
+      END:VEVENT
+      BEGIN:VEVENT
+      UID:standup@example.com
+      RECURRENCE-ID;TZID=America/New_York:20240708T120000
+      DTSTART;TZID=America/New_York:20240708T140000
+      DURATION:PT15M
+      END:VEVENT
+      END:VCALENDAR
+    ICS
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Mon Jul  1 2024 06:00AM -1000",
+        "Mon Jul  8 2024 08:00AM -1000"
+      )
+  end
+
+  it "adds single and all-day events, including their RDATEs" do
+    schedule.add_ical(<<~ICS)
+      BEGIN:VCALENDAR
+      BEGIN:VEVENT
+      DTSTART:20240704T190000Z
+      DTEND:20240704T200000Z
+      SUMMARY:Fireworks\\, rooftop
+      END:VEVENT
+      BEGIN:VEVENT
+      DTSTART;VALUE=DATE:20240801
+      RDATE;VALUE=DATE:20240901
+      SUMMARY:Rent
+      END:VEVENT
+      END:VCALENDAR
+    ICS
+
+    expect(schedule.occurrences.map { |o| [localized_occurrence_starts_at(o), o.label] })
+      .to contain_exactly(
+        ["Thu Jul  4 2024 09:00AM -1000", "Fireworks, rooftop"],
+        ["Thu Aug  1 2024 12:00AM -1000", "Rent"],
+        ["Sun Sep  1 2024 12:00AM -1000", "Rent"]
+      )
+  end
+
+  it "gives RDATE periods their own durations" do
+    schedule.add_ical(<<~ICS)
+      BEGIN:VCALENDAR
+      BEGIN:VEVENT
+      DTSTART;TZID=America/New_York:20240701T120000
+      DURATION:PT15M
+      RDATE;VALUE=PERIOD;TZID=America/New_York:20240702T120000/20240702T140000,20240703T120000/PT45M
+      END:VEVENT
+      END:VCALENDAR
+    ICS
+
+    expect(schedule.occurrences.map { |o| [localized_occurrence_starts_at(o), o.ends_at - o.starts_at] })
+      .to contain_exactly(
+        ["Mon Jul  1 2024 06:00AM -1000", 15.minutes],
+        ["Tue Jul  2 2024 06:00AM -1000", 2.hours],
+        ["Wed Jul  3 2024 06:00AM -1000", 45.minutes]
+      )
+  end
+
+  describe ".from_ical" do
+    it "returns a new schedule with the events of the document" do
+      schedule = Reprise::Schedule.from_ical(<<~ICS, starts_at:, ends_at:, time_zone:)
+        BEGIN:VCALENDAR
+        BEGIN:VEVENT
+        DTSTART:20240704T190000Z
+        END:VEVENT
+        END:VCALENDAR
+      ICS
+
+      expect(schedule.occurrences.size).to eq(1)
+    end
+  end
+
+  context "when a recurring event's time zone differs from the schedule's" do
+    let(:ends_at) { starts_at + 6.months }
+
+    it "keeps the event's local time across its DST changes" do
+      schedule.add_ical(<<~ICS)
+        BEGIN:VCALENDAR
+        BEGIN:VEVENT
+        DTSTART;TZID=America/New_York:20241025T120000
+        RRULE:FREQ=WEEKLY;COUNT=3
+        END:VEVENT
+        END:VCALENDAR
+      ICS
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Fri Oct 25 2024 06:00AM -1000",
+          "Fri Nov  1 2024 06:00AM -1000",
+          "Fri Nov  8 2024 07:00AM -1000"
+        ])
+    end
+
+    it "keeps the event's local time across the DST changes of its VTIMEZONE" do
+      schedule.add_ical(<<~ICS)
+        BEGIN:VCALENDAR
+        #{vtimezone.chomp}
+        BEGIN:VEVENT
+        DTSTART;TZID=Eastern Standard Time:20241025T120000
+        RRULE:FREQ=WEEKLY;COUNT=3
+        END:VEVENT
+        END:VCALENDAR
+      ICS
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Fri Oct 25 2024 06:00AM -1000",
+          "Fri Nov  1 2024 06:00AM -1000",
+          "Fri Nov  8 2024 07:00AM -1000"
+        ])
+    end
+
+    it "occurs on the event's local days" do
+      schedule.add_ical(<<~ICS)
+        BEGIN:VCALENDAR
+        BEGIN:VEVENT
+        DTSTART;TZID=Asia/Tokyo:20240701T080000
+        RRULE:FREQ=WEEKLY;BYDAY=MO;COUNT=2
+        END:VEVENT
+        END:VCALENDAR
+      ICS
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Sun Jun 30 2024 01:00PM -1000",
+          "Sun Jul  7 2024 01:00PM -1000"
+        ])
+    end
+  end
+
+  context "when an event refers to an unknown time zone" do
+    it "raises a Reprise::UnsupportedICalError" do
+      ical = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART;TZID=Atlantis:20240701T120000\nEND:VEVENT\nEND:VCALENDAR\n"
+
+      expect { schedule.add_ical(ical) }
+        .to raise_error(Reprise::UnsupportedICalError, /TZID "Atlantis"/)
+    end
+  end
+
+  context "when an event's recurrence rule is yearly, without a day" do
+    let(:ends_at) { starts_at + 3.years }
+
+    it "repeats on the month and day of DTSTART, or on its day in each month that the rule lists" do
+      schedule.add_ical(<<~ICS)
+        BEGIN:VCALENDAR
+        BEGIN:VEVENT
+        DTSTART:20240701T190000Z
+        RRULE:FREQ=YEARLY
+        END:VEVENT
+        BEGIN:VEVENT
+        DTSTART:20240701T200000Z
+        RRULE:FREQ=YEARLY;BYMONTH=3
+        END:VEVENT
+        END:VCALENDAR
+      ICS
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Mon Jul  1 2024 09:00AM -1000",
+          "Sat Mar  1 2025 10:00AM -1000",
+          "Tue Jul  1 2025 09:00AM -1000",
+          "Sun Mar  1 2026 10:00AM -1000",
+          "Wed Jul  1 2026 09:00AM -1000",
+          "Mon Mar  1 2027 10:00AM -1000"
+        ])
+    end
+  end
+
+  context "when an event's duration is out of range" do
+    it "raises a Reprise::UnsupportedICalError" do
+      ical = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:20240701T120000Z\nDURATION:P99999999999999W\nEND:VEVENT\nEND:VCALENDAR\n"
+
+      expect { schedule.add_ical(ical) }
+        .to raise_error(Reprise::UnsupportedICalError, /DURATION "P99999999999999W" is out of range/)
+    end
+  end
+
+  context "when an event's recurrence rule cannot be represented" do
+    it "raises a Reprise::UnsupportedICalError" do
+      ical = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:20240701T120000Z\nRRULE:FREQ=YEARLY;BYMONTHDAY=15\nEND:VEVENT\nEND:VCALENDAR\n"
+
+      expect { schedule.add_ical(ical) }
+        .to raise_error(Reprise::UnsupportedICalError, /BYMONTHDAY is not supported/)
+    end
+  end
+end
@@ -0,0 +1,108 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "interval anchoring", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 2.years }
+  let(:time_zone) { "Hawaii" }
+
+  # Windows of various lengths, none of which begin on the same phase as the schedule.
+  let(:windows) do
+    [
+      [starts_at + 1.day, starts_at + 9.days],
+      [starts_at + 8.days + 7.hours, starts_at + 40.days],
+      [starts_at + 3.months + 5.days, starts_at + 7.months],
+      [starts_at + 13.months + 17.days, starts_at + 23.months]
+    ]
+  end
+
+  def occurrence_summaries(occurrences)
+    occurrences.map { |o| [localized_occurrence_starts_at(o), o.label] }
+  end
+
+  def expect_windows_to_agree_with_full_expansion
+    all_occurrences = schedule.occurrences
+
+    windows.each do |window_starts_at, window_ends_at|
+      expected_occurrences = all_occurrences.select do |o|
+        o.starts_at >= window_starts_at && o.ends_at <= window_ends_at
+      end
+
+      expect(occurrence_summaries(schedule.occurrences_between(window_starts_at, window_ends_at)))
+        .to eq(occurrence_summaries(expected_occurrences))
+      expect(schedule.occurs_between?(window_starts_at, window_ends_at)).to eq(expected_occurrences.any?)
+    end
+  end
+
+  it "selects every other week counted from the series' first occurrence" do
+    schedule.repeat_weekly(:tuesday, time_of_day: { hour: 9 }, duration_in_seconds: 1.hour, interval: 2)
+
+    # The first Tuesday of the schedule is Jul 2; the window begins during an "off" week.
+    expect(occurrence_summaries(schedule.occurrences_between(starts_at + 8.days, starts_at + 31.days)).map(&:first))
+      .to eq([
+        "Tue Jul 16 2024 09:00AM -1000",
+        "Tue Jul 30 2024 09:00AM -1000"
+      ])
+  end
+
+  {
+    "hourly" => ->(s) { s.repeat_hourly(time_of_day: { minute: 20 }, duration_in_seconds: 60, interval: 7, label: "hourly") },
+    "daily" => ->(s) { s.repeat_daily(time_of_day: { hour: 9 }, duration_in_seconds: 60, interval: 3, label: "daily") },
+    "weekly" => ->(s) { s.repeat_weekly(:friday, time_of_day: { hour: 10 }, duration_in_seconds: 60, interval: 2, label: "weekly") },
+    "monthly by day" => lambda { |s|
+      s.repeat_monthly_by_day(31, time_of_day: { hour: 11 }, duration_in_seconds: 60, interval: 2, label: "monthly by day")
+    },
+    "monthly by nth weekday" => lambda { |s|
+      s.repeat_monthly_by_nth_weekday(:monday, 0, time_of_day: { hour: 12 }, duration_in_seconds: 60, interval: 3, label: "monthly by nth weekday")
+    },
+    "annually by day" => lambda { |s|
+      s.repeat_annually_by_day(200, time_of_day: { hour: 13 }, duration_in_seconds: 60, interval: 2, label: "annually by day")
+    }
+  }.each do |description, add_series|
+    context "with a #{description} series" do
+      before { add_series.call(schedule) }
+
+      it "returns the same occurrences from windowed queries as from a full expansion" do
+        expect_windows_to_agree_with_full_expansion
+      end
+
+      it "returns the same occurrences from lazy enumeration as from a full expansion" do
+        expect(occurrence_summaries(schedule.each_occurrence.to_a)).to eq(occurrence_summaries(schedule.occurrences))
+      end
+    end
+  end
+
+  context "when the series has its own start" do
+    before do
+      schedule.repeat_daily(
+        time_of_day: { hour: 9 }, duration_in_seconds: 60, interval: 4, starts_at: starts_at + 5.days, label: "daily"
+      )
+    end
+
+    it "counts the interval from the series' start rather than the schedule's" do
+      expect(occurrence_summaries(schedule.occurrences.first(2)).map(&:first))
+        .to eq(["Fri Jul  5 2024 09:00AM -1000", "Tue Jul  9 2024 09:00AM -1000"])
+      expect_windows_to_agree_with_full_expansion
+    end
+  end
+
+  it "skips months without the requested day without shifting the phase of the interval" do
+    schedule.repeat_monthly_by_day(31, time_of_day: { hour: 9 }, duration_in_seconds: 60, interval: 2)
+
+    # Every other month from July; September and November lack a 31st.
+    expect(occurrence_summaries(schedule.occurrences.first(4)).map(&:first))
+      .to eq([
+        "Wed Jul 31 2024 09:00AM -1000",
+        "Fri Jan 31 2025 09:00AM -1000",
+        "Mon Mar 31 2025 09:00AM -1000",
+        "Sat May 31 2025 09:00AM -1000"
+      ])
+    # Versions up to 0.1.3 kept every other 31st instead: Jul 31, Oct 31, Jan 31 and May 31.
+    expect(occurrence_summaries(schedule.occurrences).map(&:first)).not_to include("Thu Oct 31 2024 09:00AM -1000")
+  end
+end
@@ -0,0 +1,91 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "Series with multiple times of day", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 2.days }
+  let(:time_zone) { "Hawaii" }
+  let(:times_of_day) { [{ hour: 20 }, { hour: 8 }, { hour: 14 }] }
+
+  it "generates an occurrence at each time of day on every day of the series" do
+    schedule.repeat_daily(time_of_day: times_of_day, duration_in_seconds: 5.minutes)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Sun Jun 30 2024 08:00AM -1000",
+        "Sun Jun 30 2024 02:00PM -1000",
+        "Sun Jun 30 2024 08:00PM -1000",
+        "Mon Jul  1 2024 08:00AM -1000",
+        "Mon Jul  1 2024 02:00PM -1000",
+        "Mon Jul  1 2024 08:00PM -1000"
+      ])
+  end
+
+  it "counts the occurrences at every time of day towards a single count" do
+    schedule.repeat_daily(time_of_day: times_of_day, duration_in_seconds: 5.minutes, count: 4)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Sun Jun 30 2024 08:00AM -1000",
+        "Sun Jun 30 2024 02:00PM -1000",
+        "Sun Jun 30 2024 08:00PM -1000",
+        "Mon Jul  1 2024 08:00AM -1000"
+      ])
+  end
+
+  context "when the series repeats at an interval" do
+    let(:ends_at) { starts_at + 4.days }
+
+    it "generates the occurrences at every time of day in each selected period" do
+      schedule.repeat_daily(time_of_day: [{ hour: 8 }, { hour: 20 }], duration_in_seconds: 5.minutes, interval: 2)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Sun Jun 30 2024 08:00AM -1000",
+          "Sun Jun 30 2024 08:00PM -1000",
+          "Tue Jul  2 2024 08:00AM -1000",
+          "Tue Jul  2 2024 08:00PM -1000"
+        ])
+    end
+  end
+
+  context "when the series is added from an RRULE" do
+    it "generates an occurrence at every combination of the rule's hours and minutes" do
+      schedule.repeat_rrule("FREQ=DAILY;BYHOUR=9,17;BYMINUTE=0,30;COUNT=4", duration_in_seconds: 5.minutes)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Sun Jun 30 2024 09:00AM -1000",
+          "Sun Jun 30 2024 09:30AM -1000",
+          "Sun Jun 30 2024 05:00PM -1000",
+          "Sun Jun 30 2024 05:30PM -1000"
+        ])
+    end
+  end
+
+  it "exports the times of day as the BYHOUR of an RRULE" do
+    schedule.repeat_daily(time_of_day: times_of_day, duration_in_seconds: 5.minutes)
+
+    expect(schedule.to_ical.split("\r\n"))
+      .to include("RRULE:FREQ=DAILY;BYHOUR=8,14,20;UNTIL=20240702T100000Z")
+  end
+
+  it "restores the times of day of a serialized schedule" do
+    schedule.repeat_daily(time_of_day: times_of_day, duration_in_seconds: 5.minutes)
+
+    expect(Reprise::Schedule.from_json(schedule.to_json).occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+  end
+
+  context "when no times of day are given" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_daily(time_of_day: [], duration_in_seconds: 5.minutes) }
+        .to raise_error(ArgumentError, "At least one time of day is required")
+    end
+  end
+end
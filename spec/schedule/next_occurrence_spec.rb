@@ -0,0 +1,83 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#next_occurrence and #previous_occurrence", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 1.year }
+  let(:time_zone) { "Hawaii" }
+
+  before do
+    schedule.repeat_daily(time_of_day: { hour: 9 }, duration_in_seconds: 1.hour, label: "Daily")
+    schedule.repeat_weekly(:monday, time_of_day: { hour: 12 }, duration_in_seconds: 1.hour, label: "Weekly")
+  end
+
+  describe "#next_occurrence" do
+    it "returns the first occurrence that starts strictly after the given time" do
+      occurrence = schedule.next_occurrence(after: starts_at + 1.day + 9.hours)
+
+      expect(localized_occurrence_starts_at(occurrence)).to eq("Mon Jul  1 2024 12:00PM -1000")
+      expect(occurrence.label).to eq("Weekly")
+    end
+
+    it "skips excluded occurrences" do
+      schedule.add_exclusion(starts_at: starts_at + 1.day, ends_at: starts_at + 2.days)
+
+      expect(localized_occurrence_starts_at(schedule.next_occurrence(after: starts_at + 10.hours)))
+        .to eq("Tue Jul  2 2024 09:00AM -1000")
+    end
+
+    it "respects the interval of a series from its start" do
+      other_schedule = Reprise::Schedule.new(starts_at:, ends_at:, time_zone:)
+      other_schedule.repeat_daily(time_of_day: { hour: 9 }, interval: 3, duration_in_seconds: 1.hour)
+
+      expect(localized_occurrence_starts_at(other_schedule.next_occurrence(after: starts_at + 2.days)))
+        .to eq("Wed Jul  3 2024 09:00AM -1000")
+    end
+
+    it "returns nil after the end of the schedule" do
+      expect(schedule.next_occurrence(after: ends_at)).to be_nil
+    end
+
+    context "when the schedule is open-ended" do
+      let(:ends_at) { nil }
+
+      it "finds occurrences far into the future" do
+        expect(localized_occurrence_starts_at(schedule.next_occurrence(after: starts_at + 20.years)))
+          .to eq("Thu Jun 30 2044 09:00AM -1000")
+      end
+    end
+  end
+
+  describe "#previous_occurrence" do
+    it "returns the last occurrence that starts strictly before the given time" do
+      occurrence = schedule.previous_occurrence(before: starts_at + 1.day + 12.hours)
+
+      expect(localized_occurrence_starts_at(occurrence)).to eq("Mon Jul  1 2024 09:00AM -1000")
+      expect(occurrence.label).to eq("Daily")
+    end
+
+    it "skips excluded occurrences" do
+      schedule.add_exclusion(starts_at: starts_at + 1.day, ends_at: starts_at + 2.days)
+
+      expect(localized_occurrence_starts_at(schedule.previous_occurrence(before: starts_at + 2.days)))
+        .to eq("Sun Jun 30 2024 09:00AM -1000")
+    end
+
+    it "looks back beyond long gaps between occurrences" do
+      other_schedule = Reprise::Schedule.new(starts_at:, ends_at: starts_at + 5.years, time_zone:)
+      other_schedule.repeat_annually_by_day(200, time_of_day: { hour: 9 }, duration_in_seconds: 1.hour)
+
+      expect(localized_occurrence_starts_at(other_schedule.previous_occurrence(before: starts_at + 4.years)))
+        .to eq("Mon Jul 19 2027 09:00AM -1000")
+    end
+
+    it "returns nil before the start of the schedule" do
+      expect(schedule.previous_occurrence(before: starts_at + 9.hours)).to be_nil
+    end
+  end
+end
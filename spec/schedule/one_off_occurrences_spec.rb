@@ -0,0 +1,76 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "one-off occurrences", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 4.weeks }
+  let(:time_zone) { "Hawaii" }
+
+  describe "#add_occurrence" do
+    it "merges the occurrence into the occurrences of the schedule's series" do
+      schedule.repeat_weekly(:monday, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes, label: "Standup")
+      schedule.add_occurrence(starts_at: Time.new(2024, 7, 10, 14, 0, 0, "-10:00"), duration_in_seconds: 1.hour, label: "Offsite")
+      schedule.add_occurrence(starts_at: Time.new(2024, 7, 3, 16, 0, 0, "-10:00"), duration_in_seconds: 2.hours)
+
+      expect(schedule.occurrences.map { |o| [localized_occurrence_start_and_end_time(o), o.label] }).to eq([
+        ["Mon Jul  1 2024 09:00AM -1000 - Mon Jul  1 2024 09:30AM -1000", "Standup"],
+        ["Wed Jul  3 2024 04:00PM -1000 - Wed Jul  3 2024 06:00PM -1000", nil],
+        ["Mon Jul  8 2024 09:00AM -1000 - Mon Jul  8 2024 09:30AM -1000", "Standup"],
+        ["Wed Jul 10 2024 02:00PM -1000 - Wed Jul 10 2024 03:00PM -1000", "Offsite"],
+        ["Mon Jul 15 2024 09:00AM -1000 - Mon Jul 15 2024 09:30AM -1000", "Standup"],
+        ["Mon Jul 22 2024 09:00AM -1000 - Mon Jul 22 2024 09:30AM -1000", "Standup"]
+      ])
+    end
+
+    it "removes the occurrence when it overlaps with an exclusion" do
+      schedule.add_occurrence(starts_at: Time.new(2024, 7, 3, 16, 0, 0, "-10:00"), duration_in_seconds: 2.hours)
+      schedule.add_occurrence(starts_at: Time.new(2024, 7, 10, 16, 0, 0, "-10:00"), duration_in_seconds: 2.hours)
+      schedule.add_exclusion(starts_at: Time.new(2024, 7, 10, 17, 0, 0, "-10:00"), ends_at: Time.new(2024, 7, 10, 20, 0, 0, "-10:00"))
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) }).to eq(["Wed Jul  3 2024 04:00PM -1000"])
+    end
+
+    it "holds the occurrence to the schedule's bookends" do
+      schedule.add_occurrence(starts_at: starts_at - 1.day, duration_in_seconds: 1.hour)
+      schedule.add_occurrence(starts_at: ends_at + 1.day, duration_in_seconds: 1.hour)
+
+      expect(schedule.occurrences).to be_empty
+    end
+
+    it "returns the occurrence from interval queries" do
+      schedule.add_occurrence(starts_at: Time.new(2024, 7, 10, 14, 0, 0, "-10:00"), duration_in_seconds: 1.hour, label: "Offsite")
+
+      expect(schedule.occurrences_between(Time.new(2024, 7, 10, 0, 0, 0, "-10:00"), Time.new(2024, 7, 11, 0, 0, 0, "-10:00")).map(&:label))
+        .to eq(["Offsite"])
+      expect(schedule.occurrences_between(Time.new(2024, 7, 10, 14, 30, 0, "-10:00"), Time.new(2024, 7, 11, 0, 0, 0, "-10:00")))
+        .to be_empty
+      expect(schedule.occurrences_between(Time.new(2024, 7, 10, 14, 30, 0, "-10:00"), Time.new(2024, 7, 11, 0, 0, 0, "-10:00"), include_overlapping: true).map(&:label))
+        .to eq(["Offsite"])
+      expect(schedule.next_occurrence(after: starts_at).label).to eq("Offsite")
+    end
+
+    it "lists the occurrences of each duration and label as the RDATEs of a VEVENT" do
+      schedule.add_occurrence(starts_at: Time.new(2024, 7, 3, 16, 0, 0, "-10:00"), duration_in_seconds: 1.hour, label: "Offsite")
+      schedule.add_occurrence(starts_at: Time.new(2024, 7, 10, 16, 0, 0, "-10:00"), duration_in_seconds: 1.hour, label: "Offsite")
+      schedule.add_occurrence(starts_at: Time.new(2024, 7, 5, 9, 0, 0, "-10:00"), duration_in_seconds: 2.hours, label: "Offsite")
+
+      content_lines = schedule.to_ical.split("\r\n")
+      expect(content_lines.count("BEGIN:VEVENT")).to eq(2)
+      expect(content_lines).to include(
+        "DTSTART;TZID=Pacific/Honolulu:20240703T160000",
+        "RDATE;TZID=Pacific/Honolulu:20240710T160000",
+        "DTSTART;TZID=Pacific/Honolulu:20240705T090000",
+        "DURATION:PT2H"
+      )
+
+      restored_schedule = Reprise::Schedule.from_ical(schedule.to_ical, starts_at:, ends_at:, time_zone:)
+      expect(restored_schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+        .to eq(schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+    end
+  end
+end
@@ -0,0 +1,96 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "Open-ended schedules", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:time_zone) { "Hawaii" }
+
+  before do
+    schedule.repeat_weekly(:monday, time_of_day: { hour: 9 }, duration_in_seconds: 1.hour, label: "Forever")
+  end
+
+  it "queries occurrences within any interval of time" do
+    occurrences = schedule.occurrences_between(
+      Time.new(2074, 6, 1, 0, 0, 0, "-10:00"),
+      Time.new(2074, 6, 15, 0, 0, 0, "-10:00")
+    )
+
+    expect(occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq(["Mon Jun  4 2074 09:00AM -1000", "Mon Jun 11 2074 09:00AM -1000"])
+  end
+
+  it "enumerates occurrences lazily" do
+    expect(schedule.each_occurrence.first(2).map { |o| localized_occurrence_starts_at(o) })
+      .to eq(["Mon Jul  1 2024 09:00AM -1000", "Mon Jul  8 2024 09:00AM -1000"])
+  end
+
+  it "refuses to expand all of the schedule's occurrences" do
+    expect { schedule.occurrences }.to raise_error(Reprise::UnboundedScheduleError)
+  end
+
+  context "when every series is bounded by its own count or end" do
+    it "expands all of the schedule's occurrences" do
+      bounded_schedule = Reprise::Schedule.new(starts_at:, time_zone:)
+      bounded_schedule.repeat_weekly(:monday, time_of_day: { hour: 9 }, duration_in_seconds: 1.hour, ends_at: starts_at + 2.weeks)
+      bounded_schedule.repeat_daily(time_of_day: { hour: 8 }, duration_in_seconds: 1.hour, count: 2)
+
+      expect(bounded_schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Sun Jun 30 2024 08:00AM -1000",
+          "Mon Jul  1 2024 08:00AM -1000",
+          "Mon Jul  1 2024 09:00AM -1000",
+          "Mon Jul  8 2024 09:00AM -1000"
+        ])
+    end
+  end
+
+  context "when a series can never occur" do
+    let(:unsatisfiable_schedule) { Reprise::Schedule.new(starts_at:, time_zone:) }
+
+    it "rejects the series instead of searching for its occurrences forever" do
+      expect { unsatisfiable_schedule.repeat_monthly_by_weekday(:monday, set_positions: 6, time_of_day: { hour: 9 }, duration_in_seconds: 1.hour) }
+        .to raise_error(ArgumentError, "The set position 6 is out of range; the series occurs at most 5 times a month")
+      expect { unsatisfiable_schedule.repeat_monthly_by_day(40, time_of_day: { hour: 9 }, duration_in_seconds: 1.hour) }
+        .to raise_error(ArgumentError, /The day 40 is out of range/)
+      expect { unsatisfiable_schedule.repeat_annually_by_day(400, time_of_day: { hour: 9 }, duration_in_seconds: 1.hour) }
+        .to raise_error(ArgumentError, /The day 400 is out of range/)
+    end
+
+    it "gives up on a series that has no occurrences left" do
+      # The 100th day of the year is the only one that the rule selects, so there is no second.
+      unsatisfiable_schedule.add_ical(<<~ICS)
+        BEGIN:VCALENDAR
+        BEGIN:VEVENT
+        DTSTART:20240701T190000Z
+        RRULE:FREQ=YEARLY;BYYEARDAY=100;BYSETPOS=2
+        END:VEVENT
+        END:VCALENDAR
+      ICS
+
+      expect(unsatisfiable_schedule.each_occurrence.first(1)).to be_empty
+      expect(unsatisfiable_schedule.next_occurrence(after: starts_at)).to be_nil
+    end
+  end
+
+  it "exports an RRULE without an end" do
+    expect(schedule.to_ical.split("\r\n")).to include("RRULE:FREQ=WEEKLY;BYDAY=MO")
+  end
+
+  it "exports a schedule that starts within ten years of the latest supported date" do
+    distant_schedule = Reprise::Schedule.new(starts_at: Time.new(262_140, 1, 1, 0, 0, 0, "-10:00"), time_zone:)
+
+    expect(distant_schedule.to_ical.split("\r\n")).to include("BEGIN:VTIMEZONE", "END:VCALENDAR")
+  end
+
+  it "survives serialization" do
+    restored_schedule = Reprise::Schedule.from_json(schedule.to_json)
+
+    expect { restored_schedule.occurrences }.to raise_error(Reprise::UnboundedScheduleError)
+    expect(restored_schedule.each_occurrence.first.starts_at).to eq(schedule.each_occurrence.first.starts_at)
+  end
+end
@@ -59,6 +59,15 @@ RSpec.describe "#repeat_annually_by_day", aggregate_failures: true do
       )
   end
 
+  context "when the day is out of range" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_annually_by_day(0, **series_options) }
+        .to raise_error(ArgumentError, "The day 0 is out of range; it must be between 1 and 366")
+      expect { schedule.repeat_annually_by_day(400, **series_options) }
+        .to raise_error(ArgumentError, "The day 400 is out of range; it must be between 1 and 366")
+    end
+  end
+
   context "when the schedule starts on a transition from Standard Time (ST) to Daylight Savings Time (DST)" do
     let(:starts_at) do
       (TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024 + 5.minutes).in_time_zone(time_zone)
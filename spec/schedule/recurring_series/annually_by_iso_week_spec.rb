@@ -0,0 +1,101 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#repeat_annually_by_iso_week", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024.in_time_zone(time_zone) }
+  let(:ends_at) { starts_at + 3.years }
+  let(:time_zone) { TimeZoneHelpers::LOS_ANGELES_TIME_ZONE }
+
+  it_behaves_like "a series that supports optional occurrence labels" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_annually_by_iso_week(11..13, :monday, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports the duration_in_seconds argument" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_annually_by_iso_week(11..13, :monday, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports an optional count argument" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_annually_by_iso_week(11..13, :monday, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it "generates an occurrence on the weekdays of each ISO week" do
+    schedule.repeat_annually_by_iso_week(11..13, :monday, **series_options)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Mon Mar 11 2024 10:15PM -0700",
+        "Mon Mar 18 2024 10:15PM -0700",
+        "Mon Mar 25 2024 10:15PM -0700",
+        "Mon Mar 10 2025 10:15PM -0700",
+        "Mon Mar 17 2025 10:15PM -0700",
+        "Mon Mar 24 2025 10:15PM -0700",
+        "Mon Mar  9 2026 10:15PM -0700",
+        "Mon Mar 16 2026 10:15PM -0700",
+        "Mon Mar 23 2026 10:15PM -0700"
+      ])
+  end
+
+  it "counts negative week numbers back from the last week of the ISO year" do
+    schedule.repeat_annually_by_iso_week(-1, :friday, **series_options)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Fri Dec 27 2024 10:15PM -0800",
+        "Fri Dec 26 2025 10:15PM -0800",
+        "Fri Jan  1 2027 10:15PM -0800"
+      ])
+  end
+
+  it "skips the ISO years that lack the week number" do
+    schedule.repeat_annually_by_iso_week(53, %i[thursday sunday], **series_options)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Thu Dec 31 2026 10:15PM -0800",
+        "Sun Jan  3 2027 10:15PM -0800"
+      ])
+  end
+
+  it "applies the interval to ISO years, rather than calendar years" do
+    schedule.repeat_annually_by_iso_week(1, :monday, **series_options(interval: 2))
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Mon Dec 30 2024 10:15PM -0800",
+        "Mon Jan  4 2027 10:15PM -0800"
+      ])
+  end
+
+  context "when a week number is out of range" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_annually_by_iso_week([1, 54], :monday, **series_options) }
+        .to raise_error(ArgumentError, "The week number 54 is out of range; it must be between 1 and 53 or -53 and -1")
+    end
+  end
+
+  context "when no week numbers or weekdays are given" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_annually_by_iso_week([], :monday, **series_options) }
+        .to raise_error(ArgumentError, "At least one week number is required")
+      expect { schedule.repeat_annually_by_iso_week(1, [], **series_options) }
+        .to raise_error(ArgumentError, "At least one weekday is required")
+    end
+  end
+end
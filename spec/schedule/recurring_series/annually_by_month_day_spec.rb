@@ -0,0 +1,113 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#repeat_annually_by_month_day", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024.in_time_zone(time_zone) }
+  let(:ends_at) { starts_at + 6.years }
+  let(:time_zone) { TimeZoneHelpers::LOS_ANGELES_TIME_ZONE }
+
+  it_behaves_like "a series that supports optional occurrence labels" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_annually_by_month_day(3, 15, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports the duration_in_seconds argument" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_annually_by_month_day(3, 15, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports an optional count argument" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_annually_by_month_day(3, 15, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it "generates an array of annual occurrences on the same day of the month" do
+    schedule.repeat_annually_by_month_day(3, 15, **series_options)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Fri Mar 15 2024 10:15PM -0700",
+        "Sat Mar 15 2025 10:15PM -0700",
+        "Sun Mar 15 2026 10:15PM -0700",
+        "Mon Mar 15 2027 10:15PM -0700",
+        "Wed Mar 15 2028 10:15PM -0700",
+        "Thu Mar 15 2029 10:15PM -0700"
+      ])
+  end
+
+  it "honours the interval of the series in years" do
+    schedule.repeat_annually_by_month_day(3, 15, **series_options(interval: 2))
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Fri Mar 15 2024 10:15PM -0700",
+        "Sun Mar 15 2026 10:15PM -0700",
+        "Wed Mar 15 2028 10:15PM -0700"
+      ])
+  end
+
+  context "when the series repeats on the 29th of February" do
+    it "skips common years by default" do
+      schedule.repeat_annually_by_month_day(2, 29, **series_options)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq(["Tue Feb 29 2028 10:15PM -0800"])
+    end
+
+    it "clamps the day to the 28th of February in common years" do
+      schedule.repeat_annually_by_month_day(2, 29, missing_day: :clamp, **series_options)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Fri Feb 28 2025 10:15PM -0800",
+          "Sat Feb 28 2026 10:15PM -0800",
+          "Sun Feb 28 2027 10:15PM -0800",
+          "Tue Feb 29 2028 10:15PM -0800",
+          "Wed Feb 28 2029 10:15PM -0800"
+        ])
+    end
+
+    it "rolls the day over to the 1st of March in common years" do
+      schedule.repeat_annually_by_month_day(2, 29, missing_day: :roll, **series_options)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Sat Mar  1 2025 10:15PM -0800",
+          "Sun Mar  1 2026 10:15PM -0800",
+          "Mon Mar  1 2027 10:15PM -0800",
+          "Tue Feb 29 2028 10:15PM -0800",
+          "Thu Mar  1 2029 10:15PM -0800"
+        ])
+    end
+  end
+
+  context "when the day does not exist in the month" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_annually_by_month_day(4, 31, **series_options) }
+        .to raise_error(ArgumentError, "The day 31 does not exist in month 4")
+      expect { schedule.repeat_annually_by_month_day(13, 1, **series_options) }
+        .to raise_error(ArgumentError, "The month 13 is out of range; it must be between 1 and 12")
+    end
+  end
+
+  context "when the missing day policy is not supported" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_annually_by_month_day(2, 29, missing_day: :backward, **series_options) }
+        .to raise_error(ArgumentError, "Unsupported missing_day :backward")
+    end
+  end
+end
@@ -0,0 +1,85 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#repeat_annually_by_nth_weekday", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024.in_time_zone(time_zone) }
+  let(:ends_at) { starts_at + 6.years }
+  let(:time_zone) { TimeZoneHelpers::LOS_ANGELES_TIME_ZONE }
+
+  it_behaves_like "a series that supports optional occurrence labels" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_annually_by_nth_weekday(11, :thursday, 3, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports the duration_in_seconds argument" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_annually_by_nth_weekday(11, :thursday, 3, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports an optional count argument" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_annually_by_nth_weekday(11, :thursday, 3, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it "generates an array of annual occurrences on the nth weekday of the month" do
+    schedule.repeat_annually_by_nth_weekday(11, :thursday, 3, **series_options)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Thu Nov 28 2024 10:15PM -0800",
+        "Thu Nov 27 2025 10:15PM -0800",
+        "Thu Nov 26 2026 10:15PM -0800",
+        "Thu Nov 25 2027 10:15PM -0800",
+        "Thu Nov 23 2028 10:15PM -0800",
+        "Thu Nov 22 2029 10:15PM -0800"
+      ])
+  end
+
+  it "allows negative indexing into the weekdays of the month" do
+    schedule.repeat_annually_by_nth_weekday(5, :monday, -1, **series_options)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Mon May 27 2024 10:15PM -0700",
+        "Mon May 26 2025 10:15PM -0700",
+        "Mon May 25 2026 10:15PM -0700",
+        "Mon May 31 2027 10:15PM -0700",
+        "Mon May 29 2028 10:15PM -0700",
+        "Mon May 28 2029 10:15PM -0700"
+      ])
+  end
+
+  it "skips the years in which the month lacks the nth weekday" do
+    schedule.repeat_annually_by_nth_weekday(3, :friday, 4, **series_options)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Fri Mar 29 2024 10:15PM -0700",
+        "Fri Mar 31 2028 10:15PM -0700",
+        "Fri Mar 30 2029 10:15PM -0700"
+      ])
+  end
+
+  context "when the month or nth weekday is out of range" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_annually_by_nth_weekday(0, :thursday, 3, **series_options) }
+        .to raise_error(ArgumentError, "The month 0 is out of range; it must be between 1 and 12")
+      expect { schedule.repeat_annually_by_nth_weekday(11, :thursday, -7, **series_options) }
+        .to raise_error(ArgumentError, "The nth weekday -7 is out of range; it must be between -5 and 4")
+    end
+  end
+end
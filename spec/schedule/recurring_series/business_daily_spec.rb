@@ -0,0 +1,130 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#repeat_business_daily", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:, business_calendar:) }
+
+  let(:starts_at) { TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024.in_time_zone(time_zone) }
+  let(:ends_at) { starts_at + 10.days }
+  let(:time_zone) { TimeZoneHelpers::LOS_ANGELES_TIME_ZONE }
+  let(:business_calendar) { nil }
+
+  it_behaves_like "a series that supports optional occurrence labels" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_business_daily(**series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports the duration_in_seconds argument" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_business_daily(**series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports an optional count argument" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_business_daily(**series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  context "when the schedule has no business calendar" do
+    it "generates occurrences on every weekday" do
+      schedule.repeat_business_daily(time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Mon Mar 11 2024 09:00AM -0700",
+          "Tue Mar 12 2024 09:00AM -0700",
+          "Wed Mar 13 2024 09:00AM -0700",
+          "Thu Mar 14 2024 09:00AM -0700",
+          "Fri Mar 15 2024 09:00AM -0700",
+          "Mon Mar 18 2024 09:00AM -0700",
+          "Tue Mar 19 2024 09:00AM -0700"
+        ])
+    end
+  end
+
+  context "when the schedule's business calendar has holidays" do
+    let(:ends_at) { Time.new(2024, 4, 20, 0, 0, 0, "-07:00") }
+    let(:business_calendar) { Reprise::BusinessCalendar.new(holidays: [Date.new(2024, 3, 29), Date.new(2024, 4, 1)]) }
+
+    it "skips over the holidays" do
+      schedule.repeat_business_daily(
+        time_of_day: { hour: 9 }, duration_in_seconds: 300, starts_at: Time.new(2024, 3, 27, 0, 0, 0, "-07:00"), count: 4
+      )
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Wed Mar 27 2024 09:00AM -0700",
+          "Thu Mar 28 2024 09:00AM -0700",
+          "Tue Apr  2 2024 09:00AM -0700",
+          "Wed Apr  3 2024 09:00AM -0700"
+        ])
+    end
+
+    context "and the holidays take out every business day of a week" do
+      let(:ends_at) { starts_at + 4.weeks }
+      let(:business_calendar) do
+        Reprise::BusinessCalendar.new(weekend: %i[monday tuesday wednesday thursday friday saturday], holidays: [Date.new(2024, 3, 17)])
+      end
+
+      it "skips over the whole week" do
+        schedule.repeat_business_daily(time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+        expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+          .to eq([
+            "Sun Mar 10 2024 09:00AM -0700",
+            "Sun Mar 24 2024 09:00AM -0700",
+            "Sun Mar 31 2024 09:00AM -0700"
+          ])
+      end
+    end
+
+    it "counts the interval in business days" do
+      schedule.repeat_business_daily(interval: 3, time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Mon Mar 11 2024 09:00AM -0700",
+          "Thu Mar 14 2024 09:00AM -0700",
+          "Tue Mar 19 2024 09:00AM -0700",
+          "Fri Mar 22 2024 09:00AM -0700",
+          "Wed Mar 27 2024 09:00AM -0700",
+          "Wed Apr  3 2024 09:00AM -0700",
+          "Mon Apr  8 2024 09:00AM -0700",
+          "Thu Apr 11 2024 09:00AM -0700",
+          "Tue Apr 16 2024 09:00AM -0700",
+          "Fri Apr 19 2024 09:00AM -0700"
+        ])
+    end
+  end
+
+  context "when the schedule's business calendar has a different weekend" do
+    let(:business_calendar) { Reprise::BusinessCalendar.new(weekend: %i[friday saturday]) }
+
+    it "generates occurrences on every day other than those of the weekend" do
+      schedule.repeat_business_daily(time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Sun Mar 10 2024 09:00AM -0700",
+          "Mon Mar 11 2024 09:00AM -0700",
+          "Tue Mar 12 2024 09:00AM -0700",
+          "Wed Mar 13 2024 09:00AM -0700",
+          "Thu Mar 14 2024 09:00AM -0700",
+          "Sun Mar 17 2024 09:00AM -0700",
+          "Mon Mar 18 2024 09:00AM -0700",
+          "Tue Mar 19 2024 09:00AM -0700"
+        ])
+    end
+  end
+end
@@ -71,6 +71,20 @@ RSpec.describe "#repeat_hourly", aggregate_failures: true do
     end
   end
 
+  context "when a step is included" do
+    it "generates an array of occurrences that many seconds apart" do
+      schedule.repeat_hourly(**series_options(time_of_day: { hour: 1, minute: 2, second: 3 }), every: 90.minutes)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to contain_exactly(
+          "Sun Mar 10 2024 03:32AM -0700",
+          "Sun Mar 10 2024 05:02AM -0700",
+          "Sun Mar 10 2024 06:32AM -0700",
+          "Sun Mar 10 2024 08:02AM -0700"
+        )
+    end
+  end
+
   context "when the schedule starts on a transition from Standard Time (ST) to Daylight Savings Time (DST)" do
     let(:starts_at) do
       (TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024 + 5.minutes).in_time_zone(time_zone)
@@ -116,6 +130,27 @@ RSpec.describe "#repeat_hourly", aggregate_failures: true do
     end
   end
 
+  context "when restricted to a daily window that recurs on a transition from Daylight Savings Time (DST) to Standard Time (ST)" do
+    let(:starts_at) { Time.new(2024, 11, 2, 0, 0, 0, "-07:00").in_time_zone(time_zone) }
+    let(:ends_at) { starts_at + 3.days }
+
+    it "generates an array of occurrences within both instances of the window" do
+      schedule.repeat_hourly(
+        time_of_day: { hour: 0, minute: 30 }, window_start: { hour: 1 }, window_end: { hour: 2 }, duration_in_seconds: 5.minutes
+      )
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to contain_exactly(
+          "Sat Nov  2 2024 01:30AM -0700",
+          "Sun Nov  3 2024 01:30AM -0700",
+          # N.B. Clocks are turned back 1 hour at 2:00am,
+          # so the window recurs.
+          "Sun Nov  3 2024 01:30AM -0800",
+          "Mon Nov  4 2024 01:30AM -0800"
+        )
+    end
+  end
+
   context "when the schedule crosses a transition from Daylight Savings Time (DST) to Standard Time (ST)" do
     let(:starts_at) do
       (TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_ST_2024 - 1.hour)
@@ -66,6 +66,52 @@ RSpec.describe "#repeat_minutely", aggregate_failures: true do
     end
   end
 
+  context "when a step is included" do
+    it "generates an array of occurrences that many seconds apart" do
+      schedule.repeat_minutely(**series_options, time_of_day: nil, every: 90.seconds)
+
+      expect(schedule.occurrences.map { |o| o.starts_at.in_time_zone(time_zone).strftime("%I:%M:%S%p %z") })
+        .to contain_exactly(
+          "01:59:00AM -0800",
+          "03:00:30AM -0700",
+          "03:02:00AM -0700",
+          "03:03:30AM -0700"
+        )
+    end
+  end
+
+  context "when restricted to a daily window on certain weekdays" do
+    let(:starts_at) { Time.new(2024, 3, 8, 16, 0, 0, "-08:00").in_time_zone(time_zone) }
+    let(:ends_at) { Time.new(2024, 3, 11, 9, 31, 0, "-07:00").in_time_zone(time_zone) }
+
+    it "generates an array of occurrences within the window, skipping the weekend and its DST change" do
+      schedule.repeat_minutely(
+        time_of_day: { hour: 0 },
+        every: 15.minutes,
+        window_start: { hour: 9 },
+        window_end: { hour: 17 },
+        weekdays: %i[monday tuesday wednesday thursday friday],
+        duration_in_seconds: 5.minutes
+      )
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to contain_exactly(
+          "Fri Mar  8 2024 04:00PM -0800",
+          "Fri Mar  8 2024 04:15PM -0800",
+          "Fri Mar  8 2024 04:30PM -0800",
+          "Fri Mar  8 2024 04:45PM -0800",
+          "Mon Mar 11 2024 09:00AM -0700",
+          "Mon Mar 11 2024 09:15AM -0700",
+          "Mon Mar 11 2024 09:30AM -0700"
+        )
+    end
+
+    it "raises an error when the window ends before it starts" do
+      expect { schedule.repeat_minutely(**series_options, window_start: { hour: 17 }, window_end: { hour: 9 }) }
+        .to raise_error(ArgumentError, /The daily window must end after it starts/)
+    end
+  end
+
   context "when the schedule starts on a transition from Standard Time (ST) to Daylight Savings Time (DST)" do
     let(:starts_at) do
       (TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024 + 2.minutes).in_time_zone(time_zone)
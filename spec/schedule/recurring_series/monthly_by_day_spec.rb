@@ -14,7 +14,7 @@ RSpec.describe "#repeat_monthly_by_day", aggregate_failures: true do
   it_behaves_like "a series that supports optional occurrence labels" do
     let(:series_options_hash) { series_options(time_of_day: { hour: 1, minute: 2, second: 3 }) }
     let(:occurrences) do
-      schedule.repeat_monthly_by_day(0, **series_options_hash)
+      schedule.repeat_monthly_by_day(1, **series_options_hash)
       schedule.occurrences
     end
   end
@@ -22,7 +22,7 @@ RSpec.describe "#repeat_monthly_by_day", aggregate_failures: true do
   it_behaves_like "a series that supports the duration_in_seconds argument" do
     let(:series_options_hash) { series_options(time_of_day: { hour: 1, minute: 2, second: 3 }) }
     let(:occurrences) do
-      schedule.repeat_monthly_by_day(0, **series_options_hash)
+      schedule.repeat_monthly_by_day(1, **series_options_hash)
       schedule.occurrences
     end
   end
@@ -59,6 +59,105 @@ RSpec.describe "#repeat_monthly_by_day", aggregate_failures: true do
       )
   end
 
+  it "counts negative day numbers back from the end of the month" do
+    schedule.repeat_monthly_by_day(-1, **series_options)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Sun Mar 31 2024 10:15PM -0700",
+        "Tue Apr 30 2024 10:15PM -0700",
+        "Fri May 31 2024 10:15PM -0700",
+        "Sun Jun 30 2024 10:15PM -0700",
+        "Wed Jul 31 2024 10:15PM -0700"
+      )
+  end
+
+  it "generates occurrences on each of several days" do
+    schedule.repeat_monthly_by_day([15, -1], **series_options)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Fri Mar 15 2024 10:15PM -0700",
+        "Sun Mar 31 2024 10:15PM -0700",
+        "Mon Apr 15 2024 10:15PM -0700",
+        "Tue Apr 30 2024 10:15PM -0700",
+        "Wed May 15 2024 10:15PM -0700",
+        "Fri May 31 2024 10:15PM -0700",
+        "Sat Jun 15 2024 10:15PM -0700",
+        "Sun Jun 30 2024 10:15PM -0700",
+        "Mon Jul 15 2024 10:15PM -0700",
+        "Wed Jul 31 2024 10:15PM -0700"
+      )
+  end
+
+  context "when missing days are clamped" do
+    it "moves missing days to the last day of the month" do
+      schedule.repeat_monthly_by_day(31, missing_day: :clamp, **series_options)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to contain_exactly(
+          "Sun Mar 31 2024 10:15PM -0700",
+          "Tue Apr 30 2024 10:15PM -0700",
+          "Fri May 31 2024 10:15PM -0700",
+          "Sun Jun 30 2024 10:15PM -0700",
+          "Wed Jul 31 2024 10:15PM -0700"
+        )
+    end
+  end
+
+  context "when missing days are rolled over" do
+    it "moves missing days to the first day of the following month" do
+      schedule.repeat_monthly_by_day(31, missing_day: :roll, **series_options)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to contain_exactly(
+          "Sun Mar 31 2024 10:15PM -0700",
+          "Wed May  1 2024 10:15PM -0700",
+          "Fri May 31 2024 10:15PM -0700",
+          "Mon Jul  1 2024 10:15PM -0700",
+          "Wed Jul 31 2024 10:15PM -0700"
+        )
+    end
+  end
+
+  context "when set positions are given" do
+    it "keeps the occurrences at those positions among the days of each month" do
+      schedule.repeat_monthly_by_day([1, 15, 29, 30, 31], set_positions: [2, -1], **series_options)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Fri Mar 15 2024 10:15PM -0700",
+          "Sun Mar 31 2024 10:15PM -0700",
+          "Mon Apr 15 2024 10:15PM -0700",
+          "Tue Apr 30 2024 10:15PM -0700",
+          "Wed May 15 2024 10:15PM -0700",
+          "Fri May 31 2024 10:15PM -0700",
+          "Sat Jun 15 2024 10:15PM -0700",
+          "Sun Jun 30 2024 10:15PM -0700",
+          "Mon Jul 15 2024 10:15PM -0700",
+          "Wed Jul 31 2024 10:15PM -0700"
+        ])
+    end
+  end
+
+  context "when a day is out of range" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_monthly_by_day(0, **series_options) }
+        .to raise_error(ArgumentError, "The day 0 is out of range; it must be between 1 and 31, or -31 and -1")
+      expect { schedule.repeat_monthly_by_day([15, 40], **series_options) }
+        .to raise_error(ArgumentError, "The day 40 is out of range; it must be between 1 and 31, or -31 and -1")
+      expect { schedule.repeat_monthly_by_day(-32, **series_options) }
+        .to raise_error(ArgumentError, "The day -32 is out of range; it must be between 1 and 31, or -31 and -1")
+    end
+  end
+
+  context "when the missing day policy is not supported" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_monthly_by_day(31, missing_day: :nearest, **series_options) }
+        .to raise_error(ArgumentError, "Unsupported missing_day :nearest")
+    end
+  end
+
   context "when the schedule starts on a transition from Standard Time (ST) to Daylight Savings Time (DST)" do
     let(:starts_at) do
       (TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024 + 5.minutes).in_time_zone(time_zone)
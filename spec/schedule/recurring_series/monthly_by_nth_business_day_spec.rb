@@ -0,0 +1,104 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#repeat_monthly_by_nth_business_day", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:, business_calendar:) }
+
+  let(:starts_at) { TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024.in_time_zone(time_zone) }
+  let(:ends_at) { starts_at + 5.months }
+  let(:time_zone) { TimeZoneHelpers::LOS_ANGELES_TIME_ZONE }
+  let(:business_calendar) { Reprise::BusinessCalendar.new(holidays: [Date.new(2024, 3, 29), Date.new(2024, 4, 1)]) }
+
+  it_behaves_like "a series that supports optional occurrence labels" do
+    let(:series_options_hash) { series_options(time_of_day: { hour: 1, minute: 2, second: 3 }) }
+    let(:occurrences) do
+      schedule.repeat_monthly_by_nth_business_day(0, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports the duration_in_seconds argument" do
+    let(:series_options_hash) { series_options(time_of_day: { hour: 1, minute: 2, second: 3 }) }
+    let(:occurrences) do
+      schedule.repeat_monthly_by_nth_business_day(0, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports an optional count argument" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_monthly_by_nth_business_day(0, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it "generates occurrences on the first business day of every month" do
+    schedule.repeat_monthly_by_nth_business_day(0, time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    # N.B. Mon Apr 1 is a holiday.
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Tue Apr  2 2024 09:00AM -0700",
+        "Wed May  1 2024 09:00AM -0700",
+        "Mon Jun  3 2024 09:00AM -0700",
+        "Mon Jul  1 2024 09:00AM -0700",
+        "Thu Aug  1 2024 09:00AM -0700"
+      ])
+  end
+
+  it "generates occurrences on the last business day of every month" do
+    schedule.repeat_monthly_by_nth_business_day(-1, time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    # N.B. Fri Mar 29 is a holiday.
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Thu Mar 28 2024 09:00AM -0700",
+        "Tue Apr 30 2024 09:00AM -0700",
+        "Fri May 31 2024 09:00AM -0700",
+        "Fri Jun 28 2024 09:00AM -0700",
+        "Wed Jul 31 2024 09:00AM -0700"
+      ])
+  end
+
+  it "generates occurrences in every other month" do
+    schedule.repeat_monthly_by_nth_business_day(2, interval: 2, time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Thu Apr  4 2024 09:00AM -0700",
+        "Wed Jun  5 2024 09:00AM -0700",
+        "Mon Aug  5 2024 09:00AM -0700"
+      ])
+  end
+
+  it "skips the months that have fewer business days" do
+    schedule.repeat_monthly_by_nth_business_day(20, time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Tue Apr 30 2024 09:00AM -0700",
+        "Wed May 29 2024 09:00AM -0700",
+        "Mon Jul 29 2024 09:00AM -0700"
+      ])
+  end
+
+  context "when the nth business day is out of range" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_monthly_by_nth_business_day(31, time_of_day: { hour: 9 }, duration_in_seconds: 300) }
+        .to raise_error(ArgumentError, "The nth business day 31 is out of range; it must be between -23 and 22")
+    end
+
+    context "when the business calendar has fewer business days" do
+      let(:business_calendar) { Reprise::BusinessCalendar.new(weekend: %i[friday saturday sunday]) }
+
+      it "raises an ArgumentError for the days that no month has" do
+        expect { schedule.repeat_monthly_by_nth_business_day(-20, time_of_day: { hour: 9 }, duration_in_seconds: 300) }
+          .to raise_error(ArgumentError, "The nth business day -20 is out of range; it must be between -19 and 18")
+      end
+    end
+  end
+end
@@ -77,6 +77,74 @@ RSpec.describe "#repeat_monthly_by_nth_weekday", aggregate_failures: true do
     )
   end
 
+  context "when the series repeats on several nth weekdays" do
+    it "generates an occurrence on each of the nth weekdays in every month" do
+      schedule.repeat_monthly_by_nth_weekday(
+        [[:tuesday, 0], [:tuesday, 2]], time_of_day: { hour: 1, minute: 2, second: 3 }, duration_in_seconds: 300
+      )
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Tue Mar 19 2024 01:02AM -0700",
+          "Tue Apr  2 2024 01:02AM -0700",
+          "Tue Apr 16 2024 01:02AM -0700",
+          "Tue May  7 2024 01:02AM -0700",
+          "Tue May 21 2024 01:02AM -0700",
+          "Tue Jun  4 2024 01:02AM -0700",
+          "Tue Jun 18 2024 01:02AM -0700",
+          "Tue Jul  2 2024 01:02AM -0700",
+          "Tue Jul 16 2024 01:02AM -0700",
+          "Tue Aug  6 2024 01:02AM -0700"
+        ])
+    end
+
+    it "orders the occurrences of different weekdays within each month" do
+      schedule.repeat_monthly_by_nth_weekday(
+        [[:friday, -1], [:monday, 0]], time_of_day: { hour: 1, minute: 2, second: 3 }, duration_in_seconds: 300, count: 5
+      )
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Fri Mar 29 2024 01:02AM -0700",
+          "Mon Apr  1 2024 01:02AM -0700",
+          "Fri Apr 26 2024 01:02AM -0700",
+          "Mon May  6 2024 01:02AM -0700",
+          "Fri May 31 2024 01:02AM -0700"
+        ])
+    end
+
+    it "generates the occurrences of every nth weekday in each selected month" do
+      schedule.repeat_monthly_by_nth_weekday(
+        [[:tuesday, 0], [:tuesday, 2]], time_of_day: { hour: 1, minute: 2, second: 3 }, duration_in_seconds: 300, interval: 2
+      )
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Tue Mar 19 2024 01:02AM -0700",
+          "Tue May  7 2024 01:02AM -0700",
+          "Tue May 21 2024 01:02AM -0700",
+          "Tue Jul  2 2024 01:02AM -0700",
+          "Tue Jul 16 2024 01:02AM -0700"
+        ])
+    end
+
+    context "and no weekdays are given" do
+      it "raises an ArgumentError" do
+        expect { schedule.repeat_monthly_by_nth_weekday([], time_of_day: { hour: 1 }, duration_in_seconds: 300) }
+          .to raise_error(ArgumentError, "At least one weekday is required")
+      end
+    end
+  end
+
+  context "when the nth weekday is out of range" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_monthly_by_nth_weekday(:friday, -7, time_of_day: { hour: 1 }, duration_in_seconds: 300) }
+        .to raise_error(ArgumentError, "The nth weekday -7 is out of range; it must be between -5 and 4")
+      expect { schedule.repeat_monthly_by_nth_weekday([[:monday, 0], [:friday, 5]], time_of_day: { hour: 1 }, duration_in_seconds: 300) }
+        .to raise_error(ArgumentError, "The nth weekday 5 is out of range; it must be between -5 and 4")
+    end
+  end
+
   context "when the schedule starts on a transition from Standard Time (ST) to Daylight Savings Time (DST)" do
     let(:starts_at) do
       (TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024 + 5.minutes).in_time_zone(time_zone)
@@ -0,0 +1,135 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#repeat_monthly_by_weekday", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024.in_time_zone(time_zone) }
+  let(:ends_at) { starts_at + 5.months }
+  let(:time_zone) { TimeZoneHelpers::LOS_ANGELES_TIME_ZONE }
+  let(:weekdays) { %i[monday tuesday wednesday thursday friday] }
+
+  it_behaves_like "a series that supports optional occurrence labels" do
+    let(:series_options_hash) { series_options(time_of_day: { hour: 1, minute: 2, second: 3 }) }
+    let(:occurrences) do
+      schedule.repeat_monthly_by_weekday(weekdays, set_positions: -1, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports the duration_in_seconds argument" do
+    let(:series_options_hash) { series_options(time_of_day: { hour: 1, minute: 2, second: 3 }) }
+    let(:occurrences) do
+      schedule.repeat_monthly_by_weekday(weekdays, set_positions: -1, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports an optional count argument" do
+    let(:series_options_hash) { series_options }
+    let(:occurrences) do
+      schedule.repeat_monthly_by_weekday(weekdays, set_positions: -1, **series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it "generates occurrences on every one of the weekdays in each selected month" do
+    schedule.repeat_monthly_by_weekday(:monday, interval: 2, time_of_day: { hour: 1, minute: 2, second: 3 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Mon Mar 11 2024 01:02AM -0700",
+        "Mon Mar 18 2024 01:02AM -0700",
+        "Mon Mar 25 2024 01:02AM -0700",
+        "Mon May  6 2024 01:02AM -0700",
+        "Mon May 13 2024 01:02AM -0700",
+        "Mon May 20 2024 01:02AM -0700",
+        "Mon May 27 2024 01:02AM -0700",
+        "Mon Jul  1 2024 01:02AM -0700",
+        "Mon Jul  8 2024 01:02AM -0700",
+        "Mon Jul 15 2024 01:02AM -0700",
+        "Mon Jul 22 2024 01:02AM -0700",
+        "Mon Jul 29 2024 01:02AM -0700"
+      ])
+  end
+
+  context "when set positions are given" do
+    it "generates occurrences on the last weekday of every month" do
+      schedule.repeat_monthly_by_weekday(weekdays, set_positions: -1, time_of_day: { hour: 1, minute: 2, second: 3 }, duration_in_seconds: 300)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Fri Mar 29 2024 01:02AM -0700",
+          "Tue Apr 30 2024 01:02AM -0700",
+          "Fri May 31 2024 01:02AM -0700",
+          "Fri Jun 28 2024 01:02AM -0700",
+          "Wed Jul 31 2024 01:02AM -0700"
+        ])
+    end
+
+    it "generates occurrences on the second-to-last weekday of every month" do
+      schedule.repeat_monthly_by_weekday(weekdays, set_positions: -2, time_of_day: { hour: 1, minute: 2, second: 3 }, duration_in_seconds: 300)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Thu Mar 28 2024 01:02AM -0700",
+          "Mon Apr 29 2024 01:02AM -0700",
+          "Thu May 30 2024 01:02AM -0700",
+          "Thu Jun 27 2024 01:02AM -0700",
+          "Tue Jul 30 2024 01:02AM -0700"
+        ])
+    end
+
+    it "counts the positions among all of the weekdays in the month, including those before the schedule starts" do
+      schedule.repeat_monthly_by_weekday(weekdays, set_positions: 1, time_of_day: { hour: 1, minute: 2, second: 3 }, duration_in_seconds: 300)
+
+      # N.B. The first weekday of March (Fri Mar 1) precedes the start of the schedule.
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Mon Apr  1 2024 01:02AM -0700",
+          "Wed May  1 2024 01:02AM -0700",
+          "Mon Jun  3 2024 01:02AM -0700",
+          "Mon Jul  1 2024 01:02AM -0700",
+          "Thu Aug  1 2024 01:02AM -0700"
+        ])
+    end
+
+    it "counts the positions among the occurrences at each of the series' times of day" do
+      schedule.repeat_monthly_by_weekday(
+        weekdays, set_positions: [-1, -2], time_of_day: [{ hour: 9 }, { hour: 17 }], duration_in_seconds: 300, count: 4
+      )
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Fri Mar 29 2024 09:00AM -0700",
+          "Fri Mar 29 2024 05:00PM -0700",
+          "Tue Apr 30 2024 09:00AM -0700",
+          "Tue Apr 30 2024 05:00PM -0700"
+        ])
+    end
+
+    context "and a set position is zero" do
+      it "raises an ArgumentError" do
+        expect { schedule.repeat_monthly_by_weekday(weekdays, set_positions: [1, 0], time_of_day: { hour: 1 }, duration_in_seconds: 300) }
+          .to raise_error(ArgumentError, "Set positions must be non-zero")
+      end
+    end
+
+    context "and a set position is beyond the occurrences of any month" do
+      it "raises an ArgumentError" do
+        expect { schedule.repeat_monthly_by_weekday(weekdays, set_positions: [1, -24], time_of_day: { hour: 1 }, duration_in_seconds: 300) }
+          .to raise_error(ArgumentError, "The set position -24 is out of range; the series occurs at most 23 times a month")
+      end
+    end
+  end
+
+  context "when no weekdays are given" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_monthly_by_weekday([], time_of_day: { hour: 1 }, duration_in_seconds: 300) }
+        .to raise_error(ArgumentError, "At least one weekday is required")
+    end
+  end
+end
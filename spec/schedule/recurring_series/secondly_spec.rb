@@ -0,0 +1,133 @@
+# frozen_string_literal: true
+
+require "rspec"
+require "spec_helper"
+
+RSpec.describe "#repeat_secondly", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024.in_time_zone(time_zone) }
+  let(:ends_at) { starts_at + 5.seconds }
+  let(:time_zone) { TimeZoneHelpers::LOS_ANGELES_TIME_ZONE }
+
+  def localized_occurrence_starts_at_with_seconds(occurrence)
+    occurrence.starts_at.in_time_zone(time_zone).strftime("%a %b %e %Y %I:%M:%S%p %z")
+  end
+
+  it_behaves_like "a series that supports optional occurrence labels" do
+    let(:series_options_hash) { series_options(time_of_day: { hour: 1, minute: 2, second: 3 }) }
+    let(:occurrences) do
+      schedule.repeat_secondly(**series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports the duration_in_seconds argument" do
+    let(:series_options_hash) { series_options(time_of_day: { hour: 1, minute: 2, second: 3 }) }
+    let(:occurrences) do
+      schedule.repeat_secondly(**series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it_behaves_like "a series that supports an optional count argument" do
+    let(:series_options_hash) { series_options(time_of_day: { hour: 1, minute: 2, second: 3 }) }
+    let(:occurrences) do
+      schedule.repeat_secondly(**series_options_hash)
+      schedule.occurrences
+    end
+  end
+
+  it "generates an array of secondly occurrences" do
+    schedule.repeat_secondly(**series_options, time_of_day: nil)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at_with_seconds(o) })
+      .to contain_exactly(
+        "Sun Mar 10 2024 01:59:00AM -0800",
+        "Sun Mar 10 2024 01:59:01AM -0800",
+        "Sun Mar 10 2024 01:59:02AM -0800",
+        "Sun Mar 10 2024 01:59:03AM -0800",
+        "Sun Mar 10 2024 01:59:04AM -0800"
+      )
+  end
+
+  context "when a step is included" do
+    let(:ends_at) { starts_at + 75.seconds }
+
+    it "generates an array of occurrences that many seconds apart, across the DST change" do
+      schedule.repeat_secondly(**series_options, time_of_day: nil, every: 15)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at_with_seconds(o) })
+        .to contain_exactly(
+          "Sun Mar 10 2024 01:59:00AM -0800",
+          "Sun Mar 10 2024 01:59:15AM -0800",
+          "Sun Mar 10 2024 01:59:30AM -0800",
+          "Sun Mar 10 2024 01:59:45AM -0800",
+          # N.B. Notice the DST jump
+          "Sun Mar 10 2024 03:00:00AM -0700"
+        )
+    end
+
+    it "applies the interval to the steps" do
+      schedule.repeat_secondly(**series_options, time_of_day: nil, every: 15, interval: 2)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at_with_seconds(o) })
+        .to contain_exactly(
+          "Sun Mar 10 2024 01:59:00AM -0800",
+          "Sun Mar 10 2024 01:59:30AM -0800",
+          "Sun Mar 10 2024 03:00:00AM -0700"
+        )
+    end
+
+    it "raises an error when the step is less than one second" do
+      expect { schedule.repeat_secondly(**series_options, every: 0) }
+        .to raise_error(ArgumentError, /The step of 0 seconds is out of range/)
+    end
+  end
+
+  context "when restricted to a daily window on certain weekdays" do
+    let(:starts_at) { Time.new(2024, 3, 9, 0, 0, 0, "-08:00").in_time_zone(time_zone) }
+    let(:ends_at) { starts_at + 3.days }
+
+    it "generates an array of occurrences within the window" do
+      schedule.repeat_secondly(
+        time_of_day: { hour: 0 },
+        every: 20,
+        window_start: { hour: 9 },
+        window_end: { hour: 9, minute: 1 },
+        weekdays: %i[sunday monday],
+        duration_in_seconds: 5
+      )
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at_with_seconds(o) })
+        .to contain_exactly(
+          "Sun Mar 10 2024 09:00:00AM -0700",
+          "Sun Mar 10 2024 09:00:20AM -0700",
+          "Sun Mar 10 2024 09:00:40AM -0700",
+          "Mon Mar 11 2024 09:00:00AM -0700",
+          "Mon Mar 11 2024 09:00:20AM -0700",
+          "Mon Mar 11 2024 09:00:40AM -0700"
+        )
+    end
+  end
+
+  context "when the schedule crosses a transition from Daylight Savings Time (DST) to Standard Time (ST)" do
+    let(:starts_at) { TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_ST_2024.in_time_zone(time_zone) }
+    let(:ends_at) { starts_at + 100.minutes }
+
+    it "generates an array of occurrences across the ST change" do
+      schedule.repeat_secondly(**series_options, time_of_day: nil, every: 30.minutes)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at_with_seconds(o) })
+        .to contain_exactly(
+          "Sun Nov  3 2024 12:59:00AM -0700",
+          "Sun Nov  3 2024 01:29:00AM -0700",
+          "Sun Nov  3 2024 01:59:00AM -0700",
+          # N.B. Clocks are turned back 1 hour at 2:00am
+          "Sun Nov  3 2024 01:29:00AM -0800"
+        )
+    end
+  end
+end
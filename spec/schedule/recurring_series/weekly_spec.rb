@@ -48,6 +48,79 @@ RSpec.describe "#repeat_weekly", aggregate_failures: true do
       )
   end
 
+  context "when several weekdays are given" do
+    it "generates occurrences on each of the weekdays" do
+      schedule.repeat_weekly(%i[monday wednesday friday], interval: 2, duration_in_seconds: 30.minutes)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to contain_exactly(
+          "Mon Mar 11 2024 01:59AM -0700",
+          "Wed Mar 13 2024 01:59AM -0700",
+          "Fri Mar 15 2024 01:59AM -0700",
+          "Mon Mar 25 2024 01:59AM -0700",
+          "Wed Mar 27 2024 01:59AM -0700",
+          "Fri Mar 29 2024 01:59AM -0700",
+          "Mon Apr  8 2024 01:59AM -0700",
+          "Wed Apr 10 2024 01:59AM -0700",
+          "Fri Apr 12 2024 01:59AM -0700"
+        )
+    end
+
+    it "selects the weeks of the interval from the given week start" do
+      schedule.repeat_weekly(%i[sunday tuesday], week_start: :sunday, interval: 2, duration_in_seconds: 30.minutes)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to contain_exactly(
+          "Sun Mar 10 2024 01:59AM -0800",
+          "Tue Mar 12 2024 01:59AM -0700",
+          "Sun Mar 24 2024 01:59AM -0700",
+          "Tue Mar 26 2024 01:59AM -0700",
+          "Sun Apr  7 2024 01:59AM -0700",
+          "Tue Apr  9 2024 01:59AM -0700"
+        )
+    end
+
+    it "selects the weeks of the interval from Monday by default" do
+      schedule.repeat_weekly(%i[sunday tuesday], interval: 2, duration_in_seconds: 30.minutes)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to contain_exactly(
+          "Sun Mar 10 2024 01:59AM -0800",
+          "Tue Mar 19 2024 01:59AM -0700",
+          "Sun Mar 24 2024 01:59AM -0700",
+          "Tue Apr  2 2024 01:59AM -0700",
+          "Sun Apr  7 2024 01:59AM -0700"
+        )
+    end
+
+    it "counts the occurrences across all of the weekdays" do
+      schedule.repeat_weekly(%i[tuesday thursday], count: 3, duration_in_seconds: 30.minutes)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to contain_exactly(
+          "Tue Mar 12 2024 01:59AM -0700",
+          "Thu Mar 14 2024 01:59AM -0700",
+          "Tue Mar 19 2024 01:59AM -0700"
+        )
+    end
+  end
+
+  context "when no weekdays are given" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_weekly([], duration_in_seconds: 30.minutes) }
+        .to raise_error(ArgumentError, "At least one weekday is required")
+    end
+  end
+
+  context "when a weekday is not supported" do
+    it "raises an ArgumentError" do
+      expect { schedule.repeat_weekly(:caturday, duration_in_seconds: 30.minutes) }
+        .to raise_error(ArgumentError, /Unsupported weekday :?caturday/)
+      expect { schedule.repeat_weekly(:monday, week_start: :funday, duration_in_seconds: 30.minutes) }
+        .to raise_error(ArgumentError, /Unsupported weekday :?funday/)
+    end
+  end
+
   context "when the schedule starts on a transition from Standard Time (ST) to Daylight Savings Time (DST)" do
     let(:starts_at) do
       (TimeZoneHelpers::ONE_MINUTE_BEFORE_LA_TRANSITION_TO_DST_2024).in_time_zone(time_zone)
@@ -0,0 +1,239 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "#repeat_rrule", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 3.months }
+  let(:time_zone) { "Hawaii" }
+
+  it "generates occurrences for a weekly rule with multiple weekdays" do
+    schedule.repeat_rrule("FREQ=WEEKLY;BYDAY=TU,TH;UNTIL=20240713", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Tue Jul  2 2024 09:00AM -1000",
+        "Thu Jul  4 2024 09:00AM -1000",
+        "Tue Jul  9 2024 09:00AM -1000",
+        "Thu Jul 11 2024 09:00AM -1000"
+      )
+  end
+
+  it "honours the interval and week start of a weekly rule with multiple weekdays" do
+    schedule.repeat_rrule("FREQ=WEEKLY;INTERVAL=2;BYDAY=SU,TU;WKST=SU;COUNT=4", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Sun Jun 30 2024 09:00AM -1000",
+        "Tue Jul  2 2024 09:00AM -1000",
+        "Sun Jul 14 2024 09:00AM -1000",
+        "Tue Jul 16 2024 09:00AM -1000"
+      )
+  end
+
+  it "generates occurrences for a monthly rule with an nth weekday" do
+    schedule.repeat_rrule("RRULE:FREQ=MONTHLY;BYDAY=-1FR", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Fri Jul 26 2024 09:00AM -1000",
+        "Fri Aug 30 2024 09:00AM -1000",
+        "Fri Sep 27 2024 09:00AM -1000"
+      )
+  end
+
+  it "honours the interval and count of a monthly rule with several nth weekdays" do
+    schedule.repeat_rrule("FREQ=MONTHLY;BYDAY=1TU,3TU;INTERVAL=2;COUNT=4", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Tue Jul  2 2024 09:00AM -1000",
+        "Tue Jul 16 2024 09:00AM -1000",
+        "Tue Sep  3 2024 09:00AM -1000",
+        "Tue Sep 17 2024 09:00AM -1000"
+      ])
+  end
+
+  it "keeps the occurrences at the set positions of a monthly rule with several weekdays" do
+    schedule.repeat_rrule("FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=1,-1", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Mon Jul  1 2024 09:00AM -1000",
+        "Wed Jul 31 2024 09:00AM -1000",
+        "Thu Aug  1 2024 09:00AM -1000",
+        "Fri Aug 30 2024 09:00AM -1000",
+        "Mon Sep  2 2024 09:00AM -1000"
+      ])
+  end
+
+  it "honours the interval of a monthly rule with weekdays" do
+    schedule.repeat_rrule("FREQ=MONTHLY;BYDAY=MO;INTERVAL=2", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Mon Jul  1 2024 09:00AM -1000",
+        "Mon Jul  8 2024 09:00AM -1000",
+        "Mon Jul 15 2024 09:00AM -1000",
+        "Mon Jul 22 2024 09:00AM -1000",
+        "Mon Jul 29 2024 09:00AM -1000",
+        "Mon Sep  2 2024 09:00AM -1000",
+        "Mon Sep  9 2024 09:00AM -1000",
+        "Mon Sep 16 2024 09:00AM -1000",
+        "Mon Sep 23 2024 09:00AM -1000"
+      ])
+  end
+
+  it "generates occurrences for a monthly rule with several days, counted from either end of the month" do
+    schedule.repeat_rrule("FREQ=MONTHLY;BYMONTHDAY=15,-1", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Sun Jun 30 2024 09:00AM -1000",
+        "Mon Jul 15 2024 09:00AM -1000",
+        "Wed Jul 31 2024 09:00AM -1000",
+        "Thu Aug 15 2024 09:00AM -1000",
+        "Sat Aug 31 2024 09:00AM -1000",
+        "Sun Sep 15 2024 09:00AM -1000"
+      )
+  end
+
+  it "moves the days that a month lacks according to the rule's SKIP" do
+    schedule.repeat_rrule("FREQ=MONTHLY;BYMONTHDAY=31;RSCALE=GREGORIAN;SKIP=BACKWARD", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Sun Jun 30 2024 09:00AM -1000",
+        "Wed Jul 31 2024 09:00AM -1000",
+        "Sat Aug 31 2024 09:00AM -1000"
+      )
+  end
+
+  it "honours the interval and count of the rule" do
+    schedule.repeat_rrule("FREQ=WEEKLY;INTERVAL=2;COUNT=3", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Sun Jun 30 2024 09:00AM -1000",
+        "Sun Jul 14 2024 09:00AM -1000",
+        "Sun Jul 28 2024 09:00AM -1000"
+      )
+  end
+
+  context "when the rule is yearly, in a given month" do
+    let(:ends_at) { starts_at + 3.years }
+
+    it "generates occurrences on the nth weekday of the month" do
+      schedule.repeat_rrule("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Thu Nov 28 2024 09:00AM -1000",
+          "Thu Nov 27 2025 09:00AM -1000",
+          "Thu Nov 26 2026 09:00AM -1000"
+        ])
+    end
+
+    it "generates occurrences on the day of the month, moving a missing 29th of February as the rule skips" do
+      schedule.repeat_rrule("FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29;RSCALE=GREGORIAN;SKIP=FORWARD", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Sat Mar  1 2025 09:00AM -1000",
+          "Sun Mar  1 2026 09:00AM -1000",
+          "Mon Mar  1 2027 09:00AM -1000"
+        ])
+    end
+
+    it "generates occurrences on the day of the series' start in each month when the rule lists no day" do
+      schedule.repeat_rrule("FREQ=YEARLY;BYMONTH=3,9", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Mon Sep 30 2024 09:00AM -1000",
+          "Sun Mar 30 2025 09:00AM -1000",
+          "Tue Sep 30 2025 09:00AM -1000",
+          "Mon Mar 30 2026 09:00AM -1000",
+          "Wed Sep 30 2026 09:00AM -1000",
+          "Tue Mar 30 2027 09:00AM -1000"
+        ])
+    end
+  end
+
+  context "when the rule is yearly, without any other parts" do
+    let(:ends_at) { starts_at + 3.years }
+
+    it "generates occurrences on the month and day of the series' start" do
+      schedule.repeat_rrule("FREQ=YEARLY", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+      expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Sun Jun 30 2024 09:00AM -1000",
+          "Mon Jun 30 2025 09:00AM -1000",
+          "Tue Jun 30 2026 09:00AM -1000"
+        ])
+    end
+  end
+
+  it "generates occurrences for a yearly rule with ISO week numbers" do
+    schedule.repeat_rrule("FREQ=YEARLY;BYWEEKNO=28,-1;BYDAY=MO,FR", time_of_day: { hour: 9 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to eq([
+        "Mon Jul  8 2024 09:00AM -1000",
+        "Fri Jul 12 2024 09:00AM -1000"
+      ])
+  end
+
+  it "steps a secondly rule by its interval" do
+    schedule.repeat_rrule("FREQ=SECONDLY;INTERVAL=15;COUNT=3", time_of_day: { hour: 9 }, duration_in_seconds: 5)
+
+    expect(schedule.occurrences.map { |o| o.starts_at.in_time_zone(time_zone).strftime("%a %b %e %Y %I:%M:%S%p %z") })
+      .to contain_exactly(
+        "Sun Jun 30 2024 09:00:00AM -1000",
+        "Sun Jun 30 2024 09:00:15AM -1000",
+        "Sun Jun 30 2024 09:00:30AM -1000"
+      )
+  end
+
+  it "restricts a sub-daily rule to the weekdays and hours that it lists" do
+    schedule.repeat_rrule("FREQ=MINUTELY;INTERVAL=30;BYDAY=MO;BYHOUR=9,10;COUNT=5", time_of_day: { hour: 0 }, duration_in_seconds: 300)
+
+    expect(schedule.occurrences.map { |o| localized_occurrence_starts_at(o) })
+      .to contain_exactly(
+        "Mon Jul  1 2024 09:00AM -1000",
+        "Mon Jul  1 2024 09:30AM -1000",
+        "Mon Jul  1 2024 10:00AM -1000",
+        "Mon Jul  1 2024 10:30AM -1000",
+        "Mon Jul  8 2024 09:00AM -1000"
+      )
+  end
+
+  it "applies the given label to the occurrences" do
+    schedule.repeat_rrule("FREQ=MONTHLY;BYMONTHDAY=15", duration_in_seconds: 300, label: "Payday")
+
+    expect(schedule.occurrences.map(&:label)).to eq(%w[Payday Payday Payday])
+  end
+
+  context "when the rule cannot be represented" do
+    it "raises a Reprise::UnsupportedRRuleError" do
+      expect { schedule.repeat_rrule("FREQ=YEARLY;BYMONTHDAY=15", duration_in_seconds: 300) }
+        .to raise_error(Reprise::UnsupportedRRuleError, /BYMONTHDAY is not supported for FREQ=YEARLY/)
+      expect { schedule.repeat_rrule("FREQ=YEARLY;BYMONTH=1,2;BYMONTHDAY=1;BYSETPOS=1", duration_in_seconds: 300) }
+        .to raise_error(Reprise::UnsupportedRRuleError, /BYSETPOS is not supported/)
+      expect { schedule.repeat_rrule("FREQ=HOURLY;BYHOUR=9,17", duration_in_seconds: 300) }
+        .to raise_error(Reprise::UnsupportedRRuleError, /BYHOUR must list consecutive hours/)
+    end
+  end
+
+  context "when the rule is malformed" do
+    it "raises a Reprise::UnsupportedRRuleError" do
+      expect { schedule.repeat_rrule("FREQ=FORTNIGHTLY", duration_in_seconds: 300) }
+        .to raise_error(Reprise::UnsupportedRRuleError, /"FORTNIGHTLY" is not a valid FREQ/)
+    end
+  end
+end
@@ -0,0 +1,71 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "interval queries far from the start of a series", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 3.years }
+  let(:time_zone) { "Hawaii" }
+
+  let(:window_starts_at) { starts_at + 2.years + 5.months + 3.hours }
+  let(:window_ends_at) { window_starts_at + 45.days }
+
+  let(:series_starts_at) { starts_at + 4.hours }
+
+  before do
+    schedule.repeat_hourly(time_of_day: { minute: 30 }, duration_in_seconds: 60, starts_at: series_starts_at, label: "Hourly")
+    schedule.repeat_daily(time_of_day: { hour: 9 }, duration_in_seconds: 60, starts_at: series_starts_at, label: "Daily")
+    schedule.repeat_weekly(:tuesday, time_of_day: { hour: 10 }, duration_in_seconds: 60, starts_at: series_starts_at, label: "Weekly")
+    schedule.repeat_monthly_by_day(31, time_of_day: { hour: 11 }, duration_in_seconds: 60, starts_at: series_starts_at, label: "MonthlyByDay")
+    schedule.repeat_monthly_by_nth_weekday(:friday, -1, time_of_day: { hour: 12 }, duration_in_seconds: 60, starts_at: series_starts_at, label: "MonthlyByNthWeekday")
+    schedule.repeat_annually_by_day(366, time_of_day: { hour: 13 }, duration_in_seconds: 60, starts_at: series_starts_at, label: "AnnuallyByDay")
+  end
+
+  it "returns the same occurrences as expanding every series from its start" do
+    expected_occurrences = schedule.occurrences.select do |o|
+      o.starts_at >= window_starts_at && o.ends_at <= window_ends_at
+    end
+
+    expect(schedule.occurrences_between(window_starts_at, window_ends_at).map { |o| [o.starts_at, o.label] })
+      .to eq(expected_occurrences.map { |o| [o.starts_at, o.label] })
+    expect(expected_occurrences.map(&:label).uniq)
+      .to contain_exactly("Hourly", "Daily", "Weekly", "MonthlyByDay", "MonthlyByNthWeekday")
+  end
+
+  context "when a series is bounded by a count" do
+    # Counted series are expanded from their start rather than sought, so that the occurrences
+    # before the window still count towards their count.
+    before { schedule.repeat_daily(time_of_day: { hour: 9 }, duration_in_seconds: 60, starts_at: series_starts_at, count: 900, label: "Counted") }
+
+    it "ends the series at its count within the window" do
+      counted_occurrences = schedule.occurrences_between(window_starts_at, window_ends_at).select { |o| o.label == "Counted" }
+
+      expect(counted_occurrences.size).to eq(17)
+      expect(localized_occurrence_starts_at(counted_occurrences.first)).to eq("Mon Nov 30 2026 09:00AM -1000")
+      expect(localized_occurrence_starts_at(counted_occurrences.last)).to eq("Wed Dec 16 2026 09:00AM -1000")
+    end
+  end
+
+  context "when the schedule spans a century" do
+    let(:ends_at) { starts_at + 100.years }
+    let(:window_starts_at) { starts_at + 80.years + 10.minutes }
+    let(:window_ends_at) { window_starts_at + 5.minutes }
+
+    before { schedule.repeat_minutely(duration_in_seconds: 30, starts_at: series_starts_at, label: "Minutely") }
+
+    it "finds the occurrences in the window without expanding the decades before it" do
+      expect(schedule.occurrences_between(window_starts_at, window_ends_at).map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Mon Jun 30 2104 12:10AM -1000",
+          "Mon Jun 30 2104 12:11AM -1000",
+          "Mon Jun 30 2104 12:12AM -1000",
+          "Mon Jun 30 2104 12:13AM -1000",
+          "Mon Jun 30 2104 12:14AM -1000"
+        ])
+    end
+  end
+end
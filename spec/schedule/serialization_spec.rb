@@ -0,0 +1,193 @@
+# frozen_string_literal: true
+
+require "spec_helper"
+
+RSpec.describe "Serialization", aggregate_failures: true do
+  include SeriesHelpers
+
+  subject(:schedule) { Reprise::Schedule.new(starts_at:, ends_at:, time_zone:, business_calendar:) }
+
+  let(:starts_at) { Time.new(2024, 6, 30, 0, 0, 0, "-10:00") }
+  let(:ends_at) { starts_at + 3.months }
+  let(:time_zone) { "Hawaii" }
+  let(:business_calendar) { Reprise::BusinessCalendar.new(holidays: [Date.new(2024, 7, 4)]) }
+
+  before do
+    schedule.repeat_weekly(:tuesday, time_of_day: { hour: 9 }, duration_in_seconds: 30.minutes, label: "Standup")
+    schedule.repeat_monthly_by_nth_weekday(:friday, -1, time_of_day: { hour: 17 }, duration_in_seconds: 1.hour, count: 2)
+    schedule.repeat_monthly_by_weekday(
+      %i[monday tuesday wednesday thursday friday], set_positions: -2, time_of_day: { hour: 8 }, duration_in_seconds: 1.hour
+    )
+    schedule.repeat_hourly(
+      every: 3.hours, window_start: { hour: 9 }, window_end: { hour: 18 }, weekdays: :saturday,
+      time_of_day: { hour: 0 }, duration_in_seconds: 10.minutes
+    )
+    schedule.repeat_business_daily(interval: 2, time_of_day: { hour: 7 }, duration_in_seconds: 15.minutes, count: 10)
+    schedule.repeat_monthly_by_nth_business_day(-1, time_of_day: { hour: 16 }, duration_in_seconds: 1.hour)
+    schedule.repeat_daily(
+      time_of_day: { hour: 12 },
+      duration_in_seconds: 1.hour,
+      starts_at: Time.new(2024, 8, 1, 0, 0, 0, "-10:00"),
+      ends_at: Time.new(2024, 8, 5, 0, 0, 0, "-10:00")
+    )
+    schedule.add_exclusion(starts_at: Time.new(2024, 7, 9, 0, 0, 0, "-10:00"), ends_at: Time.new(2024, 7, 10, 0, 0, 0, "-10:00"))
+  end
+
+  describe "#to_json and .from_json" do
+    it "restores a schedule with the same occurrences" do
+      restored_schedule = Reprise::Schedule.from_json(schedule.to_json)
+
+      expect(restored_schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+        .to eq(schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+    end
+
+    it "restores the bookends and time zone of the schedule" do
+      restored_schedule = Reprise::Schedule.from_json(schedule.to_json)
+      restored_schedule.repeat_daily(time_of_day: { hour: 6 }, duration_in_seconds: 1.minute, label: "New")
+
+      new_occurrences = restored_schedule.occurrences.select { |o| o.label == "New" }
+      expect(new_occurrences.size).to eq(91) # 92 days, less the excluded July 9th
+      expect(localized_occurrence_starts_at(new_occurrences.first)).to eq("Sun Jun 30 2024 06:00AM -1000")
+    end
+
+    it "restores the business calendar of the schedule" do
+      restored_schedule = Reprise::Schedule.from_json(schedule.to_json)
+      restored_schedule.repeat_business_daily(
+        time_of_day: { hour: 6 }, duration_in_seconds: 1.minute, ends_at: Time.new(2024, 7, 8, 0, 0, 0, "-10:00"), label: "New"
+      )
+
+      expect(restored_schedule.occurrences.select { |o| o.label == "New" }.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Mon Jul  1 2024 06:00AM -1000",
+          "Tue Jul  2 2024 06:00AM -1000",
+          "Wed Jul  3 2024 06:00AM -1000",
+          "Fri Jul  5 2024 06:00AM -1000"
+        ])
+    end
+
+    it "restores the holiday calendars of the schedule" do
+      schedule.add_holiday_calendar(Reprise::HolidayCalendar.country(:us))
+      restored_schedule = Reprise::Schedule.from_json(schedule.to_json)
+      restored_schedule.repeat_daily(
+        time_of_day: { hour: 6 }, duration_in_seconds: 1.minute, ends_at: Time.new(2024, 7, 8, 0, 0, 0, "-10:00"), label: "New"
+      )
+
+      expect(restored_schedule.occurrences.select { |o| o.label == "New" }.map { |o| localized_occurrence_starts_at(o) })
+        .to eq([
+          "Sun Jun 30 2024 06:00AM -1000",
+          "Mon Jul  1 2024 06:00AM -1000",
+          "Tue Jul  2 2024 06:00AM -1000",
+          "Wed Jul  3 2024 06:00AM -1000",
+          "Fri Jul  5 2024 06:00AM -1000",
+          "Sat Jul  6 2024 06:00AM -1000",
+          "Sun Jul  7 2024 06:00AM -1000"
+        ])
+    end
+
+    it "restores the derived series of the schedule" do
+      schedule.repeat_relative_to("Standup", offset: -1.day, duration_in_seconds: 5.minutes, label: "Standup reminder")
+      restored_schedule = Reprise::Schedule.from_json(schedule.to_json)
+
+      expect(restored_schedule.occurrences.select { |o| o.label == "Standup reminder" }.map { |o| localized_occurrence_starts_at(o) }.first(2))
+        .to eq([
+          "Mon Jul  1 2024 09:00AM -1000",
+          "Mon Jul 15 2024 09:00AM -1000"
+        ])
+    end
+
+    it "restores the one-off occurrences of the schedule" do
+      schedule.add_occurrence(starts_at: Time.new(2024, 7, 17, 14, 0, 0, "-10:00"), duration_in_seconds: 1.hour, label: "Offsite")
+      restored_schedule = Reprise::Schedule.from_json(schedule.to_json)
+      restored_schedule = Reprise::Schedule.load(restored_schedule.dump)
+
+      expect(restored_schedule.occurrences.select { |o| o.label == "Offsite" }.map { |o| localized_occurrence_start_and_end_time(o) })
+        .to eq(["Wed Jul 17 2024 02:00PM -1000 - Wed Jul 17 2024 03:00PM -1000"])
+    end
+
+    it "records the schema version of the payload" do
+      expect(JSON.parse(schedule.to_json)).to include("version" => 1)
+    end
+
+    context "when the payload was written before weekly series could repeat on several weekdays" do
+      it "restores the weekly series from its single weekday" do
+        payload = JSON.parse(schedule.to_json)
+        weekly_series = payload.dig("schedule", "recurring_series").find { |series| series["kind"] == "weekly" }
+        weekly_series.delete("week_start")
+        weekly_series["weekday"] = weekly_series.delete("weekdays").first
+
+        restored_schedule = Reprise::Schedule.from_json(payload.to_json)
+
+        expect(restored_schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+          .to eq(schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+      end
+    end
+
+    context "when the payload was written before monthly series could repeat on several nth weekdays" do
+      it "restores the monthly series from its single nth weekday" do
+        payload = JSON.parse(schedule.to_json)
+        monthly_series = payload.dig("schedule", "recurring_series").find { |series| series["kind"] == "monthly_by_nth_weekday" }
+        monthly_series["weekday"] = monthly_series.delete("weekdays").first
+        monthly_series["nth_weekday"] = monthly_series.delete("nth_weekdays").first
+
+        restored_schedule = Reprise::Schedule.from_json(payload.to_json)
+
+        expect(restored_schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+          .to eq(schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+      end
+    end
+
+    context "when the payload has an unsupported schema version" do
+      it "raises a Reprise::DeserializationError" do
+        json = JSON.parse(schedule.to_json).merge("version" => 999).to_json
+
+        expect { Reprise::Schedule.from_json(json) }
+          .to raise_error(Reprise::DeserializationError, /Schema version 999 is not supported/)
+      end
+    end
+
+    context "when a series of the payload has no times of day" do
+      it "raises a Reprise::DeserializationError" do
+        payload = JSON.parse(schedule.to_json)
+        payload.dig("schedule", "recurring_series").first["series_options"]["times_of_day"] = []
+
+        expect { Reprise::Schedule.from_json(payload.to_json) }
+          .to raise_error(Reprise::DeserializationError, /Invalid schedule JSON: .*at least one value/)
+      end
+    end
+
+    context "when the payload is not a schedule" do
+      it "raises a Reprise::DeserializationError" do
+        expect { Reprise::Schedule.from_json('{"version": 1, "schedule": {}}') }
+          .to raise_error(Reprise::DeserializationError, /Invalid schedule JSON/)
+      end
+    end
+  end
+
+  describe "#dump and .load" do
+    it "restores a schedule with the same occurrences" do
+      snapshot = schedule.dump
+      restored_schedule = Reprise::Schedule.load(snapshot)
+
+      expect(snapshot.encoding).to eq(Encoding::BINARY)
+      expect(snapshot.bytesize).to be < schedule.to_json.bytesize
+      expect(restored_schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+        .to eq(schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+    end
+
+    it "supports Marshal" do
+      restored_schedule = Marshal.load(Marshal.dump(schedule))
+
+      expect(restored_schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+        .to eq(schedule.occurrences.map { |o| [o.starts_at, o.ends_at, o.label] })
+    end
+
+    context "when the snapshot is invalid" do
+      it "raises a Reprise::DeserializationError" do
+        expect { Reprise::Schedule.load("not a schedule") }
+          .to raise_error(Reprise::DeserializationError, /Invalid schedule snapshot/)
+        expect { Reprise::Schedule.load(schedule.dump.byteslice(0, 20)) }
+          .to raise_error(Reprise::DeserializationError, /Invalid schedule snapshot/)
+      end
+    end
+  end
+end